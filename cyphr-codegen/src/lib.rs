@@ -0,0 +1,303 @@
+//! Generate cyphr structs from an exported Neo4j schema.
+//!
+//! The input is a JSON object holding the rows of three procedures:
+//!
+//! ```json
+//! {
+//!   "nodeTypeProperties": [
+//!     {"nodeLabels": ["User"], "propertyName": "email", "propertyTypes": ["String"], "mandatory": true}
+//!   ],
+//!   "relTypeProperties": [
+//!     {"relType": ":`FOLLOWS`", "propertyName": "since", "propertyTypes": ["Long"], "mandatory": false}
+//!   ],
+//!   "visualization": [
+//!     {"from": "User", "type": "FOLLOWS", "to": "User"}
+//!   ]
+//! }
+//! ```
+//!
+//! The first two arrays are `CALL db.schema.nodeTypeProperties()` and
+//! `CALL db.schema.relTypeProperties()` exported as JSON. The third lists
+//! the relationships of `db.schema.visualization()`:
+//!
+//! ```cypher
+//! CALL db.schema.visualization() YIELD relationships
+//! UNWIND relationships AS r
+//! RETURN startNode(r).name AS from, type(r) AS type, endNode(r).name AS to
+//! ```
+//!
+//! [`generate`] emits one `#[derive(CyphrNode)]` struct per label and one
+//! `#[derive(CyphrRelation)]` struct per relationship type. Properties not
+//! set on every element become `Option`, keys that aren't snake_case get a
+//! `prop` rename, and `from` / `to` come from the visualization when a type
+//! always connects the same labels. Properties stored with mixed types, and
+//! points (the schema doesn't say whether they are 2D or 3D), are left out
+//! with a comment.
+//!
+//! ```rust
+//! let schema = cyphr_codegen::ExportedSchema::from_json(r#"{
+//!     "nodeTypeProperties": [
+//!         {"nodeLabels": ["User"], "propertyName": "createdAt", "propertyTypes": ["DateTime"], "mandatory": false}
+//!     ]
+//! }"#).unwrap();
+//!
+//! let code = cyphr_codegen::generate(&schema);
+//! assert!(code.contains("#[cyphr(prop = \"createdAt\")]"));
+//! assert!(code.contains("pub created_at: Option<chrono::DateTime<chrono::FixedOffset>>,"));
+//! ```
+
+mod names;
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use cyphr_core::ident::unquote_type;
+use serde::Deserialize;
+
+/// The exported schema; see the [crate docs](crate) for the format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedSchema {
+    #[serde(default)]
+    pub node_type_properties: Vec<NodeTypeProperty>,
+    #[serde(default)]
+    pub rel_type_properties: Vec<RelTypeProperty>,
+    #[serde(default)]
+    pub visualization: Vec<SchemaRelationship>,
+}
+
+impl ExportedSchema {
+    /// Parse the JSON export.
+    pub fn from_json(text: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(text)
+    }
+}
+
+/// One row of `db.schema.nodeTypeProperties()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NodeTypeProperty {
+    pub node_labels: Vec<String>,
+    /// `null` for a label combination whose nodes have no properties.
+    pub property_name: Option<String>,
+    pub property_types: Option<Vec<String>>,
+    pub mandatory: bool,
+}
+
+/// One row of `db.schema.relTypeProperties()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RelTypeProperty {
+    /// The type as the procedure reports it, e.g. `` :`FOLLOWS` ``.
+    pub rel_type: String,
+    pub property_name: Option<String>,
+    pub property_types: Option<Vec<String>>,
+    pub mandatory: bool,
+}
+
+/// One relationship of `db.schema.visualization()`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SchemaRelationship {
+    pub from: String,
+    #[serde(rename = "type")]
+    pub rel_type: String,
+    pub to: String,
+}
+
+/// A property of one label or type, merged across rows.
+struct Property {
+    key: String,
+    types: Vec<String>,
+    mandatory: bool,
+}
+
+/// Render Rust source for every label and relationship type in `schema`.
+pub fn generate(schema: &ExportedSchema) -> String {
+    let mut out = String::from("// Generated by cyphr-codegen from an exported Neo4j schema.\n\nuse cyphr::prelude::*;\n");
+    let mut taken: Vec<String> = Vec::new();
+
+    for (label, props) in node_properties(schema) {
+        let name = names::unique(names::pascal(&label), &mut taken);
+        out.push('\n');
+        out.push_str("#[derive(Debug, Clone, CyphrNode)]\n");
+        let _ = writeln!(out, "#[cyphr(label = {label:?})]");
+        write_struct(&mut out, &name, &props);
+    }
+
+    for (rel_type, props) in rel_properties(schema) {
+        let name = names::unique(names::pascal(&rel_type), &mut taken);
+        let mut attrs = format!("type = {rel_type:?}");
+        let pairs = endpoints(schema, &rel_type);
+        if let Some((from, _)) = pairs.first().filter(|_| pairs.iter().all(|(f, _)| *f == pairs[0].0)) {
+            let _ = write!(attrs, ", from = {from:?}");
+        }
+        if let Some((_, to)) = pairs.first().filter(|_| pairs.iter().all(|(_, t)| *t == pairs[0].1)) {
+            let _ = write!(attrs, ", to = {to:?}");
+        }
+        out.push('\n');
+        if pairs.len() > 1 {
+            let listed: Vec<String> = pairs.iter().map(|(f, t)| format!("({f})->({t})")).collect();
+            let _ = writeln!(out, "// Connects {}.", listed.join(", "));
+        }
+        out.push_str("#[derive(Debug, Clone, CyphrRelation)]\n");
+        let _ = writeln!(out, "#[cyphr({attrs})]");
+        write_struct(&mut out, &name, &props);
+    }
+    out
+}
+
+fn write_struct(out: &mut String, name: &str, props: &[Property]) {
+    let mut body = String::new();
+    let mut fields: Vec<String> = Vec::new();
+    for prop in props {
+        let Some(ty) = rust_type(&prop.types) else {
+            let _ = writeln!(body, "    // `{}` skipped: stored as [{}]", prop.key, prop.types.join(", "));
+            if prop.types.iter().any(|t| t.starts_with("Point")) {
+                let _ = writeln!(body, "    // (declare it as Point2D or Point3D to match the stored points)");
+            }
+            continue;
+        };
+        let field = names::unique(names::snake(&prop.key), &mut fields);
+        if field.strip_prefix("r#").unwrap_or(&field) != prop.key {
+            let _ = writeln!(body, "    #[cyphr(prop = {:?})]", prop.key);
+        }
+        if prop.mandatory {
+            let _ = writeln!(body, "    pub {field}: {ty},");
+        } else {
+            let _ = writeln!(body, "    pub {field}: Option<{ty}>,");
+        }
+    }
+    if body.is_empty() {
+        let _ = writeln!(out, "pub struct {name} {{}}");
+    } else {
+        let _ = writeln!(out, "pub struct {name} {{\n{body}}}");
+    }
+}
+
+/// Properties per label, in label order.
+///
+/// A node row covers one combination of labels. A property is mandatory
+/// for a label only if every combination including it has it mandatory.
+fn node_properties(schema: &ExportedSchema) -> BTreeMap<String, Vec<Property>> {
+    let mut combos: BTreeMap<&str, Vec<&Vec<String>>> = BTreeMap::new();
+    for row in &schema.node_type_properties {
+        for label in &row.node_labels {
+            let seen = combos.entry(label).or_default();
+            if !seen.contains(&&row.node_labels) {
+                seen.push(&row.node_labels);
+            }
+        }
+    }
+
+    let mut out = BTreeMap::new();
+    for (label, label_combos) in combos {
+        let rows: Vec<_> = schema
+            .node_type_properties
+            .iter()
+            .filter(|r| r.node_labels.iter().any(|l| l == label))
+            .map(|r| (r.property_name.as_deref(), r.property_types.as_deref(), r.mandatory))
+            .collect();
+        let mut props = merge(&rows);
+        for prop in &mut props {
+            prop.mandatory = prop.mandatory
+                && label_combos.iter().all(|combo| {
+                    schema.node_type_properties.iter().any(|r| {
+                        &r.node_labels == *combo && r.property_name.as_deref() == Some(&prop.key) && r.mandatory
+                    })
+                });
+        }
+        out.insert(label.to_owned(), props);
+    }
+    out
+}
+
+/// Properties per relationship type, in type order.
+fn rel_properties(schema: &ExportedSchema) -> BTreeMap<String, Vec<Property>> {
+    let mut types: Vec<String> = schema.rel_type_properties.iter().map(|r| unquote_type(&r.rel_type)).collect();
+    types.extend(schema.visualization.iter().map(|r| r.rel_type.clone()));
+
+    let mut out = BTreeMap::new();
+    for rel_type in types {
+        if out.contains_key(&rel_type) {
+            continue;
+        }
+        let rows: Vec<_> = schema
+            .rel_type_properties
+            .iter()
+            .filter(|r| unquote_type(&r.rel_type) == rel_type)
+            .map(|r| (r.property_name.as_deref(), r.property_types.as_deref(), r.mandatory))
+            .collect();
+        let props = merge(&rows);
+        out.insert(rel_type, props);
+    }
+    out
+}
+
+/// `(property, types, mandatory)` from either procedure.
+type Row<'a> = (Option<&'a str>, Option<&'a [String]>, bool);
+
+/// Merge rows by property, keeping the order of first appearance.
+fn merge(rows: &[Row<'_>]) -> Vec<Property> {
+    let mut props: Vec<Property> = Vec::new();
+    for &(key, types, mandatory) in rows {
+        let Some(key) = key else { continue };
+        let index = match props.iter().position(|p| p.key == key) {
+            Some(i) => i,
+            None => {
+                props.push(Property { key: key.to_owned(), types: Vec::new(), mandatory: true });
+                props.len() - 1
+            }
+        };
+        let prop = &mut props[index];
+        prop.mandatory &= mandatory;
+        for t in types.unwrap_or_default() {
+            if !prop.types.contains(t) {
+                prop.types.push(t.clone());
+            }
+        }
+    }
+    props
+}
+
+/// Distinct `(from, to)` label pairs for `rel_type`.
+fn endpoints<'a>(schema: &'a ExportedSchema, rel_type: &str) -> Vec<(&'a str, &'a str)> {
+    let mut pairs = Vec::new();
+    for r in schema.visualization.iter().filter(|r| r.rel_type == rel_type) {
+        let pair = (r.from.as_str(), r.to.as_str());
+        if !pairs.contains(&pair) {
+            pairs.push(pair);
+        }
+    }
+    pairs
+}
+
+/// The Rust type for a property stored as `types`, or `None` if the types
+/// are mixed or unknown.
+fn rust_type(types: &[String]) -> Option<String> {
+    let [ty] = types else { return None };
+    if let Some(element) = ty.strip_suffix("Array") {
+        if element == "Byte" {
+            return Some("CyphrBytes".to_owned());
+        }
+        return scalar_type(element).map(|t| format!("Vec<{t}>"));
+    }
+    scalar_type(ty).map(str::to_owned)
+}
+
+/// The Rust type for a single stored type. `Point` has none: the export
+/// doesn't distinguish 2D from 3D points.
+fn scalar_type(ty: &str) -> Option<&'static str> {
+    Some(match ty {
+        "String" => "String",
+        "Long" => "i64",
+        "Double" => "f64",
+        "Boolean" => "bool",
+        "Date" => "chrono::NaiveDate",
+        "LocalTime" => "chrono::NaiveTime",
+        "Time" => "(chrono::NaiveTime, chrono::FixedOffset)",
+        "LocalDateTime" => "chrono::NaiveDateTime",
+        "DateTime" => "chrono::DateTime<chrono::FixedOffset>",
+        "Duration" => "std::time::Duration",
+        _ => return None,
+    })
+}
@@ -0,0 +1,52 @@
+//! `cyphr-codegen [SCHEMA.json] [-o OUTPUT.rs]`
+//!
+//! Reads the exported schema from the file, or stdin if omitted or `-`, and
+//! writes the generated structs to `OUTPUT.rs` or stdout.
+
+use std::io::{Read, Write};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: cyphr-codegen [SCHEMA.json] [-o OUTPUT.rs]";
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("cyphr-codegen: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), String> {
+    let mut input: Option<String> = None;
+    let mut output: Option<String> = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            "-o" | "--output" => output = Some(args.next().ok_or(USAGE)?),
+            _ if input.is_none() => input = Some(arg),
+            _ => return Err(USAGE.to_owned()),
+        }
+    }
+
+    let text = match input.as_deref() {
+        None | Some("-") => {
+            let mut text = String::new();
+            std::io::stdin().read_to_string(&mut text).map_err(|e| format!("cannot read stdin: {e}"))?;
+            text
+        }
+        Some(path) => std::fs::read_to_string(path).map_err(|e| format!("cannot read `{path}`: {e}"))?,
+    };
+    let schema = cyphr_codegen::ExportedSchema::from_json(&text).map_err(|e| format!("invalid schema JSON: {e}"))?;
+    let code = cyphr_codegen::generate(&schema);
+
+    match output {
+        Some(path) => std::fs::write(&path, code).map_err(|e| format!("cannot write `{path}`: {e}")),
+        None => std::io::stdout().write_all(code.as_bytes()).map_err(|e| format!("cannot write stdout: {e}")),
+    }
+}
@@ -0,0 +1,80 @@
+//! Turning labels, types and property keys into Rust identifiers.
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "static", "struct",
+    "trait", "true", "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "gen",
+    "macro", "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// Words of `name`, split at non-alphanumerics and lower-to-upper case
+/// changes; `HTTPStatus` splits as `HTTP`, `Status`.
+fn words(name: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let chars: Vec<char> = name.chars().collect();
+    let mut current = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(i + 1);
+        let boundary = c.is_uppercase()
+            && prev.is_some_and(|p| p.is_lowercase() || p.is_numeric()
+                || (p.is_uppercase() && next.is_some_and(|n| n.is_lowercase())));
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// `WROTE_POST` / `blog post` → `WrotePost` / `BlogPost`.
+pub(crate) fn pascal(name: &str) -> String {
+    let mut out: String = words(name)
+        .iter()
+        .map(|w| {
+            let mut chars = w.chars();
+            let first = chars.next().map(|c| c.to_uppercase().collect::<String>()).unwrap_or_default();
+            first + &chars.as_str().to_lowercase()
+        })
+        .collect();
+    if out.is_empty() || out.starts_with(|c: char| c.is_numeric()) {
+        out.insert(0, 'T');
+    }
+    out
+}
+
+/// `createdAt` / `HTTPStatus` → `created_at` / `http_status`; keywords get
+/// the `r#` prefix.
+pub(crate) fn snake(key: &str) -> String {
+    let mut out = words(key).iter().map(|w| w.to_lowercase()).collect::<Vec<_>>().join("_");
+    if out.is_empty() || out.starts_with(|c: char| c.is_numeric()) {
+        out.insert(0, '_');
+    }
+    match out.as_str() {
+        "self" | "super" | "crate" => out.push('_'),
+        _ if KEYWORDS.contains(&out.as_str()) => out.insert_str(0, "r#"),
+        _ => {}
+    }
+    out
+}
+
+/// `name`, or `name2`, `name3`, ... if already in `taken`.
+pub(crate) fn unique(name: String, taken: &mut Vec<String>) -> String {
+    let mut candidate = name.clone();
+    let mut n = 2;
+    while taken.contains(&candidate) {
+        candidate = format!("{name}{n}");
+        n += 1;
+    }
+    taken.push(candidate.clone());
+    candidate
+}
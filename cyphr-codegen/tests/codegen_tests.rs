@@ -0,0 +1,131 @@
+use cyphr_codegen::{generate, ExportedSchema};
+
+fn fixture() -> ExportedSchema {
+    ExportedSchema::from_json(include_str!("schema.json")).unwrap()
+}
+
+fn node(labels: &[&str], property: &str, types: &[&str], mandatory: bool) -> String {
+    format!(
+        r#"{{"nodeLabels": {labels:?}, "propertyName": {property:?}, "propertyTypes": {types:?}, "mandatory": {mandatory}}}"#
+    )
+}
+
+fn generate_nodes(rows: &[String]) -> String {
+    let json = format!(r#"{{"nodeTypeProperties": [{}]}}"#, rows.join(", "));
+    generate(&ExportedSchema::from_json(&json).unwrap())
+}
+
+#[test]
+fn test_generate_fixture() {
+    assert_eq!(generate(&fixture()), r#"// Generated by cyphr-codegen from an exported Neo4j schema.
+
+use cyphr::prelude::*;
+
+#[derive(Debug, Clone, CyphrNode)]
+#[cyphr(label = "Admin")]
+pub struct Admin {
+    pub email: String,
+    pub level: i64,
+}
+
+#[derive(Debug, Clone, CyphrNode)]
+#[cyphr(label = "Blog Post")]
+pub struct BlogPost {
+    pub title: String,
+    pub tags: Vec<String>,
+    pub r#type: String,
+    // `score` skipped: stored as [Long, Double]
+}
+
+#[derive(Debug, Clone, CyphrNode)]
+#[cyphr(label = "Tag")]
+pub struct Tag {}
+
+#[derive(Debug, Clone, CyphrNode)]
+#[cyphr(label = "User")]
+pub struct User {
+    pub email: String,
+    #[cyphr(prop = "createdAt")]
+    pub created_at: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub nickname: Option<String>,
+    pub level: Option<i64>,
+}
+
+#[derive(Debug, Clone, CyphrRelation)]
+#[cyphr(type = "FOLLOWS", from = "User", to = "User")]
+pub struct Follows {
+    pub since: Option<chrono::NaiveDate>,
+}
+
+// Connects (Blog Post)->(Tag), (User)->(Tag).
+#[derive(Debug, Clone, CyphrRelation)]
+#[cyphr(type = "TAGGED", to = "Tag")]
+pub struct Tagged {}
+
+#[derive(Debug, Clone, CyphrRelation)]
+#[cyphr(type = "WROTE", from = "User", to = "Blog Post")]
+pub struct Wrote {}
+"#);
+}
+
+#[test]
+fn test_property_renames() {
+    let code = generate_nodes(&[
+        node(&["Item"], "HTTPStatus", &["Long"], true),
+        node(&["Item"], "first name", &["String"], true),
+        node(&["Item"], "self", &["Boolean"], true),
+        node(&["Item"], "2fa", &["Boolean"], true),
+        node(&["Item"], "already_snake", &["String"], true),
+    ]);
+    assert!(code.contains("    #[cyphr(prop = \"HTTPStatus\")]\n    pub http_status: i64,\n"));
+    assert!(code.contains("    #[cyphr(prop = \"first name\")]\n    pub first_name: String,\n"));
+    assert!(code.contains("    #[cyphr(prop = \"self\")]\n    pub self_: bool,\n"));
+    assert!(code.contains("    #[cyphr(prop = \"2fa\")]\n    pub _2fa: bool,\n"));
+    assert!(code.contains("    pub already_snake: String,\n"));
+    assert!(!code.contains("prop = \"already_snake\""));
+}
+
+#[test]
+fn test_field_name_collision() {
+    let code = generate_nodes(&[
+        node(&["Item"], "createdAt", &["Long"], true),
+        node(&["Item"], "created_at", &["Long"], true),
+    ]);
+    assert!(code.contains("    pub created_at: i64,\n    #[cyphr(prop = \"created_at\")]\n    pub created_at2: i64,\n"));
+}
+
+#[test]
+fn test_type_mapping() {
+    let code = generate_nodes(&[
+        node(&["Item"], "a", &["Double"], true),
+        node(&["Item"], "b", &["LocalDateTime"], true),
+        node(&["Item"], "c", &["Point"], true),
+        node(&["Item"], "d", &["ByteArray"], true),
+        node(&["Item"], "e", &["LongArray"], true),
+        node(&["Item"], "f", &["Duration"], true),
+        node(&["Item"], "g", &["Map"], true),
+    ]);
+    assert!(code.contains("pub a: f64,"));
+    assert!(code.contains("pub b: chrono::NaiveDateTime,"));
+    assert!(code.contains("// `c` skipped: stored as [Point]\n    // (declare it as Point2D or Point3D"));
+    assert!(code.contains("pub d: CyphrBytes,"));
+    assert!(code.contains("pub e: Vec<i64>,"));
+    assert!(code.contains("pub f: std::time::Duration,"));
+    assert!(code.contains("// `g` skipped: stored as [Map]"));
+}
+
+#[test]
+fn test_struct_name_collision() {
+    let json = r#"{
+        "nodeTypeProperties": [{"nodeLabels": ["Follows"], "propertyName": null, "propertyTypes": null, "mandatory": false}],
+        "visualization": [{"from": "Follows", "type": "FOLLOWS", "to": "Follows"}]
+    }"#;
+    let code = generate(&ExportedSchema::from_json(json).unwrap());
+    assert!(code.contains("#[cyphr(label = \"Follows\")]\npub struct Follows {}"));
+    assert!(code.contains("#[cyphr(type = \"FOLLOWS\", from = \"Follows\", to = \"Follows\")]\npub struct Follows2 {}"));
+}
+
+#[test]
+fn test_invalid_json() {
+    assert!(ExportedSchema::from_json(r#"{"nodeTypeProperties": 3}"#).is_err());
+}
@@ -25,6 +25,31 @@ pub enum CyphrError {
     #[error("missing field '{field}' on {struct_name}")]
     MissingField { field: String, struct_name: String },
 
+    /// A query that must return a row returned none.
+    #[error("query returned no rows")]
+    NoRows,
+
+    /// A query that must return at most one row returned more.
+    #[error("expected at most one row, got {count}")]
+    TooManyRows { count: usize },
+
+    /// Query placeholders and bound parameters do not line up.
+    ///
+    /// `missing` lists `$placeholders` with no bound value; `unused` lists
+    /// bound parameters that never appear in the query text.
+    #[error("parameter mismatch: missing [{}], unused [{}]", .missing.join(", "), .unused.join(", "))]
+    ParamMismatch { missing: Vec<String>, unused: Vec<String> },
+
+    /// A label, relationship type or property key cannot be used as a
+    /// Cypher identifier.
+    #[error("invalid identifier {name:?}: {reason}")]
+    InvalidIdent { name: String, reason: String },
+
+    /// Migrations could not be loaded or applied, e.g. because an applied
+    /// migration's checksum no longer matches its file.
+    #[error("migration error: {0}")]
+    Migration(String),
+
     /// A `BoltType` variant did not match the expected Rust type.
     #[error("type mismatch: expected {expected}, got {got} ({context})")]
     TypeMismatch {
@@ -74,6 +99,14 @@ impl CyphrError {
         }
     }
 
+    /// Create an [`InvalidIdent`](CyphrError::InvalidIdent) error.
+    pub fn invalid_ident(name: &str, reason: &str) -> Self {
+        CyphrError::InvalidIdent {
+            name: name.to_owned(),
+            reason: reason.to_owned(),
+        }
+    }
+
     /// Wrap this error with additional context, producing a [`Context`](CyphrError::Context) variant.
     ///
     /// The derive macros call this automatically to annotate errors with the
@@ -0,0 +1,49 @@
+//! Quoting names for use as labels, relationship types and property keys.
+//!
+//! The validated wrapper lives in `cyphr::ident`; these helpers are shared
+//! with code that renders Cypher from derive metadata, such as
+//! [`SchemaDef`](crate::schema::SchemaDef).
+
+use std::borrow::Cow;
+
+/// Whether `name` can be written without backticks: a letter or `_`
+/// followed by letters, ASCII digits and `_`.
+pub fn is_plain(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c == '_' || c.is_alphabetic())
+        && chars.all(|c| c == '_' || c.is_alphabetic() || c.is_ascii_digit())
+}
+
+/// `name` as it must appear in Cypher text, backtick-quoted with embedded
+/// backticks doubled if it is not [plain](is_plain).
+///
+/// ```rust
+/// # use cyphr_core::ident::escape;
+/// assert_eq!(escape("User"), "User");
+/// assert_eq!(escape("Blog Post"), "`Blog Post`");
+/// assert_eq!(escape("a`b"), "`a``b`");
+/// ```
+pub fn escape(name: &str) -> Cow<'_, str> {
+    if is_plain(name) {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(format!("`{}`", name.replace('`', "``")))
+    }
+}
+
+/// A relationship type as `db.schema.relTypeProperties()` reports it, with
+/// the leading `:` and any backticks removed.
+///
+/// ```rust
+/// # use cyphr_core::ident::unquote_type;
+/// assert_eq!(unquote_type(":`FOLLOWS`"), "FOLLOWS");
+/// assert_eq!(unquote_type(":`a``b`"), "a`b");
+/// assert_eq!(unquote_type("LIKES"), "LIKES");
+/// ```
+pub fn unquote_type(rel_type: &str) -> String {
+    let name = rel_type.strip_prefix(':').unwrap_or(rel_type);
+    match name.strip_prefix('`').and_then(|n| n.strip_suffix('`')) {
+        Some(quoted) => quoted.replace("``", "`"),
+        None => name.to_owned(),
+    }
+}
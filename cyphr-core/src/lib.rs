@@ -10,7 +10,12 @@ pub mod error;
 pub mod value;
 pub mod record;
 pub mod props;
+pub mod ident;
+pub mod schema;
+pub mod subgraph;
 
 pub use error::CyphrError;
-pub use value::{Point2D, Point3D, CyphrBytes, CyphrPath};
+pub use value::{Point2D, Point3D, CyphrBytes, CyphrPath, GraphNode, PathDirection, Raw, Segment, Triple};
+pub use record::CyphrRow;
+pub use subgraph::{Edge, Subgraph};
 pub use traits::{IntoCyphrValue, ToCyphrParams};
@@ -19,3 +19,20 @@ pub fn node_prop(node: &neo4rs::BoltNode, key: &str) -> Option<Value> {
 pub fn rel_prop(rel: &neo4rs::BoltRelation, key: &str) -> Option<Value> {
     rel.get::<Value>(key).ok()
 }
+
+/// The labels of a [`BoltNode`](neo4rs::BoltNode), in the order the server
+/// sent them.
+pub fn node_labels(node: &neo4rs::BoltNode) -> Vec<String> {
+    node.labels
+        .iter()
+        .filter_map(|label| match label {
+            Value::String(s) => Some(s.value.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether a [`BoltNode`](neo4rs::BoltNode) has `label`.
+pub fn has_label(node: &neo4rs::BoltNode, label: &str) -> bool {
+    node.labels.iter().any(|l| matches!(l, Value::String(s) if s.value == label))
+}
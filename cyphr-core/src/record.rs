@@ -1,10 +1,12 @@
-
 //! Centralized row/record access for Neo4j query results.
 //!
 //! These functions isolate the `neo4rs::Row` API to a single location so
 //! that derive-macro generated code only depends on this module.
 
+use std::collections::HashMap;
 use neo4rs::{BoltType as Value, Row as Record};
+use crate::error::CyphrError;
+use crate::traits::{FromCyphr, FromCyphrValue};
 
 /// Read a value from a [`Row`](neo4rs::Row) by column name.
 ///
@@ -17,3 +19,175 @@ pub fn get_value(record: &Record, key: &str) -> Option<Value> {
 pub fn has_key(record: &Record, key: &str) -> bool {
     get_value(record, key).is_some()
 }
+
+/// Read every column of a [`Row`](neo4rs::Row) as `(name, value)` pairs.
+///
+/// `neo4rs::Row` does not retain the server's column order, so the pairs
+/// are returned sorted by column name. Returns [`CyphrError::Mapping`] if
+/// the row cannot be read as a map of columns.
+pub fn entries(record: &Record) -> Result<Vec<(String, Value)>, CyphrError> {
+    let map: neo4rs::BoltMap = record
+        .to()
+        .map_err(|e| CyphrError::Mapping(format!("cannot read row columns: {e}")))?;
+    let mut out: Vec<(String, Value)> = map
+        .value
+        .into_iter()
+        .map(|(k, v)| (k.value, v))
+        .collect();
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(out)
+}
+
+/// Read the only column of a [`Row`](neo4rs::Row) as a `(name, value)` pair.
+///
+/// Returns [`CyphrError::Mapping`] unless the row has exactly one column.
+pub fn single_entry(record: &Record) -> Result<(String, Value), CyphrError> {
+    let mut all = entries(record)?;
+    if all.len() != 1 {
+        return Err(CyphrError::Mapping(format!(
+            "expected a single column, got {}",
+            all.len()
+        )));
+    }
+    Ok(all.remove(0))
+}
+
+/// A dynamically-typed result row with column-aware conversions.
+///
+/// Use this for ad-hoc queries where defining a [`FromCyphr`] struct is not
+/// worth it. Every accessor reports failures with the column name attached,
+/// so errors read like `column 'age': type mismatch: ...`.
+///
+/// Columns read from a `neo4rs::Row` are ordered by name (see [`entries`]);
+/// rows built via [`FromIterator`] keep the order they were given in.
+///
+/// There is no positional accessor such as `get_at(0)`. `neo4rs` keeps a
+/// row's columns in a map, so the order of the `RETURN` clause is lost
+/// before the row reaches this type, and an index would silently refer to
+/// a different column than the query suggests. Read columns by name.
+///
+/// # Example
+///
+/// ```rust
+/// # use cyphr_core::record::CyphrRow;
+/// # use neo4rs::BoltType;
+/// let row: CyphrRow = vec![
+///     ("name".to_string(), BoltType::from("Alice")),
+///     ("age".to_string(), BoltType::from(30)),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let name: String = row.get("name").unwrap();
+/// let age: i64 = row.get("age").unwrap();
+/// let email: Option<String> = row.try_get("email").unwrap();
+/// assert_eq!((name.as_str(), age, email), ("Alice", 30, None));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CyphrRow {
+    columns: Vec<String>,
+    values: Vec<Value>,
+}
+
+impl CyphrRow {
+    /// Build a row from a `neo4rs::Row`, with columns ordered by name.
+    pub fn from_row(record: &Record) -> Result<Self, CyphrError> {
+        Ok(entries(record)?.into_iter().collect())
+    }
+
+    /// Column names, in row order.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Number of columns.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Whether the row has no columns.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Check whether a column exists in the row.
+    pub fn contains(&self, column: &str) -> bool {
+        self.position(column).is_some()
+    }
+
+    /// Borrow the raw value of a column.
+    pub fn value(&self, column: &str) -> Option<&Value> {
+        self.position(column).map(|i| &self.values[i])
+    }
+
+    /// Convert a column to `T`.
+    ///
+    /// Returns [`CyphrError::MissingField`] if the column does not exist.
+    pub fn get<T: FromCyphrValue>(&self, column: &str) -> Result<T, CyphrError> {
+        self.try_get(column)?
+            .ok_or_else(|| CyphrError::missing_field(column, "CyphrRow"))
+    }
+
+    /// Convert a column to `T`, returning `Ok(None)` if the column does not
+    /// exist.
+    ///
+    /// A present column that fails to convert is still an error.
+    pub fn try_get<T: FromCyphrValue>(&self, column: &str) -> Result<Option<T>, CyphrError> {
+        match self.value(column) {
+            None => Ok(None),
+            Some(v) => T::from_value(v.clone())
+                .map(Some)
+                .map_err(|e| e.with_context(format!("column '{column}'"))),
+        }
+    }
+
+    /// Iterate over `(column, value)` pairs in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.columns.iter().map(String::as_str).zip(self.values.iter())
+    }
+
+    /// Convert the row into a map of column name → raw value.
+    pub fn into_map(self) -> HashMap<String, Value> {
+        self.into_iter().collect()
+    }
+
+    fn position(&self, column: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c == column)
+    }
+}
+
+impl FromIterator<(String, Value)> for CyphrRow {
+    fn from_iter<I: IntoIterator<Item = (String, Value)>>(iter: I) -> Self {
+        let (columns, values) = iter.into_iter().unzip();
+        CyphrRow { columns, values }
+    }
+}
+
+impl IntoIterator for CyphrRow {
+    type Item = (String, Value);
+    type IntoIter = std::iter::Zip<std::vec::IntoIter<String>, std::vec::IntoIter<Value>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.columns.into_iter().zip(self.values)
+    }
+}
+
+impl From<CyphrRow> for HashMap<String, Value> {
+    fn from(row: CyphrRow) -> Self {
+        row.into_map()
+    }
+}
+
+impl TryFrom<&Record> for CyphrRow {
+    type Error = CyphrError;
+
+    fn try_from(record: &Record) -> Result<Self, CyphrError> {
+        CyphrRow::from_row(record)
+    }
+}
+
+impl FromCyphr for CyphrRow {
+    fn from_record(record: &Record) -> Result<Self, CyphrError> {
+        CyphrRow::from_row(record)
+    }
+}
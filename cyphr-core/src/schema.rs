@@ -0,0 +1,169 @@
+//! Constraint and index definitions for a node label or relationship type.
+//!
+//! `#[derive(CyphrNode)]` and `#[derive(CyphrRelation)]` build a
+//! [`SchemaDef`] from the `unique`, `index`, `required` and `fulltext`
+//! field attributes; it is returned by `CyphrNode::schema()` /
+//! `CyphrRelation::schema()`. [`SchemaDef::statements`] renders idempotent
+//! `CREATE ... IF NOT EXISTS` commands that can be run on every start-up.
+//!
+//! The derives also list every property with the Neo4j type its field
+//! maps to, so the definition can be checked against a live database.
+//!
+//! ```rust
+//! use cyphr_core::schema::SchemaDef;
+//!
+//! let def = SchemaDef::node("User")
+//!     .unique("email")
+//!     .index("name")
+//!     .fulltext("user_search", "name")
+//!     .fulltext("user_search", "bio");
+//!
+//! assert_eq!(def.statements(), [
+//!     "CREATE CONSTRAINT User_email_unique IF NOT EXISTS FOR (n:User) REQUIRE n.email IS UNIQUE",
+//!     "CREATE INDEX User_name_index IF NOT EXISTS FOR (n:User) ON (n.name)",
+//!     "CREATE FULLTEXT INDEX user_search IF NOT EXISTS FOR (n:User) ON EACH [n.name, n.bio]",
+//! ]);
+//! ```
+
+use crate::ident::escape;
+
+/// Whether a [`SchemaDef`] applies to nodes or relationships.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchemaTarget {
+    /// Nodes with a label.
+    Node,
+    /// Relationships of a type.
+    Relationship,
+}
+
+/// The kind of a [`SchemaItem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchemaKind {
+    /// `REQUIRE x.p IS UNIQUE`, from `#[cyphr(unique)]`.
+    Unique,
+    /// `REQUIRE x.p IS NOT NULL`, from `#[cyphr(required)]`.
+    Required,
+    /// A range index, from `#[cyphr(index)]`.
+    Index,
+    /// A full-text index, from `#[cyphr(fulltext = "name")]`.
+    Fulltext,
+}
+
+/// One constraint or index.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SchemaItem {
+    /// What to create.
+    pub kind: SchemaKind,
+    /// The constraint or index name. Generated as `{label}_{prop}_{kind}`
+    /// except for full-text indexes, which are named explicitly.
+    pub name: String,
+    /// Property keys covered; several only for full-text indexes.
+    pub properties: Vec<String>,
+}
+
+/// A declared property.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PropertyDef {
+    /// The property key.
+    pub name: String,
+    /// The type as `db.schema.nodeTypeProperties()` reports it (`"String"`,
+    /// `"Long"`, `"StringArray"`, ...), or `None` if it can't be inferred.
+    pub neo4j_type: Option<String>,
+    /// Whether the property may be absent (an `Option` field).
+    pub optional: bool,
+}
+
+/// The constraints and indexes declared for one label or relationship type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaDef {
+    /// Node or relationship.
+    pub target: SchemaTarget,
+    /// The label or relationship type.
+    pub name: String,
+    /// Properties, in field order.
+    pub properties: Vec<PropertyDef>,
+    /// Constraints and indexes, in declaration order.
+    pub items: Vec<SchemaItem>,
+}
+
+impl SchemaDef {
+    /// An empty definition for nodes labelled `label`.
+    pub fn node(label: impl Into<String>) -> Self {
+        Self { target: SchemaTarget::Node, name: label.into(), properties: Vec::new(), items: Vec::new() }
+    }
+
+    /// An empty definition for relationships of type `rel_type`.
+    pub fn relationship(rel_type: impl Into<String>) -> Self {
+        Self { target: SchemaTarget::Relationship, name: rel_type.into(), properties: Vec::new(), items: Vec::new() }
+    }
+
+    /// Declare a property and the Neo4j type it holds, if known.
+    pub fn property(mut self, name: impl Into<String>, neo4j_type: Option<&str>, optional: bool) -> Self {
+        self.properties.push(PropertyDef {
+            name: name.into(),
+            neo4j_type: neo4j_type.map(str::to_owned),
+            optional,
+        });
+        self
+    }
+
+    /// Add a uniqueness constraint on `prop`.
+    pub fn unique(self, prop: impl Into<String>) -> Self {
+        self.single(SchemaKind::Unique, "unique", prop.into())
+    }
+
+    /// Add an existence constraint on `prop`.
+    pub fn required(self, prop: impl Into<String>) -> Self {
+        self.single(SchemaKind::Required, "required", prop.into())
+    }
+
+    /// Add a range index on `prop`.
+    pub fn index(self, prop: impl Into<String>) -> Self {
+        self.single(SchemaKind::Index, "index", prop.into())
+    }
+
+    /// Add `prop` to the full-text index `name`, creating it if needed.
+    pub fn fulltext(mut self, name: impl Into<String>, prop: impl Into<String>) -> Self {
+        let name = name.into();
+        let prop = prop.into();
+        match self.items.iter_mut().find(|i| i.kind == SchemaKind::Fulltext && i.name == name) {
+            Some(item) => item.properties.push(prop),
+            None => self.items.push(SchemaItem { kind: SchemaKind::Fulltext, name, properties: vec![prop] }),
+        }
+        self
+    }
+
+    fn single(mut self, kind: SchemaKind, suffix: &str, prop: String) -> Self {
+        let name = format!("{}_{prop}_{suffix}", self.name);
+        self.items.push(SchemaItem { kind, name, properties: vec![prop] });
+        self
+    }
+
+    /// `CREATE CONSTRAINT` / `CREATE INDEX` commands for every item, each
+    /// with `IF NOT EXISTS` so they can be re-run safely.
+    pub fn statements(&self) -> Vec<String> {
+        self.items.iter().map(|item| self.statement(item)).collect()
+    }
+
+    fn statement(&self, item: &SchemaItem) -> String {
+        let (var, pattern) = match self.target {
+            SchemaTarget::Node => ("n", format!("(n:{})", escape(&self.name))),
+            SchemaTarget::Relationship => ("r", format!("()-[r:{}]-()", escape(&self.name))),
+        };
+        let props: Vec<String> = item.properties.iter().map(|p| format!("{var}.{}", escape(p))).collect();
+        let name = escape(&item.name);
+        match item.kind {
+            SchemaKind::Unique => {
+                format!("CREATE CONSTRAINT {name} IF NOT EXISTS FOR {pattern} REQUIRE {} IS UNIQUE", props[0])
+            }
+            SchemaKind::Required => {
+                format!("CREATE CONSTRAINT {name} IF NOT EXISTS FOR {pattern} REQUIRE {} IS NOT NULL", props[0])
+            }
+            SchemaKind::Index => format!("CREATE INDEX {name} IF NOT EXISTS FOR {pattern} ON ({})", props[0]),
+            SchemaKind::Fulltext => format!(
+                "CREATE FULLTEXT INDEX {name} IF NOT EXISTS FOR {pattern} ON EACH [{}]",
+                props.join(", "),
+            ),
+        }
+    }
+}
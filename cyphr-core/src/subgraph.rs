@@ -0,0 +1,288 @@
+//! Nodes and relationships gathered from many rows, without duplicates.
+//!
+//! A [`Subgraph`] is built from whatever graph elements a row holds: nodes,
+//! relationships, paths, and lists of them (`collect(n)`). Each element is
+//! kept once, by the id the server sends with it, so overlapping paths or
+//! repeated `collect`s don't need deduplicating by hand.
+//!
+//! That id is the legacy integer `id` (`id(n)` in Cypher), the only one
+//! neo4rs 0.8 exposes; element ids aren't available. The server may reuse
+//! the integer id of a deleted element, so only merge rows read within one
+//! transaction, or with no deletes in between.
+//!
+//! Every node must decode as `N` and every relationship as `R`. One that
+//! doesn't, such as a node with another label in a `Subgraph<User>`, fails
+//! the whole row. Use [`GraphNode`](crate::value::GraphNode) and
+//! [`Raw`] to accept anything, or an enum of node types deriving
+//! `CyphrNode` to accept a known set.
+//!
+//! `Subgraph` implements [`FromCyphr`] for a single row and [`Extend`] /
+//! [`FromIterator`] to merge rows, so a whole stream can be gathered with
+//! `collect_into`:
+//!
+//! ```rust,ignore
+//! use cyphr::prelude::*;
+//!
+//! let graph_view: Subgraph<GraphNode> =
+//!     query("MATCH p = (:User {id: $id})-[:FOLLOWS*..2]->() RETURN p")
+//!         .param("id", 1)
+//!         .fetch_stream::<Subgraph<GraphNode>>(&graph)
+//!         .await?
+//!         .collect_into()
+//!         .await?;
+//!
+//! for (id, node) in graph_view.nodes() {
+//!     let degree = graph_view.out_edges(id).count();
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use crate::error::CyphrError;
+use crate::record::entries;
+use crate::traits::{FromCyphr, FromCyphrNode, FromCyphrRel, FromCyphrValue};
+use crate::value::{type_name, BoundPath, Raw};
+
+/// A relationship in a [`Subgraph`], with the ids of the nodes it joins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge<R> {
+    /// The relationship's id.
+    pub id: i64,
+    /// The id of its start node.
+    pub start: i64,
+    /// The id of its end node.
+    pub end: i64,
+    /// The decoded relationship.
+    pub rel: R,
+}
+
+/// Nodes and relationships deduplicated by id, with adjacency lookups.
+///
+/// Nodes decode as `N` and relationships as `R` (raw by default). Elements
+/// keep the order they were first seen in.
+#[derive(Debug, Clone)]
+pub struct Subgraph<N, R = Raw> {
+    nodes: Vec<(i64, N)>,
+    node_index: HashMap<i64, usize>,
+    edges: Vec<Edge<R>>,
+    edge_index: HashMap<i64, usize>,
+    outgoing: HashMap<i64, Vec<usize>>,
+    incoming: HashMap<i64, Vec<usize>>,
+}
+
+impl<N, R> Default for Subgraph<N, R> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            node_index: HashMap::new(),
+            edges: Vec::new(),
+            edge_index: HashMap::new(),
+            outgoing: HashMap::new(),
+            incoming: HashMap::new(),
+        }
+    }
+}
+
+impl<N, R> Subgraph<N, R> {
+    /// An empty subgraph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every node with its id.
+    pub fn nodes(&self) -> impl Iterator<Item = (i64, &N)> {
+        self.nodes.iter().map(|(id, node)| (*id, node))
+    }
+
+    /// Every relationship.
+    pub fn edges(&self) -> impl Iterator<Item = &Edge<R>> {
+        self.edges.iter()
+    }
+
+    /// The node with id `id`.
+    pub fn node(&self, id: i64) -> Option<&N> {
+        self.node_index.get(&id).map(|&i| &self.nodes[i].1)
+    }
+
+    /// The relationship with id `id`.
+    pub fn edge(&self, id: i64) -> Option<&Edge<R>> {
+        self.edge_index.get(&id).map(|&i| &self.edges[i])
+    }
+
+    /// The number of distinct nodes.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The number of distinct relationships.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Whether the subgraph has no nodes and no relationships.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.edges.is_empty()
+    }
+
+    /// Relationships starting at node `id`.
+    pub fn out_edges(&self, id: i64) -> impl Iterator<Item = &Edge<R>> {
+        self.adjacent(&self.outgoing, id)
+    }
+
+    /// Relationships ending at node `id`.
+    pub fn in_edges(&self, id: i64) -> impl Iterator<Item = &Edge<R>> {
+        self.adjacent(&self.incoming, id)
+    }
+
+    /// Nodes joined to node `id` by a relationship in either direction,
+    /// each once. Neighbors missing from the subgraph are skipped.
+    pub fn neighbors(&self, id: i64) -> impl Iterator<Item = (i64, &N)> {
+        let mut seen = Vec::new();
+        self.out_edges(id)
+            .map(|e| e.end)
+            .chain(self.in_edges(id).map(|e| e.start))
+            .filter_map(move |other| {
+                if seen.contains(&other) {
+                    return None;
+                }
+                seen.push(other);
+                self.node(other).map(|node| (other, node))
+            })
+    }
+
+    fn adjacent<'a>(&'a self, map: &'a HashMap<i64, Vec<usize>>, id: i64) -> impl Iterator<Item = &'a Edge<R>> {
+        map.get(&id).into_iter().flatten().map(|&i| &self.edges[i])
+    }
+
+    /// Add `node` unless a node with `id` is already present. Returns
+    /// whether it was added.
+    pub fn insert_node(&mut self, id: i64, node: N) -> bool {
+        if self.node_index.contains_key(&id) {
+            return false;
+        }
+        self.node_index.insert(id, self.nodes.len());
+        self.nodes.push((id, node));
+        true
+    }
+
+    /// Add `edge` unless a relationship with its id is already present.
+    /// Returns whether it was added.
+    pub fn insert_edge(&mut self, edge: Edge<R>) -> bool {
+        if self.edge_index.contains_key(&edge.id) {
+            return false;
+        }
+        let index = self.edges.len();
+        self.edge_index.insert(edge.id, index);
+        self.outgoing.entry(edge.start).or_default().push(index);
+        self.incoming.entry(edge.end).or_default().push(index);
+        self.edges.push(edge);
+        true
+    }
+
+    /// Add every node and relationship of `other` not already present.
+    pub fn merge(&mut self, other: Subgraph<N, R>) {
+        for (id, node) in other.nodes {
+            self.insert_node(id, node);
+        }
+        for edge in other.edges {
+            self.insert_edge(edge);
+        }
+    }
+}
+
+impl<N: FromCyphrNode, R: FromCyphrRel> Subgraph<N, R> {
+    /// Add the graph elements in `value`: a node, a relationship, a path,
+    /// or a list of these. Other values, including nulls, are skipped.
+    ///
+    /// Elements already present are not decoded again.
+    pub fn add_value(&mut self, value: neo4rs::BoltType) -> Result<(), CyphrError> {
+        match value {
+            neo4rs::BoltType::Node(n) => self.add_node(&n),
+            neo4rs::BoltType::Relation(r) => self.add_rel(&r),
+            neo4rs::BoltType::Path(p) => {
+                let path = BoundPath::new(&p)?;
+                for n in &path.nodes {
+                    self.add_node(n)?;
+                }
+                for r in &path.rels {
+                    self.add_rel(r)?;
+                }
+                Ok(())
+            }
+            neo4rs::BoltType::List(items) => {
+                for item in items.value {
+                    self.add_value(item)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Add the graph elements in every column of `record`.
+    pub fn add_record(&mut self, record: &neo4rs::Row) -> Result<(), CyphrError> {
+        for (column, value) in entries(record)? {
+            self.add_value(value).map_err(|e| e.with_context(format!("Subgraph (column '{column}')")))?;
+        }
+        Ok(())
+    }
+
+    fn add_node(&mut self, node: &neo4rs::BoltNode) -> Result<(), CyphrError> {
+        if !self.node_index.contains_key(&node.id.value) {
+            self.insert_node(node.id.value, N::from_cyphr_node(node)?);
+        }
+        Ok(())
+    }
+
+    fn add_rel(&mut self, rel: &neo4rs::BoltRelation) -> Result<(), CyphrError> {
+        if !self.edge_index.contains_key(&rel.id.value) {
+            self.insert_edge(Edge {
+                id: rel.id.value,
+                start: rel.start_node_id.value,
+                end: rel.end_node_id.value,
+                rel: R::from_cyphr_rel(rel)?,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// The graph elements of one row, from all its columns.
+impl<N: FromCyphrNode, R: FromCyphrRel> FromCyphr for Subgraph<N, R> {
+    fn from_record(record: &neo4rs::Row) -> Result<Self, CyphrError> {
+        let mut graph = Self::new();
+        graph.add_record(record)?;
+        Ok(graph)
+    }
+}
+
+/// The graph elements of one value; see [`Subgraph::add_value`].
+impl<N: FromCyphrNode, R: FromCyphrRel> FromCyphrValue for Subgraph<N, R> {
+    fn from_value(value: neo4rs::BoltType) -> Result<Self, CyphrError> {
+        let mut graph = Self::new();
+        match value {
+            v @ (neo4rs::BoltType::Node(_)
+            | neo4rs::BoltType::Relation(_)
+            | neo4rs::BoltType::Path(_)
+            | neo4rs::BoltType::List(_)) => graph.add_value(v)?,
+            other => return Err(CyphrError::type_mismatch("Node, Relationship, Path or List", type_name(&other), "Subgraph")),
+        }
+        Ok(graph)
+    }
+}
+
+impl<N, R> Extend<Subgraph<N, R>> for Subgraph<N, R> {
+    fn extend<I: IntoIterator<Item = Subgraph<N, R>>>(&mut self, iter: I) {
+        for other in iter {
+            self.merge(other);
+        }
+    }
+}
+
+impl<N, R> FromIterator<Subgraph<N, R>> for Subgraph<N, R> {
+    fn from_iter<I: IntoIterator<Item = Subgraph<N, R>>>(iter: I) -> Self {
+        let mut graph = Self::new();
+        graph.extend(iter);
+        graph
+    }
+}
@@ -3,12 +3,13 @@
 
 use neo4rs::{BoltType as Value, Row as Record};
 use crate::error::CyphrError;
+use crate::schema::SchemaDef;
 
 /// Maps a Neo4j node to a Rust struct.
 ///
-/// Automatically implemented by `#[derive(CyphrNode)]`. The derive also
-/// generates a [`FromCyphrValue`] impl so the struct can be converted
-/// directly from `BoltType::Node`.
+/// Automatically implemented by `#[derive(CyphrNode)]` on structs. The
+/// derive also generates a [`FromCyphrValue`] impl so the struct can be
+/// converted directly from `BoltType::Node`.
 ///
 /// # Example
 ///
@@ -21,13 +22,69 @@ use crate::error::CyphrError;
 /// }
 ///
 /// assert_eq!(User::LABEL, "User");
+/// assert_eq!(User::PROPS, &["id", "name"]);
+/// assert_eq!(user_props::NAME, "name");
 /// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a node type with a single label",
+    note = "node enums and `GraphNode` only implement `FromCyphrNode`; use a `#[derive(CyphrNode)]` struct here"
+)]
 pub trait CyphrNode: Sized {
     /// The Neo4j label (e.g. `"User"`).
     const LABEL: &'static str;
 
+    /// Property keys read by [`from_node`](Self::from_node), in field order.
+    ///
+    /// The derive also emits a companion module (`User` → `user_props`)
+    /// with one const per field, e.g. `user_props::EMAIL`.
+    const PROPS: &'static [&'static str] = &[];
+
+    /// Property key of the `#[cyphr(id)]` field, if there is one.
+    const ID: Option<&'static str> = None;
+
     /// Deserialize a [`BoltNode`](neo4rs::BoltNode) into `Self`.
     fn from_node(node: &neo4rs::BoltNode) -> Result<Self, CyphrError>;
+
+    /// Constraints and indexes declared with `#[cyphr(unique)]`,
+    /// `#[cyphr(index)]`, `#[cyphr(required)]` and `#[cyphr(fulltext = "...")]`.
+    fn schema() -> SchemaDef {
+        SchemaDef::node(Self::LABEL)
+    }
+}
+
+/// Decodes a node without claiming a single label.
+///
+/// Implemented for every [`CyphrNode`], and by `#[derive(CyphrNode)]` on
+/// enums and by [`GraphNode`](crate::value::GraphNode), which accept nodes
+/// of several labels. Paths, triples and subgraphs decode their nodes
+/// through it; query builders need a [`CyphrNode`].
+pub trait FromCyphrNode: Sized {
+    /// Deserialize a [`BoltNode`](neo4rs::BoltNode) into `Self`.
+    fn from_cyphr_node(node: &neo4rs::BoltNode) -> Result<Self, CyphrError>;
+}
+
+impl<T: CyphrNode> FromCyphrNode for T {
+    fn from_cyphr_node(node: &neo4rs::BoltNode) -> Result<Self, CyphrError> {
+        T::from_node(node)
+    }
+}
+
+/// A node whose `#[cyphr(id)]` field can be bound as a query parameter.
+///
+/// `#[derive(CyphrNode)]` implements it for every struct with an id field,
+/// whose type must then be `Clone` and implement [`IntoCyphrValue`]. Mark
+/// the field `#[cyphr(id, no_bind)]` to skip the impl and keep only
+/// [`CyphrNode::ID`] for lookups.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no id field that can be bound as a parameter",
+    note = "mark a field `#[cyphr(id)]` without `no_bind`; its type must be `Clone` and implement `IntoCyphrValue`"
+)]
+pub trait HasId: CyphrNode {
+    /// Property key of the `#[cyphr(id)]` field.
+    const ID_KEY: &'static str;
+
+    /// The value of the `#[cyphr(id)]` field.
+    fn id_value(&self) -> Value;
 }
 
 /// Maps a Neo4j relationship to a Rust struct.
@@ -47,6 +104,10 @@ pub trait CyphrNode: Sized {
 ///
 /// assert_eq!(Follows::TYPE, "FOLLOWS");
 /// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a relationship type with a single type",
+    note = "`Raw` only implements `FromCyphrRel`; use a `#[derive(CyphrRelation)]` struct here"
+)]
 pub trait CyphrRelation: Sized {
     /// The Neo4j relationship type (e.g. `"FOLLOWS"`).
     const TYPE: &'static str;
@@ -56,10 +117,53 @@ pub trait CyphrRelation: Sized {
     /// Optional label of the end node, for compile-time documentation.
     const TO_LABEL: Option<&'static str> = None;
 
+    /// Property keys read by [`from_rel`](Self::from_rel), in field order.
+    const PROPS: &'static [&'static str] = &[];
+
     /// Deserialize a [`BoltRelation`](neo4rs::BoltRelation) into `Self`.
     fn from_rel(rel: &neo4rs::BoltRelation) -> Result<Self, CyphrError>;
+
+    /// Constraints and indexes declared on the relationship's fields; see
+    /// [`CyphrNode::schema`].
+    fn schema() -> SchemaDef {
+        SchemaDef::relationship(Self::TYPE)
+    }
+}
+
+/// Decodes a relationship without claiming a single type.
+///
+/// Implemented for every [`CyphrRelation`] and by
+/// [`Raw`](crate::value::Raw), which accepts relationships of any type; see
+/// [`FromCyphrNode`].
+pub trait FromCyphrRel: Sized {
+    /// Deserialize a [`BoltRelation`](neo4rs::BoltRelation) into `Self`.
+    fn from_cyphr_rel(rel: &neo4rs::BoltRelation) -> Result<Self, CyphrError>;
+}
+
+impl<T: CyphrRelation> FromCyphrRel for T {
+    fn from_cyphr_rel(rel: &neo4rs::BoltRelation) -> Result<Self, CyphrError> {
+        T::from_rel(rel)
+    }
 }
 
+/// A relationship that may start at an `N` node.
+///
+/// `#[derive(CyphrRelation)]` implements it for the `from_type`, or for
+/// every [`CyphrNode`] when `from_type` is omitted, so query builders can
+/// reject mismatched endpoints at compile time.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` relationships don't start at `{N}` nodes",
+    note = "the relationship's `#[cyphr(from_type = ...)]` names a different node type"
+)]
+pub trait RelFrom<N: CyphrNode>: CyphrRelation {}
+
+/// A relationship that may end at an `N` node; see [`RelFrom`].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` relationships don't end at `{N}` nodes",
+    note = "the relationship's `#[cyphr(to_type = ...)]` names a different node type"
+)]
+pub trait RelTo<N: CyphrNode>: CyphrRelation {}
+
 /// Maps a `neo4rs::Row` into a Rust struct by field name.
 ///
 /// Automatically implemented by `#[derive(FromCyphr)]`. Each struct field
@@ -6,7 +6,8 @@
 
 use std::collections::HashMap;
 use crate::error::CyphrError;
-use crate::traits::{CyphrNode, CyphrRelation, FromCyphrValue, IntoCyphrValue, NodeWrapper, RelationWrapper};
+use crate::record::{get_value, single_entry};
+use crate::traits::{CyphrNode, CyphrRelation, FromCyphr, FromCyphrNode, FromCyphrRel, FromCyphrValue, IntoCyphrValue, NodeWrapper, RelationWrapper};
 
 /// Returns a human-readable name for a [`neo4rs::BoltType`] variant.
 ///
@@ -386,17 +387,21 @@ impl FromCyphrValue for CyphrBytes {
 
 /// A typed graph path returned by Neo4j `Path` values.
 ///
-/// `N` is the node type (must implement [`CyphrNode`]). Each node in the
-/// path is deserialized via `N::from_node()`. Relationships are kept as raw
-/// [`neo4rs::BoltUnboundedRelation`] values since paths return unbounded
-/// (direction-less) relationships.
+/// `N` is the node type and `R` the relationship type. Each node in the
+/// path is deserialized via `N::from_cyphr_node()` and each relationship via
+/// `R::from_cyphr_rel()`, with its start and end nodes taken from the direction it
+/// was traversed in. `R` defaults to [`Raw`], which keeps the relationship
+/// undecoded.
 ///
 /// # Fields
 ///
-/// - `nodes` — the ordered list of nodes along the path.
-/// - `rels` — the unbounded relationships connecting consecutive nodes.
+/// - `nodes` — the distinct nodes on the path, starting with its first node.
+/// - `rels` — the distinct relationships on the path.
 /// - `indices` — the raw path indices from the bolt protocol.
 ///
+/// A node or relationship visited twice appears once in `nodes` / `rels`;
+/// use [`segments`](Self::segments) to walk the path in order.
+///
 /// # Example
 ///
 /// ```rust,ignore
@@ -408,35 +413,373 @@ impl FromCyphrValue for CyphrBytes {
 ///     name: String,
 /// }
 ///
+/// #[derive(Debug, CyphrRelation)]
+/// #[cyphr(type = "KNOWS")]
+/// struct Knows {
+///     since: i64,
+/// }
+///
 /// #[derive(FromCyphr)]
 /// struct PathRow {
-///     p: CyphrPath<Person>,
+///     p: CyphrPath<Person, Knows>,
+/// }
+/// // Cypher: MATCH p = (a:Person)-[:KNOWS*..3]-(b:Person) RETURN p
+///
+/// for step in row.p.segments() {
+///     match step.direction {
+///         PathDirection::Outgoing => println!("{} knows {}", step.from.name, step.to.name),
+///         PathDirection::Incoming => println!("{} is known by {}", step.from.name, step.to.name),
+///     }
 /// }
-/// // Cypher: MATCH p = (a:Person)-[:KNOWS*..3]->(b:Person) RETURN p
 /// ```
-pub struct CyphrPath<N: CyphrNode> {
-    /// Ordered nodes along the path.
+pub struct CyphrPath<N: FromCyphrNode, R: FromCyphrRel = Raw> {
+    /// Distinct nodes on the path, starting with its first node.
     pub nodes: Vec<N>,
-    /// Unbounded relationships connecting consecutive nodes.
-    pub rels: Vec<neo4rs::BoltUnboundedRelation>,
-    /// Raw path indices from the bolt protocol.
+    /// Distinct relationships on the path.
+    pub rels: Vec<R>,
+    /// Raw path indices from the bolt protocol: a 1-based relationship
+    /// index, negative if traversed backwards, then a 0-based node index,
+    /// for each step.
     pub indices: Vec<i64>,
 }
 
-impl<N: CyphrNode> FromCyphrValue for CyphrPath<N> {
+impl<N: FromCyphrNode, R: FromCyphrRel> CyphrPath<N, R> {
+    /// The first node of the path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nodes` is empty, which it never is for a decoded path.
+    pub fn start(&self) -> &N {
+        &self.nodes[0]
+    }
+
+    /// The last node of the path; the same as [`start`](Self::start) for a
+    /// path of length zero.
+    pub fn end(&self) -> &N {
+        self.segments().last().map_or_else(|| self.start(), |step| step.to)
+    }
+
+    /// The number of relationships traversed.
+    pub fn len(&self) -> usize {
+        self.indices.len() / 2
+    }
+
+    /// Whether the path is a single node.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The path's steps, in order.
+    pub fn segments(&self) -> impl Iterator<Item = Segment<'_, N, R>> {
+        let mut at = 0;
+        self.indices.chunks_exact(2).map_while(move |step| {
+            let from = self.nodes.get(at)?;
+            let rel = self.rels.get(usize::try_from(step[0].unsigned_abs()).ok()?.checked_sub(1)?)?;
+            at = usize::try_from(step[1]).ok()?;
+            let to = self.nodes.get(at)?;
+            let direction = if step[0] > 0 { PathDirection::Outgoing } else { PathDirection::Incoming };
+            Some(Segment { from, rel, to, direction })
+        })
+    }
+}
+
+/// One step of a [`CyphrPath`]: `from` and `to` in path order, joined by
+/// `rel`.
+#[derive(Debug)]
+pub struct Segment<'a, N, R> {
+    /// The node the step leaves.
+    pub from: &'a N,
+    /// The relationship traversed.
+    pub rel: &'a R,
+    /// The node the step arrives at.
+    pub to: &'a N,
+    /// Whether `rel` points from `from` to `to` or the other way.
+    pub direction: PathDirection,
+}
+
+/// The direction a path step traversed its relationship in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PathDirection {
+    /// `(from)-[rel]->(to)`.
+    Outgoing,
+    /// `(from)<-[rel]-(to)`.
+    Incoming,
+}
+
+/// An undecoded relationship, the default relationship type of
+/// [`CyphrPath`].
+///
+/// `Raw` accepts any relationship type, so it implements only
+/// [`FromCyphrRel`], not [`CyphrRelation`], and can't be used to build
+/// queries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Raw(pub neo4rs::BoltRelation);
+
+impl FromCyphrRel for Raw {
+    fn from_cyphr_rel(rel: &neo4rs::BoltRelation) -> Result<Self, CyphrError> {
+        Ok(Raw(rel.clone()))
+    }
+}
+
+impl<N: FromCyphrNode, R: FromCyphrRel> FromCyphrValue for CyphrPath<N, R> {
+    fn from_value(value: neo4rs::BoltType) -> Result<Self, CyphrError> {
+        let p = match value {
+            neo4rs::BoltType::Path(p) => p,
+            other => return Err(CyphrError::type_mismatch("Path", type_name(&other), "CyphrPath")),
+        };
+        let bound = BoundPath::new(&p)?;
+        let mut nodes = Vec::with_capacity(bound.nodes.len());
+        for n in &bound.nodes {
+            nodes.push(N::from_cyphr_node(n)?);
+        }
+        let mut rels = Vec::with_capacity(bound.rels.len());
+        for r in &bound.rels {
+            rels.push(R::from_cyphr_rel(r)?);
+        }
+        Ok(CyphrPath { nodes, rels, indices: bound.indices })
+    }
+}
+
+/// A bolt path with each relationship bound to the nodes it joins.
+pub(crate) struct BoundPath {
+    pub(crate) nodes: Vec<neo4rs::BoltNode>,
+    pub(crate) rels: Vec<neo4rs::BoltRelation>,
+    pub(crate) indices: Vec<i64>,
+}
+
+impl BoundPath {
+    pub(crate) fn new(p: &neo4rs::BoltPath) -> Result<Self, CyphrError> {
+        let (nodes, bolt_rels) = (p.nodes(), p.rels());
+        let indices: Vec<i64> = p.indices().into_iter().map(|i| i.value).collect();
+        let malformed = || CyphrError::Mapping("malformed path indices".to_owned()).with_context("CyphrPath");
+        if nodes.is_empty() || !indices.len().is_multiple_of(2) {
+            return Err(malformed());
+        }
+
+        // Bind each relationship to the nodes it joins, in its own direction.
+        let mut ends: Vec<Option<(i64, i64)>> = vec![None; bolt_rels.len()];
+        let mut at = 0;
+        for step in indices.chunks_exact(2) {
+            let rel = usize::try_from(step[0].unsigned_abs()).map_err(|_| malformed())?;
+            let next = usize::try_from(step[1]).map_err(|_| malformed())?;
+            let (Some(end), Some(next_node)) = (rel.checked_sub(1).and_then(|i| ends.get_mut(i)), nodes.get(next))
+            else {
+                return Err(malformed());
+            };
+            let (a, b) = (nodes[at].id.value, next_node.id.value);
+            end.get_or_insert(if step[0] > 0 { (a, b) } else { (b, a) });
+            at = next;
+        }
+
+        let mut rels = Vec::with_capacity(bolt_rels.len());
+        for (rel, end) in bolt_rels.iter().zip(ends) {
+            let (start, end) = end.ok_or_else(malformed)?;
+            rels.push(bind_rel(rel, start, end));
+        }
+        Ok(BoundPath { nodes, rels, indices })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// GraphNode
+// ---------------------------------------------------------------------------
+
+/// A node of any label, with its properties kept as a map.
+///
+/// Useful where a result mixes labels, e.g. `CyphrPath<GraphNode, Raw>` for
+/// `MATCH p = (:User)-[:WROTE]->(:Post)-[:TAGGED]->(:Tag) RETURN p`. For a
+/// fixed set of labels, `#[derive(CyphrNode)]` on an enum decodes into typed
+/// variants instead.
+///
+/// Like [`Raw`], `GraphNode` accepts any node, so it implements only
+/// [`FromCyphrNode`], not [`CyphrNode`], and can't be used to build queries.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let p: CyphrPath<GraphNode> = query("MATCH p = (:User)-[*..2]->() RETURN p")
+///     .fetch_scalar(&graph)
+///     .await?;
+/// for node in &p.nodes {
+///     if node.has_label("Post") {
+///         let title: String = node.get("title")?;
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    /// The node's internal id.
+    pub id: i64,
+    /// The node's labels.
+    pub labels: Vec<String>,
+    /// The node's properties.
+    pub properties: HashMap<String, neo4rs::BoltType>,
+}
+
+impl GraphNode {
+    /// Whether the node has `label`.
+    pub fn has_label(&self, label: &str) -> bool {
+        self.labels.iter().any(|l| l == label)
+    }
+
+    /// Decode the `key` property as `T`.
+    pub fn get<T: FromCyphrValue>(&self, key: &str) -> Result<T, CyphrError> {
+        let value = self.properties.get(key).ok_or_else(|| {
+            CyphrError::missing_property(key, self.labels.first().map_or("GraphNode", String::as_str))
+        })?;
+        T::from_value(value.clone()).map_err(|e| e.with_context(format!("GraphNode (prop '{key}')")))
+    }
+}
+
+impl FromCyphrNode for GraphNode {
+    fn from_cyphr_node(node: &neo4rs::BoltNode) -> Result<Self, CyphrError> {
+        Ok(GraphNode {
+            id: node.id.value,
+            labels: crate::props::node_labels(node),
+            properties: node.properties.value.iter().map(|(k, v)| (k.value.clone(), v.clone())).collect(),
+        })
+    }
+}
+
+impl FromCyphrValue for GraphNode {
     fn from_value(value: neo4rs::BoltType) -> Result<Self, CyphrError> {
         match value {
-            neo4rs::BoltType::Path(p) => {
-                let bolt_nodes = p.nodes();
-                let mut nodes = Vec::with_capacity(bolt_nodes.len());
-                for n in &bolt_nodes {
-                    nodes.push(N::from_node(n)?);
-                }
-                let rels = p.rels();
-                let indices = p.indices().into_iter().map(|i| i.value).collect();
-                Ok(CyphrPath { nodes, rels, indices })
+            neo4rs::BoltType::Node(n) => Self::from_cyphr_node(&n),
+            other => Err(CyphrError::type_mismatch("Node", type_name(&other), "GraphNode")),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Triple
+// ---------------------------------------------------------------------------
+
+/// A relationship together with its start and end nodes.
+///
+/// Decodes from a row with columns `a`, `r` and `b` (`RETURN a, r, b`), or
+/// from a path of length one (`MATCH p = (a)-[r]->(b) RETURN p`), either as
+/// a whole row or as a [`FromCyphrValue`] field. The relationship must run
+/// from `a` to `b`; if it runs the other way, decoding fails with an error
+/// saying the endpoints are swapped.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use cyphr::prelude::*;
+///
+/// let follows: Vec<Triple<User, Follows, User>> =
+///     query("MATCH (a:User)-[r:FOLLOWS]->(b:User) RETURN a, r, b")
+///         .fetch_all(&graph)
+///         .await?;
+/// for Triple { from, rel, to } in &follows {
+///     println!("{} -> {} since {}", from.name, to.name, rel.since);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Triple<A, R, B> {
+    /// The relationship's start node.
+    pub from: A,
+    /// The relationship.
+    pub rel: R,
+    /// The relationship's end node.
+    pub to: B,
+}
+
+impl<A: FromCyphrNode, R: FromCyphrRel, B: FromCyphrNode> Triple<A, R, B> {
+    /// Decode `a -[r]-> b`, checking that `r` starts at `a` and ends at `b`.
+    pub fn from_parts(
+        a: &neo4rs::BoltNode,
+        r: &neo4rs::BoltRelation,
+        b: &neo4rs::BoltNode,
+    ) -> Result<Self, CyphrError> {
+        let (a_id, b_id) = (a.id.value, b.id.value);
+        let (start, end) = (r.start_node_id.value, r.end_node_id.value);
+        if (start, end) != (a_id, b_id) {
+            let reason = if (start, end) == (b_id, a_id) {
+                "endpoints are swapped: the relationship runs from `b` to `a`".to_owned()
+            } else {
+                format!("relationship connects nodes {start} and {end}, not {a_id} and {b_id}")
+            };
+            return Err(CyphrError::Mapping(reason).with_context(triple_context::<A, R, B>()));
+        }
+        Ok(Triple {
+            from: A::from_cyphr_node(a).map_err(|e| e.with_context(triple_context::<A, R, B>()))?,
+            rel: R::from_cyphr_rel(r).map_err(|e| e.with_context(triple_context::<A, R, B>()))?,
+            to: B::from_cyphr_node(b).map_err(|e| e.with_context(triple_context::<A, R, B>()))?,
+        })
+    }
+}
+
+fn triple_context<A: FromCyphrNode, R: FromCyphrRel, B: FromCyphrNode>() -> String {
+    format!("Triple<{}, {}, {}>", short_type_name::<A>(), short_type_name::<R>(), short_type_name::<B>())
+}
+
+/// `T`'s name without its module path or generic arguments, for error
+/// context.
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let base = name.split('<').next().unwrap_or(name);
+    base.rsplit("::").next().unwrap_or(base)
+}
+
+/// Give a path relationship the start and end node ids it was traversed
+/// between.
+pub(crate) fn bind_rel(rel: &neo4rs::BoltUnboundedRelation, start: i64, end: i64) -> neo4rs::BoltRelation {
+    neo4rs::BoltRelation {
+        id: rel.id.clone(),
+        start_node_id: neo4rs::BoltInteger::new(start),
+        end_node_id: neo4rs::BoltInteger::new(end),
+        typ: rel.typ.clone(),
+        properties: rel.properties.clone(),
+    }
+}
+
+impl<A: FromCyphrNode, R: FromCyphrRel, B: FromCyphrNode> FromCyphrValue for Triple<A, R, B> {
+    fn from_value(value: neo4rs::BoltType) -> Result<Self, CyphrError> {
+        let path = match value {
+            neo4rs::BoltType::Path(p) => p,
+            other => return Err(CyphrError::type_mismatch("Path", type_name(&other), "Triple")),
+        };
+        let (nodes, rels, indices) = (path.nodes(), path.rels(), path.indices());
+        let ([a, ..], [rel], [index, end]) = (nodes.as_slice(), rels.as_slice(), indices.as_slice()) else {
+            return Err(CyphrError::Mapping(format!(
+                "expected a path of length 1, got {} relationships",
+                rels.len()
+            ))
+            .with_context(triple_context::<A, R, B>()));
+        };
+        // A self-loop lists its node once, so the end node is looked up by index.
+        let b = usize::try_from(end.value).ok().and_then(|i| nodes.get(i)).ok_or_else(|| {
+            CyphrError::Mapping("malformed path indices".to_owned()).with_context(triple_context::<A, R, B>())
+        })?;
+        // A negative index means the relationship was traversed backwards.
+        let r = if index.value > 0 {
+            bind_rel(rel, a.id.value, b.id.value)
+        } else {
+            bind_rel(rel, b.id.value, a.id.value)
+        };
+        Self::from_parts(a, &r, b)
+    }
+}
+
+impl<A: FromCyphrNode, R: FromCyphrRel, B: FromCyphrNode> FromCyphr for Triple<A, R, B> {
+    fn from_record(record: &neo4rs::Row) -> Result<Self, CyphrError> {
+        let columns = (get_value(record, "a"), get_value(record, "r"), get_value(record, "b"));
+        match columns {
+            (Some(neo4rs::BoltType::Node(a)), Some(neo4rs::BoltType::Relation(r)), Some(neo4rs::BoltType::Node(b))) => {
+                Self::from_parts(&a, &r, &b)
+            }
+            (None, None, None) => {
+                let (_, value) = single_entry(record).map_err(|_| {
+                    CyphrError::Mapping("expected columns `a`, `r` and `b`, or a single path".to_owned())
+                        .with_context(triple_context::<A, R, B>())
+                })?;
+                Self::from_value(value)
             }
-            other => Err(CyphrError::type_mismatch("Path", type_name(&other), "CyphrPath")),
+            _ => Err(CyphrError::Mapping(
+                "columns `a` and `b` must be nodes and `r` a relationship".to_owned(),
+            )
+            .with_context(triple_context::<A, R, B>())),
         }
     }
 }
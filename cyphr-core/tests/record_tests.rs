@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use cyphr_core::record::{self, CyphrRow};
+use cyphr_core::traits::FromCyphr;
+use cyphr_core::CyphrError;
+use neo4rs::{BoltList, BoltType, Row};
+
+fn row(fields: &[&str], data: Vec<BoltType>) -> Row {
+    let fields: Vec<BoltType> = fields.iter().map(|f| BoltType::from(*f)).collect();
+    Row::new(BoltList::from(fields), BoltList::from(data))
+}
+
+#[test]
+fn test_entries_sorted_by_name() {
+    let r = row(&["b", "a"], vec![BoltType::from(2), BoltType::from(1)]);
+    let keys: Vec<String> = record::entries(&r).unwrap().into_iter().map(|(k, _)| k).collect();
+    assert_eq!(keys, vec!["a", "b"]);
+}
+
+#[test]
+fn test_single_entry() {
+    let r = row(&["count(u)"], vec![BoltType::from(7)]);
+    let (key, value) = record::single_entry(&r).unwrap();
+    assert_eq!(key, "count(u)");
+    assert_eq!(value, BoltType::from(7));
+}
+
+#[test]
+fn test_single_entry_rejects_multiple_columns() {
+    let r = row(&["a", "b"], vec![BoltType::from(1), BoltType::from(2)]);
+    let err = record::single_entry(&r).unwrap_err();
+    match &err {
+        CyphrError::Mapping(msg) => assert!(msg.contains("got 2")),
+        other => panic!("expected Mapping, got: {other}"),
+    }
+}
+
+// --- CyphrRow ---
+
+fn user_row() -> CyphrRow {
+    vec![
+        ("name".to_string(), BoltType::from("Alice")),
+        ("age".to_string(), BoltType::from(30)),
+    ]
+    .into_iter()
+    .collect()
+}
+
+#[test]
+fn test_cyphr_row_get() {
+    let r = user_row();
+    let name: String = r.get("name").unwrap();
+    let age: i64 = r.get("age").unwrap();
+    assert_eq!(name, "Alice");
+    assert_eq!(age, 30);
+}
+
+#[test]
+fn test_cyphr_row_get_missing_column() {
+    let err = user_row().get::<String>("email").unwrap_err();
+    match &err {
+        CyphrError::MissingField { field, struct_name } => {
+            assert_eq!(field, "email");
+            assert_eq!(struct_name, "CyphrRow");
+        }
+        other => panic!("expected MissingField, got: {other}"),
+    }
+}
+
+#[test]
+fn test_cyphr_row_get_type_mismatch_has_column_context() {
+    let err = user_row().get::<i64>("name").unwrap_err();
+    assert!(err.to_string().starts_with("column 'name': type mismatch"));
+}
+
+#[test]
+fn test_cyphr_row_try_get() {
+    let r = user_row();
+    assert_eq!(r.try_get::<String>("email").unwrap(), None);
+    assert_eq!(r.try_get::<i64>("age").unwrap(), Some(30));
+    assert!(r.try_get::<bool>("age").is_err());
+}
+
+#[test]
+fn test_cyphr_row_columns_and_iter() {
+    let r = user_row();
+    assert_eq!(r.columns(), ["name", "age"]);
+    assert_eq!(r.len(), 2);
+    assert!(r.contains("age"));
+    let keys: Vec<&str> = r.iter().map(|(k, _)| k).collect();
+    assert_eq!(keys, vec!["name", "age"]);
+}
+
+#[test]
+fn test_cyphr_row_into_map() {
+    let map: HashMap<String, BoltType> = user_row().into();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["age"], BoltType::from(30));
+}
+
+#[test]
+fn test_cyphr_row_from_record() {
+    let r = row(&["b", "a"], vec![BoltType::from(2), BoltType::from(1)]);
+    let cr = CyphrRow::from_record(&r).unwrap();
+    assert_eq!(cr.columns(), ["a", "b"]);
+    assert_eq!(cr.get::<i64>("b").unwrap(), 2);
+}
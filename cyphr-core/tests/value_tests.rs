@@ -1,6 +1,6 @@
 use std::collections::HashMap;
-use cyphr_core::traits::{CyphrNode, FromCyphrValue, IntoCyphrValue};
-use cyphr_core::value::{Point2D, Point3D, CyphrBytes, CyphrPath};
+use cyphr_core::traits::{CyphrNode, CyphrRelation, FromCyphr, FromCyphrValue, IntoCyphrValue};
+use cyphr_core::value::{Point2D, Point3D, CyphrBytes, CyphrPath, GraphNode, PathDirection, Triple};
 use cyphr_core::CyphrError;
 use neo4rs::BoltType;
 
@@ -247,7 +247,245 @@ fn test_cyphr_path_from_value() {
     assert_eq!(cp.nodes[0].name, "Mark");
     assert_eq!(cp.nodes[1].name, "James");
     assert_eq!(cp.rels.len(), 1);
+    assert_eq!(cp.rels[0].0.start_node_id.value, 1);
+    assert_eq!(cp.rels[0].0.end_node_id.value, 2);
     assert_eq!(cp.indices.len(), 2);
+    assert_eq!(cp.len(), 1);
+    assert_eq!((cp.start().name.as_str(), cp.end().name.as_str()), ("Mark", "James"));
+}
+
+// --- GraphNode ---
+
+fn tagged(id: i64, label: &str, key: &str, value: BoltType) -> BoltType {
+    BoltType::Node(neo4rs::BoltNode::new(
+        neo4rs::BoltInteger::new(id),
+        vec![BoltType::from(label)].into(),
+        vec![(neo4rs::BoltString::from(key), value)].into_iter().collect(),
+    ))
+}
+
+#[test]
+fn test_graph_node_from_value() {
+    let node = GraphNode::from_value(tagged(4, "Post", "title", BoltType::from("Hello"))).unwrap();
+    assert_eq!(node.id, 4);
+    assert!(node.has_label("Post"));
+    assert!(!node.has_label("User"));
+    assert_eq!(node.get::<String>("title").unwrap(), "Hello");
+    assert_eq!(node.get::<String>("body").unwrap_err().to_string(), "missing property 'body' on Post");
+}
+
+#[test]
+fn test_graph_node_mixed_path() {
+    let unbounded = |id: i64, typ: &str| {
+        BoltType::UnboundedRelation(neo4rs::BoltUnboundedRelation::new(
+            neo4rs::BoltInteger::new(id),
+            neo4rs::BoltString::from(typ),
+            Default::default(),
+        ))
+    };
+    // (User)-[:WROTE]->(Post)-[:TAGGED]->(Tag)
+    let path = BoltType::Path(neo4rs::BoltPath {
+        nodes: vec![
+            tagged(1, "User", "name", BoltType::from("Ann")),
+            tagged(2, "Post", "title", BoltType::from("Hello")),
+            tagged(3, "Tag", "name", BoltType::from("rust")),
+        ]
+        .into(),
+        rels: vec![unbounded(10, "WROTE"), unbounded(11, "TAGGED")].into(),
+        indices: vec![1i64, 1, 2, 2].into_iter().map(BoltType::from).collect::<Vec<_>>().into(),
+    });
+    let cp = CyphrPath::<GraphNode>::from_value(path).unwrap();
+    let steps: Vec<_> = cp
+        .segments()
+        .map(|s| (s.from.labels[0].as_str(), s.rel.0.typ.value.as_str(), s.to.labels[0].as_str()))
+        .collect();
+    assert_eq!(steps, [("User", "WROTE", "Post"), ("Post", "TAGGED", "Tag")]);
+    assert_eq!(cp.end().get::<String>("name").unwrap(), "rust");
+}
+
+// --- Triple ---
+
+struct TestKnows {
+    since: i64,
+}
+
+impl CyphrRelation for TestKnows {
+    const TYPE: &'static str = "KNOWS";
+    fn from_rel(rel: &neo4rs::BoltRelation) -> Result<Self, CyphrError> {
+        let v = cyphr_core::props::rel_prop(rel, "since")
+            .ok_or_else(|| CyphrError::missing_property("since", "KNOWS"))?;
+        Ok(TestKnows { since: i64::from_value(v)? })
+    }
+}
+
+fn person(id: i64, name: &str) -> neo4rs::BoltNode {
+    neo4rs::BoltNode::new(
+        neo4rs::BoltInteger::new(id),
+        vec![BoltType::from("TestPathNode")].into(),
+        vec![(neo4rs::BoltString::from("name"), BoltType::from(name))].into_iter().collect(),
+    )
+}
+
+fn knows(start: i64, end: i64) -> neo4rs::BoltRelation {
+    neo4rs::BoltRelation {
+        id: neo4rs::BoltInteger::new(10),
+        start_node_id: neo4rs::BoltInteger::new(start),
+        end_node_id: neo4rs::BoltInteger::new(end),
+        typ: neo4rs::BoltString::from("KNOWS"),
+        properties: vec![(neo4rs::BoltString::from("since"), BoltType::from(2020))].into_iter().collect(),
+    }
+}
+
+fn one_hop(index: i64) -> BoltType {
+    let rel = knows(0, 0);
+    BoltType::Path(neo4rs::BoltPath {
+        nodes: vec![BoltType::Node(person(1, "Mark")), BoltType::Node(person(2, "James"))].into(),
+        rels: vec![BoltType::UnboundedRelation(neo4rs::BoltUnboundedRelation::new(rel.id, rel.typ, rel.properties))].into(),
+        indices: vec![BoltType::from(index), BoltType::from(1)].into(),
+    })
+}
+
+fn unbounded(id: i64, since: i64) -> BoltType {
+    let rel = neo4rs::BoltUnboundedRelation::new(
+        neo4rs::BoltInteger::new(id),
+        neo4rs::BoltString::from("KNOWS"),
+        vec![(neo4rs::BoltString::from("since"), BoltType::from(since))].into_iter().collect(),
+    );
+    BoltType::UnboundedRelation(rel)
+}
+
+#[test]
+fn test_cyphr_path_segments() {
+    // (Mark)-[:KNOWS {since: 2020}]->(James)<-[:KNOWS {since: 2021}]-(Ann)-[:KNOWS {since: 2022}]->(Mark)
+    let path = BoltType::Path(neo4rs::BoltPath {
+        nodes: vec![
+            BoltType::Node(person(1, "Mark")),
+            BoltType::Node(person(2, "James")),
+            BoltType::Node(person(3, "Ann")),
+        ]
+        .into(),
+        rels: vec![unbounded(10, 2020), unbounded(11, 2021), unbounded(12, 2022)].into(),
+        indices: vec![1i64, 1, -2, 2, 3, 0].into_iter().map(BoltType::from).collect::<Vec<_>>().into(),
+    });
+    let cp = CyphrPath::<TestPathNode, TestKnows>::from_value(path).unwrap();
+    assert_eq!(cp.len(), 3);
+    assert_eq!((cp.start().name.as_str(), cp.end().name.as_str()), ("Mark", "Mark"));
+
+    let steps: Vec<_> = cp
+        .segments()
+        .map(|s| (s.from.name.as_str(), s.rel.since, s.to.name.as_str(), s.direction))
+        .collect();
+    assert_eq!(
+        steps,
+        [
+            ("Mark", 2020, "James", PathDirection::Outgoing),
+            ("James", 2021, "Ann", PathDirection::Incoming),
+            ("Ann", 2022, "Mark", PathDirection::Outgoing),
+        ]
+    );
+}
+
+#[test]
+fn test_cyphr_path_binds_rel_direction() {
+    let path = BoltType::Path(neo4rs::BoltPath {
+        nodes: vec![BoltType::Node(person(1, "Mark")), BoltType::Node(person(2, "James"))].into(),
+        rels: vec![unbounded(10, 2020)].into(),
+        indices: vec![BoltType::from(-1), BoltType::from(1)].into(),
+    });
+    let cp = CyphrPath::<TestPathNode>::from_value(path).unwrap();
+    assert_eq!((cp.rels[0].0.start_node_id.value, cp.rels[0].0.end_node_id.value), (2, 1));
+    assert_eq!(cp.segments().next().unwrap().direction, PathDirection::Incoming);
+}
+
+#[test]
+fn test_cyphr_path_single_node() {
+    let path = BoltType::Path(neo4rs::BoltPath {
+        nodes: vec![BoltType::Node(person(1, "Mark"))].into(),
+        rels: vec![].into(),
+        indices: vec![].into(),
+    });
+    let cp = CyphrPath::<TestPathNode, TestKnows>::from_value(path).unwrap();
+    assert!(cp.is_empty());
+    assert_eq!(cp.end().name, "Mark");
+    assert_eq!(cp.segments().count(), 0);
+}
+
+#[test]
+fn test_cyphr_path_malformed_indices() {
+    let path = BoltType::Path(neo4rs::BoltPath {
+        nodes: vec![BoltType::Node(person(1, "Mark"))].into(),
+        rels: vec![unbounded(10, 2020)].into(),
+        indices: vec![BoltType::from(2), BoltType::from(0)].into(),
+    });
+    let err = CyphrPath::<TestPathNode, TestKnows>::from_value(path).err().unwrap();
+    assert_eq!(err.to_string(), "CyphrPath: mapping error: malformed path indices");
+}
+
+fn abr_row(a: neo4rs::BoltNode, r: neo4rs::BoltRelation, b: neo4rs::BoltNode) -> neo4rs::Row {
+    neo4rs::Row::new(
+        vec![BoltType::from("a"), BoltType::from("r"), BoltType::from("b")].into(),
+        vec![BoltType::Node(a), BoltType::Relation(r), BoltType::Node(b)].into(),
+    )
+}
+
+type Knows = Triple<TestPathNode, TestKnows, TestPathNode>;
+
+#[test]
+fn test_triple_from_row() {
+    let t = Knows::from_record(&abr_row(person(1, "Mark"), knows(1, 2), person(2, "James"))).unwrap();
+    assert_eq!((t.from.name.as_str(), t.rel.since, t.to.name.as_str()), ("Mark", 2020, "James"));
+}
+
+#[test]
+fn test_triple_swapped_endpoints() {
+    let err = Knows::from_record(&abr_row(person(1, "Mark"), knows(2, 1), person(2, "James"))).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Triple<TestPathNode, TestKnows, TestPathNode>: mapping error: endpoints are swapped: the relationship runs from `b` to `a`"
+    );
+}
+
+#[test]
+fn test_triple_unrelated_endpoints() {
+    let err = Knows::from_record(&abr_row(person(1, "Mark"), knows(1, 3), person(2, "James"))).err().unwrap();
+    assert!(err.to_string().ends_with("relationship connects nodes 1 and 3, not 1 and 2"), "{err}");
+}
+
+#[test]
+fn test_triple_from_path() {
+    let t = Knows::from_value(one_hop(1)).unwrap();
+    assert_eq!((t.from.name.as_str(), t.to.name.as_str()), ("Mark", "James"));
+
+    let row = neo4rs::Row::new(vec![BoltType::from("p")].into(), vec![one_hop(1)].into());
+    assert_eq!(Knows::from_record(&row).unwrap().rel.since, 2020);
+}
+
+#[test]
+fn test_triple_from_self_loop_path() {
+    // (Mark)-[:KNOWS]->(Mark): the path lists Mark once and ends at index 0.
+    let path = BoltType::Path(neo4rs::BoltPath {
+        nodes: vec![BoltType::Node(person(1, "Mark"))].into(),
+        rels: vec![unbounded(10, 2020)].into(),
+        indices: vec![BoltType::from(1), BoltType::from(0)].into(),
+    });
+    let t = Knows::from_value(path).unwrap();
+    assert_eq!((t.from.name.as_str(), t.rel.since, t.to.name.as_str()), ("Mark", 2020, "Mark"));
+}
+
+#[test]
+fn test_triple_from_reversed_path() {
+    let err = Knows::from_value(one_hop(-1)).err().unwrap();
+    assert!(err.to_string().contains("endpoints are swapped"), "{err}");
+}
+
+#[test]
+fn test_triple_bad_row() {
+    let row = neo4rs::Row::new(
+        vec![BoltType::from("x"), BoltType::from("y")].into(),
+        vec![BoltType::from(1), BoltType::from(2)].into(),
+    );
+    let err = Knows::from_record(&row).err().unwrap();
+    assert!(err.to_string().ends_with("expected columns `a`, `r` and `b`, or a single path"), "{err}");
 }
 
 // --- Error context chaining ---
@@ -332,3 +570,17 @@ fn test_into_value_cyphr_bytes() {
         other => panic!("expected Bytes, got: {other:?}"),
     }
 }
+
+// --- Cardinality errors ---
+
+#[test]
+fn test_no_rows_error() {
+    let err = CyphrError::NoRows;
+    assert_eq!(err.to_string(), "query returned no rows");
+}
+
+#[test]
+fn test_too_many_rows_error() {
+    let err = CyphrError::TooManyRows { count: 3 };
+    assert_eq!(err.to_string(), "expected at most one row, got 3");
+}
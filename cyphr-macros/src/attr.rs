@@ -0,0 +1,29 @@
+//! Helpers for `#[cyphr(...)]`, which every derive reads.
+//!
+//! A field may carry keys meant for another derive on the same type (e.g.
+//! `fulltext = "..."` next to `prop = "..."` on a struct deriving both
+//! `CyphrNode` and `ToCyphrParams`), so each derive skips keys it doesn't
+//! know, value included, instead of stopping at them.
+
+use syn::meta::ParseNestedMeta;
+use syn::{Expr, LitStr, Token};
+
+/// Consume the value of a key this derive doesn't handle: `= expr`, a
+/// parenthesized list, or nothing.
+pub fn skip(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|nested| skip(&nested))?;
+    }
+    Ok(())
+}
+
+/// The string value of `key = "..."`.
+pub fn string(meta: &ParseNestedMeta) -> syn::Result<LitStr> {
+    let expr: Expr = meta.value()?.parse()?;
+    match expr {
+        Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => Ok(s),
+        other => Err(syn::Error::new_spanned(other, "expected a string literal")),
+    }
+}
@@ -1,31 +1,65 @@
-
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{quote, quote_spanned};
+
+use crate::lex::{Kind, Tok};
+use crate::{lex, parse, print};
 
 /// Convert a token stream like:
 ///   MATCH (u:User) RETURN u
-/// into a &'static str with normalized whitespace.
+/// into a `&'static str` of Browser-pasteable Cypher.
 ///
-/// This is intentionally simple to keep Cypher flexible.
+/// The query is syntax-checked first; see [`crate::parse`].
 pub fn expand(input: TokenStream) -> TokenStream {
-    let s = input.to_string();
+    match render(input.into()) {
+        Ok(out) => out.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
 
-    // TokenStream::to_string() inserts spaces around punctuation; normalize a bit.
-    // Keep it simple and predictable.
-    let mut out = s.replace(" ,", ",")
-        .replace(" :", ":")
-        .replace(": ", ":")
-        .replace("( ", "(")
-        .replace(" )", ")")
-        .replace("[ ", "[")
-        .replace(" ]", "]")
-        .replace("{ ", "{")
-        .replace(" }", "}")
-        .replace("  ", " ");
+/// Check a Cypher token stream and print it with canonical spacing, as an
+/// expression of type `&'static str`.
+///
+/// Literals keep their exact contents; whitespace between tokens is
+/// reconstructed and never depends on how rustc spaced the input. Without
+/// `#Type` splices the result is a plain string literal; with them it is a
+/// block that concatenates the text with each type's `LABEL`/`TYPE`,
+/// backtick-quoted where needed, in a `const`.
+pub fn render(input: proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenStream> {
+    let toks = lex::lex(input)?;
+    let layout = parse::parse(&toks)?;
+    let parts = print::print(&toks, &layout.glue);
+    if let [text] = parts.as_slice() {
+        return Ok(quote! { #text });
+    }
 
-    // Preserve newlines: users may include them via explicit \n in tokens; otherwise to_string flattens.
-    // We'll just trim.
-    out = out.trim().to_string();
+    let splices = toks.iter().enumerate().filter(|(_, t)| t.kind == Kind::Splice).map(|(i, tok)| {
+        splice(tok, layout.rel_types.contains(&i))
+    });
+    let (first, rest) = parts.split_first().expect("print returns at least one part");
+    let mut pieces = vec![quote! { cyphr::__private::Part::Text(#first) }];
+    for (splice, text) in splices.zip(rest) {
+        pieces.push(quote! { cyphr::__private::Part::Name(#splice) });
+        pieces.push(quote! { cyphr::__private::Part::Text(#text) });
+    }
+    Ok(quote! {
+        {
+            const PARTS: &[cyphr::__private::Part] = &[#(#pieces),*];
+            const BYTES: [u8; cyphr::__private::concat_len(PARTS)] = cyphr::__private::concat_bytes(PARTS);
+            const TEXT: &str = match ::core::str::from_utf8(&BYTES) {
+                Ok(text) => text,
+                Err(_) => panic!("cypher!: spliced text is not UTF-8"),
+            };
+            TEXT
+        }
+    })
+}
 
-    quote! { #out }.into()
+/// `<Path as CyphrRelation>::TYPE` or `<Path as CyphrNode>::LABEL`.
+fn splice(tok: &Tok, rel_type: bool) -> proc_macro2::TokenStream {
+    let path = tok.path.as_ref().expect("splice token carries its path");
+    if rel_type {
+        quote_spanned! { tok.span=> <#path as cyphr_core::traits::CyphrRelation>::TYPE }
+    } else {
+        quote_spanned! { tok.span=> <#path as cyphr_core::traits::CyphrNode>::LABEL }
+    }
 }
@@ -1,13 +1,110 @@
 //! Implementation of the `cypher_query!` proc macro.
 //!
-//! Scans a Cypher token stream for `$ident` patterns, normalizes whitespace
-//! (same rules as `cypher!`), and generates a `CyphrQuery::new("...").param(...)...`
-//! expression with all discovered parameters auto-bound.
+//! Syntax-checks a Cypher token stream, scans it for `$ident` patterns, prints
+//! it the same way as `cypher!`, and generates a `CyphrQuery::new("...").param(...)...`
+//! expression with all discovered parameters bound.
+//!
+//! An optional binding section after a top-level `;` supplies explicit
+//! expressions (`; id = user.id, limit = page.size`), as does a
+//! parenthesized expression right after a placeholder (`$id(user.id)`),
+//! which is removed from the printed query. Placeholders without a binding
+//! capture the local variable of the same name.
 
 use proc_macro::TokenStream;
-use proc_macro2::TokenTree;
+use proc_macro2::{Delimiter, Group, TokenTree};
 use quote::quote;
 use std::collections::HashSet;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, Token};
+
+/// `name = expr` (or `$name = expr`) in the binding section.
+pub struct Binding {
+    name: Ident,
+    expr: Expr,
+}
+
+impl Parse for Binding {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let _: Option<Token![$]> = input.parse()?;
+        let name = Ident::parse_any(input)?;
+        let _: Token![=] = input.parse()?;
+        let expr = input.parse()?;
+        Ok(Binding { name, expr })
+    }
+}
+
+/// Split the input at its last top-level `;` when bindings follow it.
+/// A trailing `;` with nothing after it stays part of the query.
+pub fn split_bindings(
+    input: proc_macro2::TokenStream,
+) -> (proc_macro2::TokenStream, Option<proc_macro2::TokenStream>) {
+    let tokens: Vec<TokenTree> = input.clone().into_iter().collect();
+    let semi = tokens
+        .iter()
+        .rposition(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ';'));
+    match semi {
+        Some(i) if i + 1 < tokens.len() => (
+            tokens[..i].iter().cloned().collect(),
+            Some(tokens[i + 1..].iter().cloned().collect()),
+        ),
+        _ => (input, None),
+    }
+}
+
+pub fn parse_bindings(tokens: Option<proc_macro2::TokenStream>) -> syn::Result<Vec<Binding>> {
+    let Some(tokens) = tokens else { return Ok(Vec::new()) };
+    let bindings: Vec<Binding> = Punctuated::<Binding, Token![,]>::parse_terminated
+        .parse2(tokens)?
+        .into_iter()
+        .collect();
+    check_unique(&bindings)?;
+    Ok(bindings)
+}
+
+fn check_unique(bindings: &[Binding]) -> syn::Result<()> {
+    let mut seen = HashSet::new();
+    for b in bindings {
+        let name = b.name.unraw().to_string();
+        if !seen.insert(name.clone()) {
+            return Err(syn::Error::new_spanned(&b.name, format!("duplicate binding for `${name}`")));
+        }
+    }
+    Ok(())
+}
+
+/// Remove every inline binding `$name(expr)` from `stream`, leaving
+/// `$name`, and collect them. A parameter is never called in Cypher, so
+/// the parentheses can't belong to the query.
+fn take_inline(stream: proc_macro2::TokenStream, bindings: &mut Vec<Binding>) -> syn::Result<proc_macro2::TokenStream> {
+    let tokens: Vec<TokenTree> = stream.into_iter().collect();
+    let mut out = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+    while i < tokens.len() {
+        match (&tokens[i], tokens.get(i + 1), tokens.get(i + 2)) {
+            (TokenTree::Punct(p), Some(TokenTree::Ident(name)), Some(TokenTree::Group(g)))
+                if p.as_char() == '$' && g.delimiter() == Delimiter::Parenthesis =>
+            {
+                let expr: Expr = syn::parse2(g.stream())?;
+                bindings.push(Binding { name: name.clone(), expr });
+                out.extend([tokens[i].clone(), tokens[i + 1].clone()]);
+                i += 3;
+            }
+            (TokenTree::Group(g), ..) => {
+                let mut group = Group::new(g.delimiter(), take_inline(g.stream(), bindings)?);
+                group.set_span(g.span());
+                out.push(TokenTree::Group(group));
+                i += 1;
+            }
+            (token, ..) => {
+                out.push(token.clone());
+                i += 1;
+            }
+        }
+    }
+    Ok(out.into_iter().collect())
+}
 
 /// Recursively walk a token stream (including groups like `(...)`, `{...}`)
 /// and collect every `$ident` parameter into `params` (deduplicated by name).
@@ -23,7 +120,7 @@ fn scan_params(
             TokenTree::Punct(p) if p.as_char() == '$' => {
                 if i + 1 < tokens.len() {
                     if let TokenTree::Ident(ident) = &tokens[i + 1] {
-                        let name = ident.to_string();
+                        let name = ident.unraw().to_string();
                         if seen.insert(name) {
                             params.push(ident.clone());
                         }
@@ -45,36 +142,64 @@ fn scan_params(
 }
 
 pub fn expand(input: TokenStream) -> TokenStream {
-    let input2: proc_macro2::TokenStream = input.into();
-    let s = input2.to_string();
-
-    // Same whitespace normalization as cypher!, plus `$ ` → `$` for params.
-    let out = s
-        .replace(" ,", ",")
-        .replace(" :", ":")
-        .replace(": ", ":")
-        .replace("( ", "(")
-        .replace(" )", ")")
-        .replace("[ ", "[")
-        .replace(" ]", "]")
-        .replace("{ ", "{")
-        .replace(" }", "}")
-        .replace("$ ", "$")
-        .replace("  ", " ");
-    let out = out.trim().to_string();
+    let (query, bindings) = split_bindings(input.into());
+    let mut bindings = match parse_bindings(bindings) {
+        Ok(bindings) => bindings,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let query = match take_inline(query, &mut bindings).and_then(|query| check_unique(&bindings).map(|_| query)) {
+        Ok(query) => query,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let out = match crate::cypher::render(query.clone()) {
+        Ok(out) => out,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     // Scan tokens for $ident patterns
     let mut params: Vec<proc_macro2::Ident> = Vec::new();
     let mut seen = HashSet::new();
-    scan_params(input2, &mut params, &mut seen);
+    scan_params(query, &mut params, &mut seen);
 
-    let param_calls = params.iter().map(|ident| {
-        let name = ident.to_string();
-        quote! { .param(#name, #ident) }
+    let names: Vec<String> = params.iter().map(|ident| ident.unraw().to_string()).collect();
+    let param_calls = bind(&names, &bindings, |name| {
+        let ident = params.iter().find(|ident| ident.unraw() == name);
+        Ok(quote! { #ident })
     });
+    let param_calls = match param_calls {
+        Ok(calls) => calls,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     quote! {
         cyphr::query::CyphrQuery::new(#out) #(#param_calls)*
     }
     .into()
 }
+
+/// Build one `.param(name, value)` call per placeholder, taking the value
+/// from its binding or, failing that, from `capture`. Bindings that match
+/// no placeholder are an error.
+pub fn bind(
+    params: &[String],
+    bindings: &[Binding],
+    capture: impl Fn(&str) -> syn::Result<proc_macro2::TokenStream>,
+) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    if let Some(unused) = bindings.iter().find(|b| !params.contains(&b.name.unraw().to_string())) {
+        let name = unused.name.unraw();
+        return Err(syn::Error::new_spanned(&unused.name, format!("`${name}` does not appear in the query")));
+    }
+    params
+        .iter()
+        .map(|name| {
+            let value = match bindings.iter().find(|b| b.name.unraw() == name) {
+                Some(b) => {
+                    let expr = &b.expr;
+                    quote! { #expr }
+                }
+                None => capture(name)?,
+            };
+            Ok(quote! { .param(#name, #value) })
+        })
+        .collect()
+}
@@ -1,24 +1,26 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
+use syn::ext::IdentExt;
 use syn::{parse_macro_input, DeriveInput, Data, Fields};
 
-fn has_flatten(f: &syn::Field) -> bool {
-    for attr in &f.attrs {
-        if attr.path().is_ident("cyphr") {
-            let mut found = false;
-            let _ = attr.parse_nested_meta(|meta| {
+use crate::attr;
+
+fn has_flatten(f: &syn::Field) -> syn::Result<bool> {
+    let mut found = false;
+    for a in &f.attrs {
+        if a.path().is_ident("cyphr") {
+            a.parse_nested_meta(|meta| {
                 if meta.path.is_ident("flatten") {
                     found = true;
+                    Ok(())
+                } else {
+                    attr::skip(&meta)
                 }
-                Ok(())
-            });
-            if found {
-                return true;
-            }
+            })?;
         }
     }
-    false
+    Ok(found)
 }
 
 pub fn expand(input: TokenStream) -> TokenStream {
@@ -41,15 +43,19 @@ pub fn expand(input: TokenStream) -> TokenStream {
         }
     };
 
-    let struct_name = name.to_string();
+    let struct_name = name.unraw().to_string();
     let mut inits = Vec::new();
 
     for f in fields {
         let ident = f.ident.as_ref().unwrap();
-        let key = ident.to_string();
+        let key = ident.unraw().to_string();
         let ty = &f.ty;
 
-        if has_flatten(f) {
+        let flatten = match has_flatten(f) {
+            Ok(flatten) => flatten,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        if flatten {
             inits.push(quote! {
                 #ident: <#ty as cyphr_core::traits::FromCyphr>::from_record(record)?
             });
@@ -0,0 +1,191 @@
+//! Implementation of the `include_cypher!` proc macro.
+//!
+//! Reads `.cypher` files relative to `CARGO_MANIFEST_DIR`, validates them with
+//! the same parser as `cypher!`, and produces either a `CyphrQuery`
+//! expression (placeholders bound like `cypher_query!`) or one function per
+//! file whose arguments are the placeholders.
+//!
+//! File text is passed to Neo4j verbatim, so what the DBA wrote is what runs.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use std::path::{Path, PathBuf};
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitStr, Token, Visibility};
+
+use crate::cypher_query::{self, Binding};
+use crate::{lex, parse};
+
+enum Input {
+    /// `"path" [; bindings]`
+    Query { path: LitStr, bindings: Vec<Binding> },
+    /// `vis fn name, "path"`
+    Fn { vis: Visibility, name: Ident, path: LitStr },
+    /// `vis mod name, "dir"`
+    Mod { vis: Visibility, name: Ident, path: LitStr },
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis: Visibility = input.parse()?;
+        if input.peek(Token![fn]) || input.peek(Token![mod]) {
+            let is_fn = input.peek(Token![fn]);
+            if is_fn {
+                let _: Token![fn] = input.parse()?;
+            } else {
+                let _: Token![mod] = input.parse()?;
+            }
+            let name: Ident = input.parse()?;
+            let _: Token![,] = input.parse()?;
+            let path: LitStr = input.parse()?;
+            let _: Option<Token![,]> = input.parse()?;
+            return Ok(if is_fn {
+                Input::Fn { vis, name, path }
+            } else {
+                Input::Mod { vis, name, path }
+            });
+        }
+        if !matches!(vis, Visibility::Inherited) {
+            return Err(input.error("expected `fn` or `mod` after visibility"));
+        }
+        let path: LitStr = input.parse()?;
+        if input.is_empty() {
+            return Ok(Input::Query { path, bindings: Vec::new() });
+        }
+        let _: Token![;] = input.parse()?;
+        let rest: proc_macro2::TokenStream = input.parse()?;
+        Ok(Input::Query { path, bindings: cypher_query::parse_bindings(Some(rest))? })
+    }
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let result = syn::parse::<Input>(input).and_then(|input| match input {
+        Input::Query { path, bindings } => query(&path, &bindings),
+        Input::Fn { vis, name, path } => {
+            let file = read(&path, &resolve(&path))?;
+            Ok(function(&vis, &name, &path.value(), &file))
+        }
+        Input::Mod { vis, name, path } => module(&vis, &name, &path),
+    });
+    match result {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// A checked `.cypher` file.
+struct File {
+    abs: String,
+    text: String,
+    params: Vec<String>,
+}
+
+pub fn resolve(path: &LitStr) -> PathBuf {
+    let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".into());
+    Path::new(&root).join(path.value())
+}
+
+fn read(lit: &LitStr, abs: &Path) -> syn::Result<File> {
+    let rel = lit.value();
+    let text = std::fs::read_to_string(abs)
+        .map_err(|e| syn::Error::new(lit.span(), format!("cannot read `{}`: {e}", abs.display())))?;
+    let toks = lex::lex_str(&text, lit.span())
+        .and_then(|toks| parse::parse(&toks).map(|_| toks))
+        .map_err(|e| syn::Error::new(lit.span(), format!("{rel}: {e}")))?;
+
+    let mut params: Vec<String> = Vec::new();
+    for tok in toks.iter().filter(|t| t.kind == lex::Kind::Param) {
+        if !params.contains(&tok.text) {
+            params.push(tok.text.clone());
+        }
+    }
+    Ok(File { abs: abs.display().to_string(), text: text.trim().to_owned(), params })
+}
+
+/// The Rust identifier for a placeholder, raw if it is a keyword.
+fn param_ident(name: &str, span: Span) -> Option<Ident> {
+    if syn::parse_str::<Ident>(name).is_ok() {
+        Some(Ident::new(name, span))
+    } else if syn::parse_str::<Ident>(&format!("r#{name}")).is_ok() {
+        Some(Ident::new_raw(name, span))
+    } else {
+        None
+    }
+}
+
+fn query(path: &LitStr, bindings: &[Binding]) -> syn::Result<proc_macro2::TokenStream> {
+    let file = read(path, &resolve(path))?;
+    let calls = cypher_query::bind(&file.params, bindings, |name| {
+        match syn::parse_str::<Ident>(name) {
+            Ok(_) => {
+                let ident = Ident::new(name, Span::call_site());
+                Ok(quote! { #ident })
+            }
+            Err(_) => Err(syn::Error::new(
+                path.span(),
+                format!("placeholder `${name}` cannot be captured from a local variable; bind it explicitly"),
+            )),
+        }
+    })?;
+    let (abs, text) = (&file.abs, &file.text);
+    Ok(quote! {
+        {
+            const _: &str = include_str!(#abs);
+            cyphr::query::CyphrQuery::new(#text) #(#calls)*
+        }
+    })
+}
+
+fn function(vis: &Visibility, name: &Ident, rel: &str, file: &File) -> proc_macro2::TokenStream {
+    let mut args = Vec::new();
+    let mut calls = Vec::new();
+    for param in &file.params {
+        let Some(ident) = param_ident(param, Span::call_site()) else {
+            return syn::Error::new(
+                name.span(),
+                format!("`{rel}`: placeholder `${param}` is not a valid Rust identifier"),
+            )
+            .to_compile_error();
+        };
+        args.push(quote! { #ident: impl Into<neo4rs::BoltType> });
+        calls.push(quote! { .param(#param, #ident) });
+    }
+    let doc = format!("Query from `{rel}`:\n\n```cypher\n{}\n```", file.text);
+    let (abs, text) = (&file.abs, &file.text);
+    quote! {
+        #[doc = #doc]
+        #vis fn #name(#(#args),*) -> cyphr::query::CyphrQuery {
+            const _: &str = include_str!(#abs);
+            cyphr::query::CyphrQuery::new(#text) #(#calls)*
+        }
+    }
+}
+
+fn module(vis: &Visibility, name: &Ident, path: &LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    let dir = resolve(path);
+    let entries = std::fs::read_dir(&dir)
+        .map_err(|e| syn::Error::new(path.span(), format!("cannot read `{}`: {e}", dir.display())))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "cypher"))
+        .collect();
+    files.sort();
+
+    let mut fns = Vec::new();
+    for file in files {
+        let stem = file.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let rel = format!("{}/{}", path.value().trim_end_matches('/'), file.file_name().unwrap_or_default().to_string_lossy());
+        let Ok(fn_name) = syn::parse_str::<Ident>(&stem) else {
+            return Err(syn::Error::new(path.span(), format!("`{rel}`: `{stem}` is not a valid function name")));
+        };
+        let lit = LitStr::new(&rel, path.span());
+        let checked = read(&lit, &file)?;
+        fns.push(function(&syn::parse_quote!(pub), &fn_name, &rel, &checked));
+    }
+    Ok(quote! {
+        #vis mod #name {
+            #(#fns)*
+        }
+    })
+}
@@ -0,0 +1,359 @@
+//! Tokenizer shared by the Cypher macros.
+//!
+//! Flattens a Rust [`TokenStream`] into a list of Cypher tokens, each
+//! carrying the span of the source token so that the parser can point
+//! `compile_error!` at the exact offending token. [`lex_str`] does the same
+//! for Cypher read from a file, where tokens carry a line and column instead.
+
+use proc_macro2::{Delimiter, Spacing, Span, TokenStream, TokenTree};
+use syn::ext::IdentExt;
+
+/// The lexical class of a [`Tok`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// A bare identifier or keyword.
+    Word,
+    /// A `$name` parameter; `text` holds the bare name.
+    Param,
+    /// A numeric literal.
+    Number,
+    /// A string literal; `text` holds the literal including its quotes,
+    /// escaped for Cypher.
+    Str,
+    /// An operator or punctuation, possibly two characters long (`<>`, `..`).
+    Op,
+    /// An opening `(`, `[` or `{`.
+    Open,
+    /// A closing `)`, `]` or `}`.
+    Close,
+    /// A `#Type` label or relationship-type splice; `text` holds the path.
+    Splice,
+}
+
+/// A single Cypher token.
+#[derive(Debug, Clone)]
+pub struct Tok {
+    pub kind: Kind,
+    pub text: String,
+    pub span: Span,
+    /// 1-based line and column, for tokens read from a file.
+    pub loc: Option<(usize, usize)>,
+    /// The Rust path of a [`Kind::Splice`].
+    pub path: Option<TokenStream>,
+}
+
+impl Tok {
+    fn new(kind: Kind, text: impl Into<String>, span: Span) -> Self {
+        Tok { kind, text: text.into(), span, loc: None, path: None }
+    }
+
+    /// Whether this token is the keyword `kw` (case-insensitive).
+    pub fn is_word(&self, kw: &str) -> bool {
+        self.kind == Kind::Word && self.text.eq_ignore_ascii_case(kw)
+    }
+
+    /// Whether this token is the operator or bracket `op`.
+    pub fn is_op(&self, op: &str) -> bool {
+        matches!(self.kind, Kind::Op | Kind::Open | Kind::Close) && self.text == op
+    }
+}
+
+/// Two-character operators that Rust splits into joint punctuation.
+const JOINT_OPS: &[&str] = &["<>", "<=", ">=", "=~", "..", "+=", "::", "||", "!=", "=="];
+
+/// Flatten a macro input into Cypher tokens.
+pub fn lex(input: TokenStream) -> syn::Result<Vec<Tok>> {
+    let mut out = Vec::new();
+    lex_into(input, &mut out)?;
+    Ok(out)
+}
+
+fn lex_into(input: TokenStream, out: &mut Vec<Tok>) -> syn::Result<()> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            TokenTree::Group(g) => {
+                let (open, close) = match g.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => {
+                        lex_into(g.stream(), out)?;
+                        i += 1;
+                        continue;
+                    }
+                };
+                out.push(Tok::new(Kind::Open, open, g.span_open()));
+                lex_into(g.stream(), out)?;
+                out.push(Tok::new(Kind::Close, close, g.span_close()));
+            }
+            TokenTree::Ident(ident) => {
+                let s = ident.to_string();
+                let s = s.strip_prefix("r#").unwrap_or(&s).to_owned();
+                out.push(Tok::new(Kind::Word, s, ident.span()));
+            }
+            TokenTree::Literal(lit) => out.push(lex_literal(lit)?),
+            TokenTree::Punct(p) if p.as_char() == '#' && matches!(tokens.get(i + 1), Some(TokenTree::Ident(_))) => {
+                i = lex_splice(&tokens, i + 1, out);
+                continue;
+            }
+            TokenTree::Punct(p) if p.as_char() == '$' => {
+                let name = match tokens.get(i + 1) {
+                    Some(TokenTree::Ident(ident)) => Some(ident.unraw().to_string()),
+                    Some(TokenTree::Literal(lit)) if is_number(&lit.to_string()) => Some(lit.to_string()),
+                    _ => None,
+                };
+                match name {
+                    Some(name) => {
+                        out.push(Tok::new(Kind::Param, name, p.span()));
+                        i += 2;
+                        continue;
+                    }
+                    None => out.push(Tok::new(Kind::Op, "$", p.span())),
+                }
+            }
+            TokenTree::Punct(p) => {
+                if p.spacing() == Spacing::Joint {
+                    if let Some(TokenTree::Punct(next)) = tokens.get(i + 1) {
+                        let pair: String = [p.as_char(), next.as_char()].iter().collect();
+                        if JOINT_OPS.contains(&pair.as_str()) {
+                            let span = p.span().join(next.span()).unwrap_or_else(|| p.span());
+                            out.push(Tok::new(Kind::Op, pair, span));
+                            i += 2;
+                            continue;
+                        }
+                    }
+                }
+                out.push(Tok::new(Kind::Op, p.as_char().to_string(), p.span()));
+            }
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+/// Lex `Ident (:: Ident)*` starting at `start` into a [`Kind::Splice`];
+/// returns the index after the path.
+fn lex_splice(tokens: &[TokenTree], start: usize, out: &mut Vec<Tok>) -> usize {
+    let mut end = start + 1;
+    while let (Some(TokenTree::Punct(a)), Some(TokenTree::Punct(b)), Some(TokenTree::Ident(_))) =
+        (tokens.get(end), tokens.get(end + 1), tokens.get(end + 2))
+    {
+        if a.as_char() != ':' || a.spacing() != Spacing::Joint || b.as_char() != ':' {
+            break;
+        }
+        end += 3;
+    }
+    let path: TokenStream = tokens[start..end].iter().cloned().collect();
+    let text = path.to_string().replace(' ', "");
+    let mut tok = Tok::new(Kind::Splice, text, tokens[end - 1].span());
+    tok.path = Some(path);
+    out.push(tok);
+    end
+}
+
+fn lex_literal(lit: &proc_macro2::Literal) -> syn::Result<Tok> {
+    match syn::Lit::new(lit.clone()) {
+        syn::Lit::Str(s) => return Ok(Tok::new(Kind::Str, quote_str(&s.value(), '"'), lit.span())),
+        syn::Lit::Char(c) => return Ok(Tok::new(Kind::Str, quote_str(&c.value().to_string(), '\''), lit.span())),
+        _ => {}
+    }
+    let s = lit.to_string();
+    if is_number(&s) {
+        return Ok(Tok::new(Kind::Number, s, lit.span()));
+    }
+    Err(syn::Error::new(
+        lit.span(),
+        format!("unsupported literal `{s}` in Cypher; use a plain \"...\" string or number"),
+    ))
+}
+
+/// Quote a decoded Rust string as a Cypher string literal. Rust escapes
+/// like `\u{1F600}` are not valid Cypher, so the value is re-escaped with
+/// Cypher's own escapes and other characters are written as-is.
+fn quote_str(value: &str, quote: char) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push(quote);
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c == quote => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if c.is_control() => out.push_str(&format!("\\u{:04X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push(quote);
+    out
+}
+
+fn is_number(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// Tokenize Cypher source text, e.g. the contents of a `.cypher` file.
+///
+/// Every token gets `span` (typically the path literal) plus its line and
+/// column in `text`. Comments are skipped; quoted identifiers stay `Word`s
+/// with their backticks, so they never match a keyword.
+pub fn lex_str(text: &str, span: Span) -> syn::Result<Vec<Tok>> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut out = Vec::new();
+    let mut brackets: Vec<(char, (usize, usize))> = Vec::new();
+    let (mut line, mut col) = (1, 1);
+    let mut i = 0;
+
+    let at = |i: usize| chars.get(i).map(|&(_, c)| c);
+    let slice = |from: usize, to: usize| {
+        let start = chars[from].0;
+        let end = chars.get(to).map_or(text.len(), |&(b, _)| b);
+        &text[start..end]
+    };
+    let err = |(line, col): (usize, usize), msg: &str| {
+        syn::Error::new(span, format!("invalid Cypher at line {line}, column {col}: {msg}"))
+    };
+
+    while i < chars.len() {
+        let c = chars[i].1;
+        let loc = (line, col);
+        let start = i;
+        let next = at(i + 1);
+
+        let kind = if c.is_whitespace() {
+            i += 1;
+            None
+        } else if c == '/' && next == Some('/') {
+            while at(i).is_some_and(|c| c != '\n') {
+                i += 1;
+            }
+            None
+        } else if c == '/' && next == Some('*') {
+            i += 2;
+            while !(at(i) == Some('*') && at(i + 1) == Some('/')) {
+                if at(i).is_none() {
+                    return Err(err(loc, "unterminated comment"));
+                }
+                i += 1;
+            }
+            i += 2;
+            None
+        } else if c == '\'' || c == '"' {
+            i += 1;
+            loop {
+                match at(i) {
+                    None => return Err(err(loc, "unterminated string literal")),
+                    Some('\\') => i += 2,
+                    Some(q) if q == c => break,
+                    Some(_) => i += 1,
+                }
+            }
+            i += 1;
+            Some(Kind::Str)
+        } else if c == '`' {
+            i = skip_backticks(&chars, i).ok_or_else(|| err(loc, "unterminated quoted identifier"))?;
+            Some(Kind::Word)
+        } else if c == '$' {
+            i += 1;
+            if at(i) == Some('`') {
+                i = skip_backticks(&chars, i).ok_or_else(|| err(loc, "unterminated quoted identifier"))?;
+            } else {
+                while at(i).is_some_and(is_ident_char) {
+                    i += 1;
+                }
+            }
+            if i == start + 1 {
+                Some(Kind::Op)
+            } else {
+                Some(Kind::Param)
+            }
+        } else if c.is_ascii_digit() {
+            while let Some(c) = at(i) {
+                let exponent = matches!(c, '+' | '-')
+                    && matches!(at(i - 1), Some('e' | 'E'))
+                    && !slice(start, i).starts_with("0x");
+                let fraction = c == '.' && at(i + 1).is_some_and(|c| c.is_ascii_digit())
+                    && !slice(start, i).contains('.');
+                if is_ident_char(c) || exponent || fraction {
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            Some(Kind::Number)
+        } else if is_ident_char(c) {
+            while at(i).is_some_and(is_ident_char) {
+                i += 1;
+            }
+            Some(Kind::Word)
+        } else if matches!(c, '(' | '[' | '{') {
+            brackets.push((c, loc));
+            i += 1;
+            Some(Kind::Open)
+        } else if matches!(c, ')' | ']' | '}') {
+            let open = match c {
+                ')' => '(',
+                ']' => '[',
+                _ => '{',
+            };
+            match brackets.pop() {
+                Some((o, _)) if o == open => {}
+                _ => return Err(err(loc, &format!("unexpected `{c}`"))),
+            }
+            i += 1;
+            Some(Kind::Close)
+        } else {
+            let pair: String = [Some(c), next].iter().flatten().collect();
+            i += if JOINT_OPS.contains(&pair.as_str()) { 2 } else { 1 };
+            Some(Kind::Op)
+        };
+
+        for &(_, ch) in &chars[start..i.min(chars.len())] {
+            if ch == '\n' {
+                line += 1;
+                col = 1;
+            } else {
+                col += 1;
+            }
+        }
+        if let Some(kind) = kind {
+            let raw = slice(start, i);
+            let text = match kind {
+                Kind::Param => {
+                    let name = &raw[1..];
+                    match name.strip_prefix('`') {
+                        Some(quoted) => quoted.strip_suffix('`').unwrap_or(quoted).replace("``", "`"),
+                        None => name.to_owned(),
+                    }
+                }
+                _ => raw.to_owned(),
+            };
+            out.push(Tok { kind, text, span, loc: Some(loc), path: None });
+        }
+    }
+    if let Some((c, loc)) = brackets.pop() {
+        return Err(err(loc, &format!("unclosed `{c}`")));
+    }
+    Ok(out)
+}
+
+/// Index just past the quoted identifier starting at `start`.
+fn skip_backticks(chars: &[(usize, char)], start: usize) -> Option<usize> {
+    let mut i = start + 1;
+    loop {
+        match chars.get(i)?.1 {
+            '`' if chars.get(i + 1).is_some_and(|&(_, c)| c == '`') => i += 2,
+            '`' => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c == '_' || c.is_alphanumeric()
+}
@@ -8,14 +8,22 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 
+mod attr;
 mod node;
 mod relation;
 mod from_cyphr;
 mod cypher;
 mod cypher_query;
+mod include;
+mod lex;
+mod migrate;
+mod parse;
+mod print;
+mod props;
 mod to_cyphr_params;
+mod query_def;
 
-/// Derive [`CyphrNode`](cyphr_core::traits::CyphrNode) and [`FromCyphrValue`](cyphr_core::traits::FromCyphrValue) for a struct.
+/// Derive [`CyphrNode`](cyphr_core::traits::CyphrNode) and [`FromCyphrValue`](cyphr_core::traits::FromCyphrValue) for a struct or enum.
 ///
 /// Maps a Neo4j node to a Rust struct. Each struct field is read from a
 /// node property via `FromCyphrValue`.
@@ -30,7 +38,35 @@ mod to_cyphr_params;
 ///
 /// **Field-level:**
 /// - `#[cyphr(prop = "...")]` — override the Neo4j property name (default: field name).
-/// - `#[cyphr(id)]` — marker for the identity field (documentation only, no runtime effect).
+/// - `#[cyphr(id)]` — the identity field, used by `NodeRepository` lookups and
+///   upserts. Sets `ID` and implements `HasId` (needed by
+///   `NodeRepository::save` and `RelationOps`), so the field type must be
+///   `Clone` and implement `IntoCyphrValue`; `#[cyphr(id, no_bind)]` keeps
+///   `ID` but skips `HasId`. At most one field may be marked.
+/// - `#[cyphr(unique)]`, `#[cyphr(index)]`, `#[cyphr(required)]`,
+///   `#[cyphr(fulltext = "index_name")]` — declare constraints and indexes,
+///   returned by `CyphrNode::schema()` (see `cyphr::schema`). Fields sharing
+///   a full-text index name are indexed together.
+///
+/// `unique` already creates a backing index, so it can't be combined with
+/// `index`:
+///
+/// ```compile_fail
+/// use cyphr_macros::CyphrNode;
+///
+/// #[derive(CyphrNode)]
+/// struct User {
+///     #[cyphr(unique, index)]
+///     email: String,
+/// }
+/// ```
+///
+/// # Property keys
+///
+/// `CyphrNode::PROPS` lists every property key in field order, and a
+/// companion module named after the struct (`User` → `user_props`) holds one
+/// const per field, so queries can refer to `user_props::EMAIL` instead of
+/// repeating `"email_address"`.
 ///
 /// # Example
 ///
@@ -47,6 +83,28 @@ mod to_cyphr_params;
 ///     email: String,
 /// }
 /// ```
+///
+/// # Enums
+///
+/// On an enum whose variants each hold one node type, the derive decodes a
+/// node into the first variant whose type's label the node has. This lets
+/// one `CyphrPath` or column hold nodes of several labels:
+///
+/// ```rust,ignore
+/// #[derive(Debug, CyphrNode)]
+/// enum Content {
+///     User(User),
+///     Post(Post),
+///     Tag(Tag),
+/// }
+///
+/// // MATCH p = (:User)-[:WROTE]->(:Post)-[:TAGGED]->(:Tag) RETURN p
+/// let p: CyphrPath<Content> = ...;
+/// ```
+///
+/// The enum has no single label, so it implements only
+/// [`FromCyphrNode`](cyphr_core::traits::FromCyphrNode), not `CyphrNode`:
+/// it is for decoding, not for patterns or repositories.
 #[proc_macro_derive(CyphrNode, attributes(cyphr))]
 pub fn cyphr_node(input: TokenStream) -> TokenStream {
     node::expand(input)
@@ -66,9 +124,22 @@ pub fn cyphr_node(input: TokenStream) -> TokenStream {
 /// - `#[cyphr(type = "...")]` — set the relationship type. Defaults to the struct name.
 /// - `#[cyphr(from = "...")]` — optional start-node label (compile-time documentation).
 /// - `#[cyphr(to = "...")]` — optional end-node label (compile-time documentation).
+/// - `#[cyphr(from_type = User)]` — optional start-node type, a
+///   `#[derive(CyphrNode)]` struct. Implements `RelFrom` for that type only,
+///   so typed queries can't start the relationship anywhere else; without it
+///   `RelFrom` holds for every node type. Also fills `FROM_LABEL` when
+///   `from` is omitted.
+/// - `#[cyphr(to_type = User)]` — optional end-node type; implements `RelTo`
+///   likewise.
 ///
 /// **Field-level:**
 /// - `#[cyphr(prop = "...")]` — override the Neo4j property name (default: field name).
+/// - `#[cyphr(unique)]`, `#[cyphr(index)]`, `#[cyphr(required)]`,
+///   `#[cyphr(fulltext = "index_name")]` — as for [`CyphrNode`](macro@CyphrNode),
+///   returned by `CyphrRelation::schema()`.
+///
+/// Property keys are exposed as `CyphrRelation::PROPS` and a companion
+/// module, as for [`CyphrNode`](macro@CyphrNode) (`Follows` → `follows_props`).
 ///
 /// # Example
 ///
@@ -133,10 +204,22 @@ pub fn from_cyphr(input: TokenStream) -> TokenStream {
     from_cyphr::expand(input)
 }
 
-/// Converts a Cypher token block into a `&'static str` with normalized whitespace.
-///
-/// The output is intentionally literal — no rewriting or validation. You can
-/// paste the resulting string directly into Neo4j Browser.
+/// Converts a Cypher token block into a `&'static str` with canonical spacing.
+///
+/// The text is reprinted from the parsed tokens rather than copied, so
+/// only its spelling changes, never its meaning. String literals are
+/// decoded and re-escaped for Cypher (`"\u{e9}"` becomes `"é"`), arrows
+/// and labels are glued (`(u:User)-[:FOLLOWS]->(f)`), map entries read
+/// `{name: $name}` and operators are surrounded by single spaces, so the
+/// result can be pasted directly into Neo4j Browser.
+///
+/// The query is parsed at compile time: a misspelled clause keyword,
+/// unbalanced pattern or missing expression becomes a compile error pointing
+/// at the offending token. Schema and admin commands (`CREATE INDEX`,
+/// `SHOW CONSTRAINTS`, ...) are passed through unchecked. A query may start
+/// with `EXPLAIN`, `PROFILE` or `CYPHER` options (`CYPHER runtime=slotted`).
+/// Clause keywords such as `ORDER` or `LIMIT` can still name variables where
+/// no clause could start, as in `MATCH (order:Order) RETURN order`.
 ///
 /// # Example
 ///
@@ -149,6 +232,34 @@ pub fn from_cyphr(input: TokenStream) -> TokenStream {
 ///     RETURN f.name, f.id
 /// };
 /// ```
+///
+/// A typo is rejected with a hint:
+///
+/// ```compile_fail
+/// // error: invalid Cypher: expected a clause keyword, found `RETRUN` (did you mean `RETURN`?)
+/// let q: &str = cyphr_macros::cypher! { MATCH (u:User) RETRUN u };
+/// ```
+///
+/// ```compile_fail
+/// // error: invalid Cypher: `!=` is not a Cypher operator, use `<>`
+/// let q: &str = cyphr_macros::cypher! { MATCH (u:User) WHERE u.age != 30 RETURN u };
+/// ```
+///
+/// # Label splices
+///
+/// `:#Type` in a node label position expands to the type's
+/// [`CyphrNode::LABEL`](cyphr_core::traits::CyphrNode::LABEL), and in a
+/// relationship-type position to
+/// [`CyphrRelation::TYPE`](cyphr_core::traits::CyphrRelation::TYPE), so the
+/// name lives only in the derive attribute. Names that aren't plain
+/// identifiers are backtick-quoted like [`escape`](cyphr_core::ident::escape)
+/// does (`` :`Blog Post` ``). The text is assembled in a `const`, so the
+/// result is still a `&'static str`:
+///
+/// ```rust,ignore
+/// let q: &str = cypher! { MATCH (u:#User)-[:#Follows]->(f:#User) RETURN f };
+/// // "MATCH (u:User)-[:FOLLOWS]->(f:User) RETURN f"
+/// ```
 #[proc_macro]
 pub fn cypher(input: TokenStream) -> TokenStream {
     cypher::expand(input)
@@ -156,9 +267,19 @@ pub fn cypher(input: TokenStream) -> TokenStream {
 
 /// Builds a [`CyphrQuery`](cyphr::query::CyphrQuery) from a Cypher block with auto-bound parameters.
 ///
-/// Any `$ident` in the Cypher text becomes a named parameter. The Rust
-/// variable `ident` must be in scope and implement `Into<BoltType>`.
-/// Duplicate parameters are bound only once.
+/// Any `$ident` in the Cypher text becomes a named parameter. By default the
+/// Rust variable `ident` must be in scope and implement `Into<BoltType>`.
+/// Duplicate parameters are bound only once. The query text is checked at
+/// compile time exactly like [`cypher!`], and `:#Type` label splices work
+/// the same way.
+///
+/// To bind a placeholder to any other expression, list it after a `;`:
+/// `name = expr` pairs separated by commas, or write it inline right after
+/// the placeholder, as `$name(expr)`; the parenthesized expression is left
+/// out of the query text. Placeholders without a binding still capture the
+/// local variable of the same name, and `$r#type` binds `type` from the
+/// variable `r#type`. A binding whose name is not used by the query, or a
+/// placeholder bound twice, is a compile error.
 ///
 /// # Example
 ///
@@ -173,15 +294,86 @@ pub fn cypher(input: TokenStream) -> TokenStream {
 ///     RETURN u
 /// };
 /// // Expands to:
-/// //   CyphrQuery::new("MATCH (u:User {name:$name}) WHERE u.age > $age RETURN u")
+/// //   CyphrQuery::new("MATCH (u:User {name: $name}) WHERE u.age > $age RETURN u")
 /// //       .param("name", name)
 /// //       .param("age", age)
 /// ```
+///
+/// With explicit bindings:
+///
+/// ```rust,ignore
+/// let query = cypher_query!(
+///     MATCH (u:User {id: $id}) RETURN u LIMIT $limit;
+///     id = user.id, limit = page.size
+/// );
+/// let query = cypher_query! { MATCH (u:User {id: $id(user.id)}) RETURN u };
+/// ```
 #[proc_macro]
 pub fn cypher_query(input: TokenStream) -> TokenStream {
     cypher_query::expand(input)
 }
 
+/// Loads Cypher from a file at compile time, resolved relative to
+/// `CARGO_MANIFEST_DIR`.
+///
+/// The file is checked with the same parser as [`cypher!`] (errors report
+/// the line and column in the file) and its text is sent to Neo4j verbatim.
+/// Editing the file triggers a rebuild.
+///
+/// Three forms are supported:
+///
+/// - `include_cypher!("queries/find_user.cypher")` builds a
+///   [`CyphrQuery`](cyphr::query::CyphrQuery) with `$params` auto-bound like
+///   [`cypher_query!`], including an optional `; name = expr` binding section.
+/// - `include_cypher!(pub fn find_user, "queries/find_user.cypher");` defines a
+///   function taking one `impl Into<BoltType>` argument per placeholder, in
+///   order of first appearance, and returning the `CyphrQuery`.
+/// - `include_cypher!(pub mod queries, "queries");` defines a module with one
+///   such function per `.cypher` file in the directory, named after the file.
+///   Adding a file to the directory requires a rebuild to be picked up.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use cyphr::prelude::*;
+///
+/// // queries/find_user.cypher:
+/// //   MATCH (u:User {id: $id})
+/// //   RETURN u LIMIT $limit
+///
+/// let id: i64 = 7;
+/// let query = include_cypher!("queries/find_user.cypher"; limit = 10);
+///
+/// include_cypher!(fn find_user, "queries/find_user.cypher");
+/// let query = find_user(7, 10);
+/// ```
+#[proc_macro]
+pub fn include_cypher(input: TokenStream) -> TokenStream {
+    include::expand(input)
+}
+
+/// Embed a directory of migration files at compile time.
+///
+/// Expands to a `cyphr::migrate::Migrator` holding every
+/// `{version}_{name}.cypher` file in the directory, resolved relative to
+/// `CARGO_MANIFEST_DIR`. Each `;`-separated statement is checked with the
+/// same parser as [`cypher!`], and file names must start with a unique
+/// version number. Editing a file triggers a rebuild; adding one requires
+/// a rebuild to be picked up.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// // migrations/0001_create_users.cypher
+/// // migrations/0002_backfill_names.cypher
+/// let migrator = cyphr::embed_migrations!("migrations");
+/// migrator.run(&graph).await?;
+/// ```
+#[proc_macro]
+pub fn embed_migrations(input: TokenStream) -> TokenStream {
+    migrate::expand(input)
+}
+
 /// Derive `ToCyphrParams` for a struct.
 ///
 /// Converts each field into a named query parameter via `IntoCyphrValue`.
@@ -211,3 +403,42 @@ pub fn cypher_query(input: TokenStream) -> TokenStream {
 pub fn to_cyphr_params(input: TokenStream) -> TokenStream {
     to_cyphr_params::expand(input)
 }
+
+/// Derive a typed query definition for a struct.
+///
+/// The struct's fields are the query parameters. At compile time the query is
+/// checked like [`cypher!`] and every `$placeholder` must match a field (after
+/// `prop` renames) and every field must be used. Generates
+/// [`ToCyphrParams`](cyphr_core::traits::ToCyphrParams),
+/// [`CyphrQueryDef`](cyphr::query::CyphrQueryDef) and inherent `query()`,
+/// `fetch_one`, `fetch_optional`, `fetch_all`, `fetch_stream` and `_in`
+/// methods returning the `returns` type.
+///
+/// # Attributes
+///
+/// **Struct-level:**
+/// - `#[cyphr(query = "...")]` — the Cypher text (required).
+/// - `#[cyphr(returns = "Type")]` — the [`FromCyphr`](cyphr_core::traits::FromCyphr)
+///   row type (default: `CyphrRow`).
+///
+/// **Field-level:**
+/// - `#[cyphr(prop = "...")]` — bind the field to a differently named placeholder.
+/// - `#[cyphr(skip)]` — not a parameter.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use cyphr::prelude::*;
+///
+/// #[derive(CyphrQueryDef)]
+/// #[cyphr(query = "MATCH (u:User {id: $id}) RETURN u", returns = "UserRow")]
+/// struct FindUser {
+///     id: i64,
+/// }
+///
+/// let user: UserRow = FindUser { id: 7 }.fetch_one(&graph).await?;
+/// ```
+#[proc_macro_derive(CyphrQueryDef, attributes(cyphr))]
+pub fn cyphr_query_def(input: TokenStream) -> TokenStream {
+    query_def::expand(input)
+}
@@ -0,0 +1,90 @@
+//! Implementation of the `embed_migrations!` proc macro.
+//!
+//! Reads every `{version}_{name}.cypher` file in a directory relative to
+//! `CARGO_MANIFEST_DIR`, checks each `;`-separated statement with the same
+//! parser as `cypher!`, and builds a `cyphr::migrate::Migrator` holding the
+//! file texts verbatim so checksums match files loaded at runtime.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use std::path::PathBuf;
+use syn::{parse_macro_input, LitStr};
+
+use crate::include::resolve;
+use crate::lex::{self, Kind, Tok};
+use crate::parse;
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    match migrations(&path) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn migrations(path: &LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    let dir = resolve(path);
+    let entries = std::fs::read_dir(&dir)
+        .map_err(|e| syn::Error::new(path.span(), format!("cannot read `{}`: {e}", dir.display())))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "cypher"))
+        .collect();
+    files.sort();
+
+    let mut seen: Vec<(i64, String)> = Vec::new();
+    let mut includes = Vec::new();
+    let mut entries = Vec::new();
+    for file in files {
+        let file_name = file.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let rel = format!("{}/{file_name}", path.value().trim_end_matches('/'));
+        let Some((version, name)) = parse_file_name(&file_name) else {
+            return Err(syn::Error::new(
+                path.span(),
+                format!("`{rel}` is not named like `0001_description.cypher`"),
+            ));
+        };
+        if let Some((_, other)) = seen.iter().find(|(v, _)| *v == version) {
+            return Err(syn::Error::new(
+                path.span(),
+                format!("`{rel}` and `{other}` both have version {version}"),
+            ));
+        }
+        let text = std::fs::read_to_string(&file)
+            .map_err(|e| syn::Error::new(path.span(), format!("cannot read `{}`: {e}", file.display())))?;
+        check(&text, path).map_err(|e| syn::Error::new(path.span(), format!("{rel}: {e}")))?;
+
+        let abs = file.display().to_string();
+        includes.push(quote! { const _: &str = include_str!(#abs); });
+        entries.push(quote! { cyphr::migrate::Migration::new(#version, #name, #text) });
+        seen.push((version, rel));
+    }
+
+    Ok(quote! {
+        {
+            #(#includes)*
+            cyphr::migrate::Migrator::new(::std::vec![#(#entries),*])
+        }
+    })
+}
+
+/// Check every `;`-separated statement in `text`.
+fn check(text: &str, path: &LitStr) -> syn::Result<()> {
+    let toks = lex::lex_str(text, path.span())?;
+    for statement in toks.split(|t: &Tok| t.kind == Kind::Op && t.text == ";") {
+        if !statement.is_empty() {
+            parse::parse(statement)?;
+        }
+    }
+    Ok(())
+}
+
+/// `0001_create_users.cypher` → `(1, "create_users")`.
+fn parse_file_name(file_name: &str) -> Option<(i64, String)> {
+    let stem = file_name.strip_suffix(".cypher")?;
+    let (version, name) = stem.split_once('_')?;
+    if version.is_empty() || !version.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((version.parse().ok()?, name.trim_start_matches('_').to_owned()))
+}
@@ -1,35 +1,42 @@
 
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Data, Fields, Expr, ExprLit, Lit};
-
-fn get_label(ast: &DeriveInput) -> String {
-    for attr in &ast.attrs {
-        if attr.path().is_ident("cyphr") {
-            let mut label = None;
-            let _ = attr.parse_nested_meta(|meta| {
+use quote::{quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, DeriveInput, Data, Fields};
+
+use crate::attr;
+use crate::props::{self, Prop, SchemaAttrs};
+
+fn get_label(ast: &DeriveInput) -> syn::Result<String> {
+    let mut label = None;
+    for a in &ast.attrs {
+        if a.path().is_ident("cyphr") {
+            a.parse_nested_meta(|meta| {
                 if meta.path.is_ident("label") {
-                    let value = meta.value()?;
-                    let expr: Expr = value.parse()?;
-                    if let Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) = expr {
-                        label = Some(s.value());
-                    }
+                    label = Some(attr::string(&meta)?.value());
+                    Ok(())
+                } else {
+                    attr::skip(&meta)
                 }
-                Ok(())
-            });
-            if let Some(l) = label {
-                return l;
-            }
+            })?;
         }
     }
-    ast.ident.to_string()
+    Ok(label.unwrap_or_else(|| ast.ident.unraw().to_string()))
 }
 
 pub fn expand(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     let name = &ast.ident;
 
-    let label = get_label(&ast);
+    if let Data::Enum(data) = &ast.data {
+        return expand_enum(&ast, data);
+    }
+
+    let label = match get_label(&ast) {
+        Ok(label) => label,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     let fields = match &ast.data {
         Data::Struct(s) => match &s.fields {
@@ -41,7 +48,7 @@ pub fn expand(input: TokenStream) -> TokenStream {
             }
         },
         _ => {
-            return syn::Error::new_spanned(&ast, "CyphrNode only supports structs")
+            return syn::Error::new_spanned(&ast, "CyphrNode only supports structs and enums")
                 .to_compile_error()
                 .into();
         }
@@ -49,50 +56,113 @@ pub fn expand(input: TokenStream) -> TokenStream {
 
     // For each field, generate: field: <ty as FromCyphrValue>::from_value(node_prop(...).ok_or(..)?)?
     let mut inits = Vec::new();
+    let mut props = Vec::new();
+    let mut id: Option<(&syn::Ident, &syn::Type, String, bool)> = None;
 
     for f in fields {
         let ident = f.ident.as_ref().unwrap();
-        let key = ident.to_string();
+        let key = ident.unraw().to_string();
         let ty = &f.ty;
 
         // Support #[cyphr(id)] or #[cyphr(prop="...")]
         let mut prop_key = key.clone();
-        for attr in &f.attrs {
-            if attr.path().is_ident("cyphr") {
-                let _ = attr.parse_nested_meta(|meta| {
-                    if meta.path.is_ident("id") {
-                        // keep same key; marker only for now
-                    } else if meta.path.is_ident("prop") {
-                        let value = meta.value()?;
-                        let expr: Expr = value.parse()?;
-                        if let Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) = expr {
-                            prop_key = s.value();
-                        }
-                    }
-                    Ok(())
-                });
+        let mut schema = SchemaAttrs::default();
+        let mut is_id = false;
+        let mut no_bind = false;
+        for a in &f.attrs {
+            if !a.path().is_ident("cyphr") {
+                continue;
+            }
+            let parsed = a.parse_nested_meta(|meta| {
+                if props::schema_attr(&meta, &mut schema)? {
+                    // collected for schema()
+                } else if meta.path.is_ident("id") {
+                    is_id = true;
+                } else if meta.path.is_ident("no_bind") {
+                    no_bind = true;
+                } else if meta.path.is_ident("prop") {
+                    prop_key = attr::string(&meta)?.value();
+                } else {
+                    attr::skip(&meta)?;
+                }
+                Ok(())
+            });
+            if let Err(err) = parsed {
+                return err.to_compile_error().into();
             }
         }
 
+        if no_bind && !is_id {
+            return syn::Error::new_spanned(f, "`no_bind` only applies to the `#[cyphr(id)]` field")
+                .to_compile_error()
+                .into();
+        }
+        if is_id {
+            if id.is_some() {
+                return syn::Error::new_spanned(f, "only one field may be marked `#[cyphr(id)]`")
+                    .to_compile_error()
+                    .into();
+            }
+            id = Some((ident, ty, prop_key.clone(), no_bind));
+        }
+        props.push(Prop { field: ident.clone(), ty: ty.clone(), key: prop_key.clone(), schema });
         inits.push(quote! {
             #ident: {
                 let v = cyphr_core::props::node_prop(node, #prop_key)
-                    .ok_or_else(|| cyphr_core::error::CyphrError::missing_property(#prop_key, <Self as cyphr_core::traits::CyphrNode>::LABEL))?;
+                    .ok_or_else(|| cyphr_core::error::CyphrError::missing_property(#prop_key, #label))?;
                 <#ty as cyphr_core::traits::FromCyphrValue>::from_value(v)
                     .map_err(|e| e.with_context(format!("{}::{} (prop '{}')", #label, #key, #prop_key)))?
             }
         });
     }
 
+    let props_slice = props::slice(&props);
+    let props_module = props::module(&ast.vis, name, &props);
+    let schema_fn = match props::schema_fn(
+        quote! { cyphr_core::schema::SchemaDef::node(<Self as cyphr_core::traits::CyphrNode>::LABEL) },
+        &props,
+    ) {
+        Ok(tokens) => tokens,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let id_const = match &id {
+        Some((_, _, key, _)) => quote! { const ID: Option<&'static str> = Some(#key); },
+        None => quote! {},
+    };
+    // Spanned on the id type so an id that can't be bound is reported at
+    // the field; `no_bind` opts out of `HasId` instead.
+    let has_id = match &id {
+        Some((field, ty, key, false)) => {
+            let value = quote_spanned! { ty.span()=>
+                cyphr_core::traits::IntoCyphrValue::into_value(::std::clone::Clone::clone(&self.#field))
+            };
+            quote! {
+                impl cyphr_core::traits::HasId for #name {
+                    const ID_KEY: &'static str = #key;
+
+                    fn id_value(&self) -> neo4rs::BoltType {
+                        #value
+                    }
+                }
+            }
+        }
+        _ => quote! {},
+    };
+
     let expanded = quote! {
         impl cyphr_core::traits::CyphrNode for #name {
             const LABEL: &'static str = #label;
+            const PROPS: &'static [&'static str] = #props_slice;
+            #id_const
 
             fn from_node(node: &neo4rs::BoltNode) -> Result<Self, cyphr_core::error::CyphrError> {
                 Ok(Self {
                     #(#inits,)*
                 })
             }
+
+            #schema_fn
         }
 
         impl cyphr_core::traits::FromCyphrValue for #name {
@@ -105,6 +175,63 @@ pub fn expand(input: TokenStream) -> TokenStream {
                 }
             }
         }
+
+        #has_id
+
+        #props_module
+    };
+
+    expanded.into()
+}
+
+/// An enum of node types, decoded into the first variant whose type's
+/// label the node has.
+fn expand_enum(ast: &DeriveInput, data: &syn::DataEnum) -> TokenStream {
+    let name = &ast.ident;
+    let enum_name = name.unraw().to_string();
+
+    let mut arms = Vec::new();
+    for variant in &data.variants {
+        let ty = match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => {
+                return syn::Error::new_spanned(variant, "CyphrNode enum variants must hold exactly one node type, e.g. `User(User)`")
+                    .to_compile_error()
+                    .into();
+            }
+        };
+        let ident = &variant.ident;
+        arms.push(quote! {
+            if cyphr_core::props::has_label(node, <#ty as cyphr_core::traits::CyphrNode>::LABEL) {
+                return <#ty as cyphr_core::traits::CyphrNode>::from_node(node)
+                    .map(Self::#ident)
+                    .map_err(|e| e.with_context(#enum_name));
+            }
+        });
+    }
+
+    let expanded = quote! {
+        impl cyphr_core::traits::FromCyphrNode for #name {
+            fn from_cyphr_node(node: &neo4rs::BoltNode) -> Result<Self, cyphr_core::error::CyphrError> {
+                #(#arms)*
+                Err(cyphr_core::error::CyphrError::Mapping(format!(
+                    "no variant of `{}` matches labels {:?}",
+                    #enum_name,
+                    cyphr_core::props::node_labels(node),
+                )))
+            }
+        }
+
+        impl cyphr_core::traits::FromCyphrValue for #name {
+            fn from_value(value: neo4rs::BoltType) -> Result<Self, cyphr_core::error::CyphrError> {
+                match value {
+                    neo4rs::BoltType::Node(n) => <Self as cyphr_core::traits::FromCyphrNode>::from_cyphr_node(&n),
+                    other => Err(cyphr_core::error::CyphrError::type_mismatch(
+                        "Node", cyphr_core::value::type_name(&other), #enum_name,
+                    )),
+                }
+            }
+        }
     };
 
     expanded.into()
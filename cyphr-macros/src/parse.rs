@@ -0,0 +1,1358 @@
+//! A recursive-descent validator for the openCypher subset accepted by the
+//! `cypher!` family of macros.
+//!
+//! The parser only checks structure; it does not build a syntax tree. It is
+//! permissive where Neo4j is (keywords as property keys and map keys,
+//! namespaced functions, schema and admin commands) and strict where typos
+//! usually hide: clause keywords, bracket balance, patterns and missing
+//! expressions. Errors carry the span of the offending token.
+//!
+//! Along the way it records spacing hints for the printer: which tokens are
+//! part of a relationship arrow, a label, a call or an index and must be
+//! glued to their neighbours.
+
+use crate::lex::{Kind, Tok};
+use crate::print::Glue;
+
+/// Operator and expression keywords that can never be used as a bare
+/// variable.
+const RESERVED: &[&str] = &[
+    "ON", "AND", "OR", "XOR", "NOT", "IN", "IS", "AS", "STARTS", "ENDS", "CONTAINS", "CASE",
+    "WHEN", "THEN", "ELSE", "END", "DISTINCT",
+];
+
+/// Keywords that may start a clause, used for "did you mean" hints. They
+/// are ordinary variables (`MATCH (order:Order)`) unless followed by
+/// something only a clause can start with; see [`Parser::keyword_at`].
+const CLAUSES: &[&str] = &[
+    "MATCH", "OPTIONAL", "WHERE", "RETURN", "WITH", "UNWIND", "CREATE", "MERGE", "SET",
+    "REMOVE", "DELETE", "DETACH", "NODETACH", "CALL", "FOREACH", "UNION", "ORDER", "SKIP",
+    "LIMIT", "LOAD", "USE", "FINISH", "YIELD",
+];
+
+/// Leading keywords of schema and administration commands. Those are only
+/// checked for bracket balance.
+const ADMIN: &[&str] = &[
+    "SHOW", "DROP", "ALTER", "GRANT", "DENY", "REVOKE", "START", "STOP", "TERMINATE",
+    "ENABLE", "DRYRUN", "RENAME",
+];
+
+/// Words that may follow `CREATE` in a schema or administration command.
+const CREATE_ADMIN: &[&str] = &[
+    "CONSTRAINT", "INDEX", "RANGE", "TEXT", "POINT", "LOOKUP", "FULLTEXT", "VECTOR", "BTREE",
+    "DATABASE", "COMPOSITE", "ALIAS", "USER", "ROLE", "OR",
+];
+
+struct SyntaxError {
+    /// Index of the offending token; `toks.len()` means end of input.
+    pos: usize,
+    msg: String,
+}
+
+type PResult<T = ()> = Result<T, SyntaxError>;
+
+/// What the printer needs to know about a validated statement.
+pub struct Layout {
+    /// A spacing hint per token.
+    pub glue: Vec<Glue>,
+    /// Indices of `#Type` splices in relationship-type position; all other
+    /// splices are node labels.
+    pub rel_types: Vec<usize>,
+}
+
+/// Validate a complete Cypher statement.
+pub fn parse(toks: &[Tok]) -> syn::Result<Layout> {
+    let mut p = Parser { toks, pos: 0, marks: Vec::new(), in_rel: false, rel_types: Vec::new() };
+    p.statement().map_err(|e| p.to_syn(e))?;
+
+    let mut glue = vec![Glue::default(); toks.len()];
+    for (i, mark) in p.marks {
+        glue[i].before = mark.before.or(glue[i].before);
+        glue[i].after = mark.after.or(glue[i].after);
+    }
+    Ok(Layout { glue, rel_types: p.rel_types })
+}
+
+struct Parser<'a> {
+    toks: &'a [Tok],
+    pos: usize,
+    /// Spacing hints by token index, discarded when a branch backtracks.
+    marks: Vec<(usize, Glue)>,
+    /// Whether label expressions are currently relationship types.
+    in_rel: bool,
+    rel_types: Vec<usize>,
+}
+
+impl<'a> Parser<'a> {
+    // -----------------------------------------------------------------------
+    // Token helpers
+    // -----------------------------------------------------------------------
+
+    fn peek(&self) -> Option<&'a Tok> {
+        self.toks.get(self.pos)
+    }
+
+    fn peek_at(&self, n: usize) -> Option<&'a Tok> {
+        self.toks.get(self.pos + n)
+    }
+
+    fn at_word(&self, kw: &str) -> bool {
+        self.peek().is_some_and(|t| t.is_word(kw))
+    }
+
+    fn at_op(&self, op: &str) -> bool {
+        self.peek().is_some_and(|t| t.is_op(op))
+    }
+
+    fn at_kind(&self, kind: Kind) -> bool {
+        self.peek().is_some_and(|t| t.kind == kind)
+    }
+
+    fn eat_word(&mut self, kw: &str) -> bool {
+        let hit = self.at_word(kw);
+        if hit {
+            self.pos += 1;
+        }
+        hit
+    }
+
+    fn eat_op(&mut self, op: &str) -> bool {
+        let hit = self.at_op(op);
+        if hit {
+            self.pos += 1;
+        }
+        hit
+    }
+
+    fn expect_word(&mut self, kw: &str) -> PResult {
+        if self.eat_word(kw) {
+            Ok(())
+        } else {
+            Err(self.expected(&format!("`{kw}`")))
+        }
+    }
+
+    fn expect_op(&mut self, op: &str) -> PResult {
+        if self.eat_op(op) {
+            Ok(())
+        } else {
+            Err(self.expected(&format!("`{op}`")))
+        }
+    }
+
+    fn expected(&self, what: &str) -> SyntaxError {
+        let msg = match self.peek() {
+            Some(t) => format!("expected {what}, found `{}`", t.text),
+            None => format!("expected {what}"),
+        };
+        SyntaxError { pos: self.pos, msg }
+    }
+
+    fn to_syn(&self, e: SyntaxError) -> syn::Error {
+        if let Some((line, col)) = self.toks.get(e.pos).or(self.toks.last()).and_then(|t| t.loc) {
+            let span = self.toks[0].span;
+            let msg = if e.pos < self.toks.len() { e.msg } else { format!("unexpected end of query, {}", e.msg) };
+            return syn::Error::new(span, format!("invalid Cypher at line {line}, column {col}: {msg}"));
+        }
+        match self.toks.get(e.pos) {
+            Some(t) => syn::Error::new(t.span, format!("invalid Cypher: {}", e.msg)),
+            None if self.toks.is_empty() => {
+                syn::Error::new(proc_macro2::Span::call_site(), format!("invalid Cypher: {}", e.msg))
+            }
+            None => {
+                let span = self.toks.last()
+                    .map_or_else(proc_macro2::Span::call_site, |t| t.span);
+                syn::Error::new(span, format!("invalid Cypher: unexpected end of query, {}", e.msg))
+            }
+        }
+    }
+
+    /// Run `f`, rewinding to the current position if it fails.
+    fn attempt<T>(&mut self, f: impl FnOnce(&mut Self) -> PResult<T>) -> PResult<T> {
+        let start = self.pos;
+        let marks = self.marks.len();
+        let rel_types = self.rel_types.len();
+        let res = f(self);
+        if res.is_err() {
+            self.pos = start;
+            self.marks.truncate(marks);
+            self.rel_types.truncate(rel_types);
+        }
+        res
+    }
+
+    /// Attach a spacing hint to the token just consumed.
+    fn mark(&mut self, before: Option<bool>, after: Option<bool>) {
+        self.marks.push((self.pos - 1, Glue { before, after }));
+    }
+
+    /// Glue the token just consumed to both neighbours.
+    fn glue(&mut self) {
+        self.mark(Some(true), Some(true));
+    }
+
+    /// Keep a space inside the subquery brace just consumed: `CALL { ... }`.
+    fn pad(&mut self) {
+        if self.toks[self.pos - 1].kind == Kind::Open {
+            self.mark(None, Some(false));
+        } else {
+            self.mark(Some(false), None);
+        }
+    }
+
+    /// Glue the token just consumed to its left neighbour (calls, indexes).
+    fn glue_left(&mut self) {
+        self.mark(Some(true), None);
+    }
+
+    /// Try `first`, then `second`; on double failure report whichever error
+    /// got further into the input.
+    fn either(
+        &mut self,
+        first: impl FnOnce(&mut Self) -> PResult,
+        second: impl FnOnce(&mut Self) -> PResult,
+    ) -> PResult {
+        match self.attempt(first) {
+            Ok(()) => Ok(()),
+            Err(e1) => match self.attempt(second) {
+                Ok(()) => Ok(()),
+                Err(e2) => Err(if e1.pos > e2.pos { e1 } else { e2 }),
+            },
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Names
+    // -----------------------------------------------------------------------
+
+    /// Any symbolic name, keywords included (labels, property keys, aliases).
+    fn name(&mut self) -> PResult {
+        if self.at_kind(Kind::Word) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.expected("a name"))
+        }
+    }
+
+    fn at_variable(&self) -> bool {
+        self.peek().is_some_and(|t| t.kind == Kind::Word) && !self.keyword_at(0)
+    }
+
+    /// Whether the word `n` tokens ahead is a keyword rather than a
+    /// variable.
+    ///
+    /// Operator keywords always are. A clause keyword is one only when the
+    /// next token couldn't follow a variable: a plain word (`ORDER BY`,
+    /// `RETURN n`), a number or parameter (`LIMIT 10`), or an opening
+    /// bracket (`MATCH (`, `CALL {`). So `order` in
+    /// `MATCH (order:Order) RETURN order` is a variable.
+    fn keyword_at(&self, n: usize) -> bool {
+        let Some(t) = self.peek_at(n) else { return false };
+        if is_reserved(t) {
+            return true;
+        }
+        if !is_clause_keyword(t) {
+            return false;
+        }
+        match self.peek_at(n + 1) {
+            None => false,
+            Some(next) => match next.kind {
+                Kind::Word => !is_reserved(next) && !is_clause_keyword(next),
+                Kind::Number | Kind::Param | Kind::Splice => true,
+                Kind::Open => next.text == "(" || next.text == "{",
+                _ => false,
+            },
+        }
+    }
+
+    fn variable(&mut self) -> PResult {
+        if self.at_variable() {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.expected("a variable"))
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Statements and clauses
+    // -----------------------------------------------------------------------
+
+    fn statement(&mut self) -> PResult {
+        if self.toks.is_empty() {
+            return Err(SyntaxError { pos: 0, msg: "query is empty".into() });
+        }
+        self.query_options()?;
+        if self.is_admin_command() {
+            return Ok(());
+        }
+        self.query()?;
+        self.eat_op(";");
+        if self.peek().is_some() {
+            return Err(self.expected("end of query"));
+        }
+        Ok(())
+    }
+
+    /// `EXPLAIN`, `PROFILE` and `CYPHER [version] [option=value ...]`
+    /// prefixes.
+    fn query_options(&mut self) -> PResult {
+        loop {
+            if self.eat_word("EXPLAIN") || self.eat_word("PROFILE") {
+                continue;
+            }
+            if !self.eat_word("CYPHER") {
+                return Ok(());
+            }
+            if self.at_kind(Kind::Number) {
+                self.pos += 1;
+            }
+            while self.at_kind(Kind::Word) && self.peek_at(1).is_some_and(|t| t.is_op("=")) {
+                self.pos += 2;
+                self.glue_left();
+                if !(self.at_kind(Kind::Word) || self.at_kind(Kind::Number)) {
+                    return Err(self.expected("an option value"));
+                }
+                self.pos += 1;
+                self.glue_left();
+            }
+        }
+    }
+
+    fn is_admin_command(&self) -> bool {
+        let Some(first) = self.peek() else { return false };
+        if ADMIN.iter().any(|kw| first.is_word(kw)) {
+            return true;
+        }
+        first.is_word("CREATE")
+            && self.peek_at(1).is_some_and(|t| CREATE_ADMIN.iter().any(|kw| t.is_word(kw)))
+    }
+
+    fn query(&mut self) -> PResult {
+        self.single_query()?;
+        while self.eat_word("UNION") {
+            let _ = self.eat_word("ALL") || self.eat_word("DISTINCT");
+            self.single_query()?;
+        }
+        Ok(())
+    }
+
+    fn single_query(&mut self) -> PResult {
+        let mut clauses = 0;
+        while self.clause()? {
+            clauses += 1;
+        }
+        match self.peek() {
+            None => {}
+            Some(t) if t.kind == Kind::Close || t.is_op(";") || t.is_word("UNION") => {}
+            Some(t) if t.kind == Kind::Word => {
+                let mut msg = format!("expected a clause keyword, found `{}`", t.text);
+                if let Some(hint) = suggest(&t.text) {
+                    msg.push_str(&format!(" (did you mean `{hint}`?)"));
+                }
+                return Err(SyntaxError { pos: self.pos, msg });
+            }
+            Some(_) if clauses > 0 => return Err(self.expected("a clause keyword")),
+            Some(_) => {}
+        }
+        if clauses == 0 {
+            return Err(self.expected("a clause such as MATCH or RETURN"));
+        }
+        Ok(())
+    }
+
+    /// Parse one clause if the next token starts one.
+    fn clause(&mut self) -> PResult<bool> {
+        let Some(t) = self.peek() else { return Ok(false) };
+        if t.kind != Kind::Word {
+            return Ok(false);
+        }
+        match t.text.to_ascii_uppercase().as_str() {
+            "OPTIONAL" => {
+                self.pos += 1;
+                if self.at_word("MATCH") {
+                    self.match_clause()?;
+                } else if self.at_word("CALL") {
+                    self.call_clause()?;
+                } else {
+                    return Err(self.expected("`MATCH` or `CALL`"));
+                }
+            }
+            "MATCH" => self.match_clause()?,
+            "UNWIND" => {
+                self.pos += 1;
+                self.expr()?;
+                self.expect_word("AS")?;
+                self.variable()?;
+            }
+            "WITH" => {
+                self.pos += 1;
+                self.projection()?;
+                if self.eat_word("WHERE") {
+                    self.expr()?;
+                }
+            }
+            "RETURN" => {
+                self.pos += 1;
+                self.projection()?;
+            }
+            "CREATE" => {
+                self.pos += 1;
+                self.pattern_list()?;
+            }
+            "MERGE" => {
+                self.pos += 1;
+                self.pattern_part()?;
+                while self.eat_word("ON") {
+                    if !(self.eat_word("MATCH") || self.eat_word("CREATE")) {
+                        return Err(self.expected("`MATCH` or `CREATE`"));
+                    }
+                    self.expect_word("SET")?;
+                    self.set_items()?;
+                }
+            }
+            "SET" => {
+                self.pos += 1;
+                self.set_items()?;
+            }
+            "REMOVE" => {
+                self.pos += 1;
+                self.remove_items()?;
+            }
+            "DETACH" | "NODETACH" => {
+                self.pos += 1;
+                self.expect_word("DELETE")?;
+                self.expr_list()?;
+            }
+            "DELETE" => {
+                self.pos += 1;
+                self.expr_list()?;
+            }
+            "CALL" => self.call_clause()?,
+            "FOREACH" => self.foreach()?,
+            "LOAD" => {
+                self.pos += 1;
+                self.expect_word("CSV")?;
+                if self.eat_word("WITH") {
+                    self.expect_word("HEADERS")?;
+                }
+                self.expect_word("FROM")?;
+                self.expr()?;
+                self.expect_word("AS")?;
+                self.variable()?;
+                if self.eat_word("FIELDTERMINATOR") {
+                    if !self.at_kind(Kind::Str) {
+                        return Err(self.expected("a string"));
+                    }
+                    self.pos += 1;
+                }
+            }
+            "USE" => {
+                self.pos += 1;
+                self.expr()?;
+            }
+            "FINISH" => self.pos += 1,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn match_clause(&mut self) -> PResult {
+        self.expect_word("MATCH")?;
+        if self.eat_word("REPEATABLE") || self.eat_word("DIFFERENT") {
+            self.name()?;
+        }
+        self.pattern_list()?;
+        while self.eat_word("USING") {
+            self.hint()?;
+        }
+        if self.eat_word("WHERE") {
+            self.expr()?;
+        }
+        Ok(())
+    }
+
+    /// `USING INDEX n:Label(prop)`, `USING SCAN n:Label`, `USING JOIN ON n`.
+    fn hint(&mut self) -> PResult {
+        if self.eat_word("JOIN") {
+            self.expect_word("ON")?;
+            return self.comma_separated(Self::variable);
+        }
+        while ["INDEX", "SCAN", "SEEK", "RANGE", "TEXT", "POINT"].iter().any(|kw| self.at_word(kw)) {
+            self.pos += 1;
+        }
+        self.variable()?;
+        self.expect_op(":")?;
+        self.glue();
+        self.name()?;
+        if self.eat_op("(") {
+            self.glue_left();
+            self.comma_separated(Self::name)?;
+            self.expect_op(")")?;
+        }
+        Ok(())
+    }
+
+    fn call_clause(&mut self) -> PResult {
+        self.expect_word("CALL")?;
+        if self.at_op("(") || self.at_op("{") {
+            if self.eat_op("(") {
+                if !self.eat_op("*") && !self.at_op(")") {
+                    self.comma_separated(Self::variable)?;
+                }
+                self.expect_op(")")?;
+            }
+            self.expect_op("{")?;
+            self.pad();
+            self.query()?;
+            self.expect_op("}")?;
+            self.pad();
+            if self.at_word("IN") {
+                self.in_transactions()?;
+            }
+            return Ok(());
+        }
+
+        self.name()?;
+        while self.eat_op(".") {
+            self.name()?;
+        }
+        if self.eat_op("(") {
+            self.glue_left();
+            if !self.at_op(")") {
+                self.expr_list()?;
+            }
+            self.expect_op(")")?;
+        }
+        if self.eat_word("YIELD") && !self.eat_op("*") {
+            self.comma_separated(|p| {
+                p.name()?;
+                if p.eat_word("AS") {
+                    p.variable()?;
+                }
+                Ok(())
+            })?;
+            if self.eat_word("WHERE") {
+                self.expr()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// `IN [n] [CONCURRENT] TRANSACTIONS [OF n ROWS] [ON ERROR ...] [REPORT STATUS AS s]`
+    fn in_transactions(&mut self) -> PResult {
+        self.expect_word("IN")?;
+        if !self.at_word("CONCURRENT") && !self.at_word("TRANSACTIONS") {
+            self.additive()?;
+        }
+        self.eat_word("CONCURRENT");
+        self.expect_word("TRANSACTIONS")?;
+        if self.eat_word("OF") {
+            self.additive()?;
+            if !(self.eat_word("ROWS") || self.eat_word("ROW")) {
+                return Err(self.expected("`ROWS`"));
+            }
+        }
+        if self.eat_word("ON") {
+            self.expect_word("ERROR")?;
+            self.name()?;
+            while self.at_kind(Kind::Word) && !self.at_clause_start() && !self.at_word("REPORT") {
+                self.pos += 1;
+            }
+        }
+        if self.eat_word("REPORT") {
+            self.expect_word("STATUS")?;
+            self.expect_word("AS")?;
+            self.variable()?;
+        }
+        Ok(())
+    }
+
+    fn at_clause_start(&self) -> bool {
+        CLAUSES.iter().any(|kw| self.at_word(kw))
+    }
+
+    fn foreach(&mut self) -> PResult {
+        self.expect_word("FOREACH")?;
+        self.expect_op("(")?;
+        self.variable()?;
+        self.expect_word("IN")?;
+        self.expr()?;
+        self.expect_op("|")?;
+        let mut clauses = 0;
+        while self.clause()? {
+            clauses += 1;
+        }
+        if clauses == 0 {
+            return Err(self.expected("an updating clause"));
+        }
+        self.expect_op(")")
+    }
+
+    fn projection(&mut self) -> PResult {
+        self.eat_word("DISTINCT");
+        if self.eat_op("*") {
+            if self.eat_op(",") {
+                self.projection_items()?;
+            }
+        } else {
+            self.projection_items()?;
+        }
+        if self.eat_word("ORDER") {
+            self.expect_word("BY")?;
+            self.comma_separated(|p| {
+                p.expr()?;
+                let _ = p.eat_word("ASC") || p.eat_word("ASCENDING")
+                    || p.eat_word("DESC") || p.eat_word("DESCENDING");
+                Ok(())
+            })?;
+        }
+        if self.eat_word("SKIP") || self.eat_word("OFFSET") {
+            self.expr()?;
+        }
+        if self.eat_word("LIMIT") {
+            self.expr()?;
+        }
+        Ok(())
+    }
+
+    fn projection_items(&mut self) -> PResult {
+        self.comma_separated(|p| {
+            p.expr()?;
+            if p.eat_word("AS") {
+                p.name()?;
+            }
+            Ok(())
+        })
+    }
+
+    fn set_items(&mut self) -> PResult {
+        self.comma_separated(|p| {
+            if p.at_op("(") {
+                p.expect_op("(")?;
+                p.expr()?;
+                p.expect_op(")")?;
+            } else {
+                p.variable()?;
+            }
+            if p.eat_op(":") || p.eat_word("IS") {
+                return p.label_expr();
+            }
+            let mut chained = false;
+            loop {
+                if p.eat_op(".") {
+                    p.name()?;
+                } else if p.eat_op("[") {
+                    p.expr()?;
+                    p.expect_op("]")?;
+                } else {
+                    break;
+                }
+                chained = true;
+            }
+            if !chained && p.eat_op("+=") {
+                return p.expr();
+            }
+            p.expect_op("=")?;
+            p.expr()
+        })
+    }
+
+    fn remove_items(&mut self) -> PResult {
+        self.comma_separated(|p| {
+            p.variable()?;
+            if p.eat_op(":") || p.eat_word("IS") {
+                return p.label_expr();
+            }
+            let mut chained = false;
+            loop {
+                if p.eat_op(".") {
+                    p.name()?;
+                } else if p.eat_op("[") {
+                    p.expr()?;
+                    p.expect_op("]")?;
+                } else {
+                    break;
+                }
+                chained = true;
+            }
+            if chained {
+                Ok(())
+            } else {
+                Err(p.expected("`.property` or `:Label`"))
+            }
+        })
+    }
+
+    fn comma_separated(&mut self, mut item: impl FnMut(&mut Self) -> PResult) -> PResult {
+        item(self)?;
+        while self.eat_op(",") {
+            item(self)?;
+        }
+        Ok(())
+    }
+
+    fn expr_list(&mut self) -> PResult {
+        self.comma_separated(Self::expr)
+    }
+
+    // -----------------------------------------------------------------------
+    // Patterns
+    // -----------------------------------------------------------------------
+
+    fn pattern_list(&mut self) -> PResult {
+        self.comma_separated(Self::pattern_part)
+    }
+
+    fn pattern_part(&mut self) -> PResult {
+        if self.at_variable() && self.peek_at(1).is_some_and(|t| t.is_op("=")) {
+            self.pos += 2;
+        }
+        // Path selectors: ANY SHORTEST, ALL SHORTEST, SHORTEST 3 GROUPS, ...
+        while ["ANY", "ALL", "SHORTEST", "PATH", "PATHS", "GROUP", "GROUPS"].iter().any(|kw| self.at_word(kw))
+            || (self.at_kind(Kind::Number) && self.pos > 0 && self.toks[self.pos - 1].kind == Kind::Word)
+        {
+            self.pos += 1;
+        }
+        if (self.at_word("shortestPath") || self.at_word("allShortestPaths"))
+            && self.peek_at(1).is_some_and(|t| t.is_op("("))
+        {
+            self.pos += 2;
+            self.glue_left();
+            self.pattern_element()?;
+            return self.expect_op(")");
+        }
+        self.pattern_element().map(drop)
+    }
+
+    /// Returns the number of relationships in the element.
+    fn pattern_element(&mut self) -> PResult<usize> {
+        let mut grouped = self.path_factor()?;
+        let mut rels = 0;
+        loop {
+            if self.at_op("<") || self.at_op("-") {
+                self.relationship()?;
+                rels += 1;
+            } else if !(self.at_op("(") && (grouped || self.at_path_group())) {
+                return Ok(rels);
+            }
+            // Quantified path groups may be juxtaposed with their neighbours.
+            grouped = self.path_factor()?;
+        }
+    }
+
+    fn at_path_group(&self) -> bool {
+        self.at_op("(") && self.peek_at(1).is_some_and(|t| t.is_op("("))
+    }
+
+    /// A node pattern or a parenthesized path; returns whether it was the latter.
+    fn path_factor(&mut self) -> PResult<bool> {
+        if self.at_path_group() {
+            self.pos += 1;
+            if self.at_variable() && self.peek_at(1).is_some_and(|t| t.is_op("=")) {
+                self.pos += 2;
+            }
+            self.pattern_element()?;
+            if self.eat_word("WHERE") {
+                self.expr()?;
+            }
+            self.expect_op(")")?;
+            self.quantifier()?;
+            return Ok(true);
+        }
+        self.node_pattern().map(|()| false)
+    }
+
+    fn node_pattern(&mut self) -> PResult {
+        self.expect_op("(")?;
+        if self.at_variable() {
+            self.pos += 1;
+        }
+        if self.eat_op(":") || self.eat_word("IS") {
+            self.label_expr()?;
+        }
+        self.properties()?;
+        if self.eat_word("WHERE") {
+            self.expr()?;
+        }
+        self.expect_op(")")
+    }
+
+    fn properties(&mut self) -> PResult {
+        if self.at_op("{") {
+            self.map_literal()
+        } else {
+            if self.at_kind(Kind::Param) {
+                self.pos += 1;
+            }
+            Ok(())
+        }
+    }
+
+    fn relationship(&mut self) -> PResult {
+        if self.eat_op("<") {
+            self.glue();
+        }
+        self.expect_op("-")?;
+        self.glue();
+        if self.eat_op("[") {
+            self.glue();
+            if self.at_variable() {
+                self.pos += 1;
+            }
+            if self.eat_op(":") || self.eat_word("IS") {
+                self.in_rel = true;
+                let types = self.label_expr();
+                self.in_rel = false;
+                types?;
+            }
+            if self.eat_op("*") {
+                self.glue();
+                if self.at_kind(Kind::Number) {
+                    self.pos += 1;
+                    self.glue_left();
+                }
+                if self.eat_op("..") && self.at_kind(Kind::Number) {
+                    self.pos += 1;
+                }
+            }
+            self.properties()?;
+            if self.eat_word("WHERE") {
+                self.expr()?;
+            }
+            self.expect_op("]")?;
+            self.glue();
+        }
+        self.expect_op("-")?;
+        self.glue();
+        if self.eat_op(">") {
+            self.glue();
+        }
+        self.quantifier()
+    }
+
+    /// Optional `+`, `*` or `{m,n}` after a relationship or parenthesized path.
+    fn quantifier(&mut self) -> PResult {
+        if self.eat_op("+") || self.eat_op("*") {
+            self.glue_left();
+            return Ok(());
+        }
+        if self.eat_op("{") {
+            self.glue_left();
+            if self.at_kind(Kind::Number) {
+                self.pos += 1;
+            }
+            if self.eat_op(",") {
+                self.glue();
+            }
+            if self.at_kind(Kind::Number) {
+                self.pos += 1;
+            }
+            self.expect_op("}")?;
+        }
+        Ok(())
+    }
+
+    /// A label or relationship-type expression after `:` or `IS`.
+    fn label_expr(&mut self) -> PResult {
+        if self.toks[self.pos - 1].is_op(":") {
+            self.glue();
+        }
+        self.label_term()?;
+        loop {
+            if self.at_op("|") || self.at_op("&") {
+                if !self.label_continues(1) {
+                    return Ok(());
+                }
+                self.pos += 1;
+                self.glue();
+                if self.eat_op(":") {
+                    self.glue();
+                }
+            } else if self.at_op(":") {
+                self.pos += 1;
+                self.glue();
+            } else {
+                return Ok(());
+            }
+            self.label_term()?;
+        }
+    }
+
+    /// Whether the token `n` ahead can continue a label expression. Guards
+    /// the `|` of `[x IN xs WHERE x:A | x.name]`.
+    fn label_continues(&self, n: usize) -> bool {
+        match self.peek_at(n) {
+            Some(t) if t.is_op(":") || t.is_op("!") || t.is_op("%") || t.is_op("(") => true,
+            Some(t) if t.kind == Kind::Splice => true,
+            Some(t) if t.kind == Kind::Word => !self.peek_at(n + 1).is_some_and(|t| {
+                t.is_op(".") || t.is_op("[") || t.is_op("(") || t.is_op("{")
+            }),
+            _ => false,
+        }
+    }
+
+    fn label_term(&mut self) -> PResult {
+        while self.eat_op("!") {
+            self.mark(None, Some(true));
+        }
+        if self.eat_op("%") {
+            return Ok(());
+        }
+        if self.eat_op("(") {
+            self.label_expr()?;
+            return self.expect_op(")");
+        }
+        if self.at_op("$") && self.peek_at(1).is_some_and(|t| t.is_op("(")) {
+            self.pos += 2;
+            self.expr()?;
+            return self.expect_op(")");
+        }
+        if self.at_kind(Kind::Word) {
+            self.pos += 1;
+            return Ok(());
+        }
+        if self.at_kind(Kind::Splice) {
+            if self.in_rel {
+                self.rel_types.push(self.pos);
+            }
+            self.pos += 1;
+            return Ok(());
+        }
+        Err(self.expected("a label or relationship type"))
+    }
+
+    // -----------------------------------------------------------------------
+    // Expressions
+    // -----------------------------------------------------------------------
+
+    fn expr(&mut self) -> PResult {
+        self.xor_expr()?;
+        while self.eat_word("OR") {
+            self.xor_expr()?;
+        }
+        Ok(())
+    }
+
+    fn xor_expr(&mut self) -> PResult {
+        self.and_expr()?;
+        while self.eat_word("XOR") {
+            self.and_expr()?;
+        }
+        Ok(())
+    }
+
+    fn and_expr(&mut self) -> PResult {
+        self.not_expr()?;
+        while self.eat_word("AND") {
+            self.not_expr()?;
+        }
+        Ok(())
+    }
+
+    fn not_expr(&mut self) -> PResult {
+        while self.eat_word("NOT") {}
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> PResult {
+        self.additive()?;
+        loop {
+            if let Some(t) = self.peek() {
+                if t.is_op("!=") || t.is_op("==") {
+                    let fix = if t.text == "!=" { "<>" } else { "=" };
+                    return Err(SyntaxError {
+                        pos: self.pos,
+                        msg: format!("`{}` is not a Cypher operator, use `{fix}`", t.text),
+                    });
+                }
+            }
+            if ["=", "<>", "<", ">", "<=", ">=", "=~"].iter().any(|op| self.at_op(op)) {
+                self.pos += 1;
+                self.additive()?;
+            } else if self.eat_word("STARTS") || self.eat_word("ENDS") {
+                self.expect_word("WITH")?;
+                self.additive()?;
+            } else if self.eat_word("CONTAINS") || self.eat_word("IN") {
+                self.additive()?;
+            } else if self.eat_word("IS") {
+                self.eat_word("NOT");
+                self.is_predicate()?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    /// The part after `IS [NOT]`.
+    fn is_predicate(&mut self) -> PResult {
+        if self.eat_word("NULL") {
+            return Ok(());
+        }
+        if self.eat_op("::") || self.eat_word("TYPED") {
+            return self.type_name();
+        }
+        let _ = self.eat_word("NFC") || self.eat_word("NFD")
+            || self.eat_word("NFKC") || self.eat_word("NFKD");
+        if self.eat_word("NORMALIZED") {
+            return Ok(());
+        }
+        self.eat_op(":");
+        self.label_expr()
+    }
+
+    /// A type such as `INTEGER`, `LIST<STRING NOT NULL>` or `INTEGER | FLOAT`.
+    fn type_name(&mut self) -> PResult {
+        loop {
+            let start = self.pos;
+            while self.peek().is_some_and(|t| {
+                t.kind == Kind::Word && (!is_reserved(t) && !is_clause_keyword(t) || t.is_word("NULL"))
+            }) {
+                self.pos += 1;
+            }
+            if self.pos == start {
+                return Err(self.expected("a type"));
+            }
+            if self.eat_op("<") {
+                self.glue();
+                self.type_name()?;
+                self.expect_op(">")?;
+                self.glue_left();
+            }
+            if self.at_word("NOT") && self.peek_at(1).is_some_and(|t| t.is_word("NULL")) {
+                self.pos += 2;
+            }
+            if !self.eat_op("|") {
+                return Ok(());
+            }
+        }
+    }
+
+    fn additive(&mut self) -> PResult {
+        self.multiplicative()?;
+        while self.eat_op("+") || self.eat_op("-") || self.eat_op("||") {
+            self.multiplicative()?;
+        }
+        Ok(())
+    }
+
+    fn multiplicative(&mut self) -> PResult {
+        self.power()?;
+        while self.eat_op("*") || self.eat_op("/") || self.eat_op("%") {
+            self.power()?;
+        }
+        Ok(())
+    }
+
+    fn power(&mut self) -> PResult {
+        self.unary()?;
+        while self.eat_op("^") {
+            self.unary()?;
+        }
+        Ok(())
+    }
+
+    fn unary(&mut self) -> PResult {
+        while self.eat_op("-") || self.eat_op("+") {
+            self.mark(None, Some(true));
+        }
+        self.postfix()
+    }
+
+    fn postfix(&mut self) -> PResult {
+        self.atom()?;
+        loop {
+            if self.eat_op(".") {
+                self.name()?;
+            } else if self.eat_op("[") {
+                self.glue_left();
+                self.index_or_slice()?;
+            } else if self.at_op(":") && self.label_continues(1) {
+                self.pos += 1;
+                self.label_expr()?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn index_or_slice(&mut self) -> PResult {
+        if self.eat_op("..") {
+            if !self.at_op("]") {
+                self.expr()?;
+            }
+        } else {
+            self.expr()?;
+            if self.eat_op("..") && !self.at_op("]") {
+                self.expr()?;
+            }
+        }
+        self.expect_op("]")
+    }
+
+    fn atom(&mut self) -> PResult {
+        let Some(t) = self.peek() else {
+            return Err(self.expected("an expression"));
+        };
+        match t.kind {
+            Kind::Number | Kind::Str | Kind::Param => {
+                self.pos += 1;
+                Ok(())
+            }
+            Kind::Open if t.text == "[" => self.list_like(),
+            Kind::Open if t.text == "{" => self.map_literal(),
+            Kind::Open if t.text == "(" => self.either(Self::pattern_predicate, Self::parenthesized),
+            Kind::Word => self.word_atom(),
+            _ => Err(self.expected("an expression")),
+        }
+    }
+
+    fn word_atom(&mut self) -> PResult {
+        let t = &self.toks[self.pos];
+        let next_is = |p: &Self, n: usize, op: &str| p.peek_at(n).is_some_and(|t| t.is_op(op));
+
+        if t.is_word("TRUE") || t.is_word("FALSE") || t.is_word("NULL") {
+            self.pos += 1;
+            return Ok(());
+        }
+        if t.is_word("CASE") {
+            return self.case_expr();
+        }
+        if (t.is_word("EXISTS") || t.is_word("COUNT") || t.is_word("COLLECT")) && next_is(self, 1, "{") {
+            let collect = t.is_word("COLLECT");
+            self.pos += 2;
+            self.pad();
+            if collect {
+                self.query()?;
+            } else {
+                self.either(Self::query, |p| {
+                    p.pattern_list()?;
+                    if p.eat_word("WHERE") {
+                        p.expr()?;
+                    }
+                    Ok(())
+                })?;
+            }
+            self.expect_op("}")?;
+            self.pad();
+            return Ok(());
+        }
+
+        // Function call, possibly namespaced: `apoc.coll.sum(...)`.
+        let mut n = 1;
+        while next_is(self, n, ".") && self.peek_at(n + 1).is_some_and(|t| t.kind == Kind::Word) {
+            n += 2;
+        }
+        if next_is(self, n, "(") {
+            return self.function_call(n);
+        }
+
+        if self.keyword_at(0) {
+            return Err(self.expected("an expression"));
+        }
+        self.pos += 1;
+        if self.at_op("{") {
+            self.map_projection()?;
+        }
+        Ok(())
+    }
+
+    /// A function call whose name spans the next `name_len` tokens.
+    fn function_call(&mut self, name_len: usize) -> PResult {
+        let name = self.toks[self.pos].text.to_ascii_lowercase();
+        let simple = name_len == 1;
+        self.pos += name_len;
+        self.expect_op("(")?;
+        self.glue_left();
+
+        if simple && ["all", "any", "none", "single", "filter", "extract"].contains(&name.as_str())
+            && self.at_variable() && self.peek_at(1).is_some_and(|t| t.is_word("IN"))
+        {
+            self.pos += 2;
+            self.expr()?;
+            if self.eat_word("WHERE") {
+                self.expr()?;
+            }
+            if self.eat_op("|") {
+                self.expr()?;
+            }
+            return self.expect_op(")");
+        }
+        if simple && name == "reduce" {
+            self.variable()?;
+            self.expect_op("=")?;
+            self.expr()?;
+            self.expect_op(",")?;
+            self.variable()?;
+            self.expect_word("IN")?;
+            self.expr()?;
+            self.expect_op("|")?;
+            self.expr()?;
+            return self.expect_op(")");
+        }
+        if simple && (name == "shortestpath" || name == "allshortestpaths") {
+            self.pattern_element()?;
+            return self.expect_op(")");
+        }
+        if simple && name == "count" && self.eat_op("*") {
+            return self.expect_op(")");
+        }
+        if !self.at_op(")") {
+            self.eat_word("DISTINCT");
+            self.expr_list()?;
+        }
+        self.expect_op(")")
+    }
+
+    /// `(a)-[:R]->(b)` used as a boolean or list expression.
+    fn pattern_predicate(&mut self) -> PResult {
+        if self.pattern_element()? == 0 {
+            return Err(self.expected("a relationship pattern"));
+        }
+        Ok(())
+    }
+
+    fn parenthesized(&mut self) -> PResult {
+        self.expect_op("(")?;
+        self.expr()?;
+        self.expect_op(")")
+    }
+
+    fn list_like(&mut self) -> PResult {
+        self.expect_op("[")?;
+        if self.eat_op("]") {
+            return Ok(());
+        }
+        if self.at_variable() && self.peek_at(1).is_some_and(|t| t.is_word("IN")) {
+            self.pos += 2;
+            self.expr()?;
+            if self.eat_word("WHERE") {
+                self.expr()?;
+            }
+            if self.eat_op("|") {
+                self.expr()?;
+            }
+            return self.expect_op("]");
+        }
+        self.either(
+            |p| {
+                if p.at_variable() && p.peek_at(1).is_some_and(|t| t.is_op("=")) {
+                    p.pos += 2;
+                }
+                if !p.at_op("(") || p.pattern_element()? == 0 {
+                    return Err(p.expected("a pattern"));
+                }
+                if p.eat_word("WHERE") {
+                    p.expr()?;
+                }
+                p.expect_op("|")?;
+                p.expr()?;
+                p.expect_op("]")
+            },
+            |p| {
+                p.expr_list()?;
+                p.expect_op("]")
+            },
+        )
+    }
+
+    fn map_literal(&mut self) -> PResult {
+        self.expect_op("{")?;
+        if !self.at_op("}") {
+            self.comma_separated(|p| {
+                p.name()?;
+                p.expect_op(":")?;
+                p.expr()
+            })?;
+        }
+        self.expect_op("}")
+    }
+
+    /// `n {.name, .*, alias: expr, other}`
+    fn map_projection(&mut self) -> PResult {
+        self.expect_op("{")?;
+        if !self.at_op("}") {
+            self.comma_separated(|p| {
+                if p.eat_op(".") {
+                    p.mark(Some(false), None);
+                    if !p.eat_op("*") {
+                        p.name()?;
+                    }
+                } else if p.peek_at(1).is_some_and(|t| t.is_op(":")) {
+                    p.name()?;
+                    p.pos += 1;
+                    p.expr()?;
+                } else {
+                    p.variable()?;
+                }
+                Ok(())
+            })?;
+        }
+        self.expect_op("}")
+    }
+
+    fn case_expr(&mut self) -> PResult {
+        self.expect_word("CASE")?;
+        let simple = !self.at_word("WHEN");
+        if simple {
+            self.expr()?;
+        }
+        if !self.at_word("WHEN") {
+            return Err(self.expected("`WHEN`"));
+        }
+        while self.eat_word("WHEN") {
+            if simple {
+                self.comma_separated(|p| {
+                    if ["=", "<>", "<", ">", "<=", ">=", "=~"].iter().any(|op| p.at_op(op)) {
+                        p.pos += 1;
+                    }
+                    p.expr()
+                })?;
+            } else {
+                self.expr()?;
+            }
+            self.expect_word("THEN")?;
+            self.expr()?;
+        }
+        if self.eat_word("ELSE") {
+            self.expr()?;
+        }
+        self.expect_word("END")
+    }
+}
+
+fn is_reserved(t: &Tok) -> bool {
+    RESERVED.iter().any(|kw| t.is_word(kw))
+}
+
+fn is_clause_keyword(t: &Tok) -> bool {
+    CLAUSES.iter().any(|kw| t.is_word(kw))
+}
+
+/// A close clause keyword for a misspelled word, if any.
+fn suggest(word: &str) -> Option<&'static str> {
+    let upper = word.to_ascii_uppercase();
+    if upper.len() < 3 {
+        return None;
+    }
+    CLAUSES.iter()
+        .map(|kw| (kw, edit_distance(&upper, kw)))
+        .filter(|(_, d)| *d <= 2)
+        .min_by_key(|(_, d)| *d)
+        .map(|(kw, _)| *kw)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
@@ -0,0 +1,76 @@
+//! Token-aware printer for the Cypher macros.
+//!
+//! Rebuilds query text from the tokens produced by [`crate::lex`], ignoring
+//! how rustc happened to space the input. Literals keep their value (strings
+//! are re-escaped for Cypher by the lexer); everything else is joined with a
+//! single space unless one side asks to be glued to its neighbour. The parser supplies those hints for constructs it
+//! understands (relationship arrows, labels, calls, map keys); the rest fall
+//! back to per-token defaults.
+
+use crate::lex::{Kind, Tok};
+
+/// Whether a token sticks to its left or right neighbour.
+///
+/// `None` falls back to the token's default; `Some(false)` forces a space on
+/// that side unless the neighbour insists on gluing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Glue {
+    pub before: Option<bool>,
+    pub after: Option<bool>,
+}
+
+/// Render `toks` as a single line of Cypher.
+///
+/// The text is split at every [`Kind::Splice`], so the result has one more
+/// part than there are splices; the caller fills in the gaps.
+pub fn print(toks: &[Tok], glue: &[Glue]) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut out = String::new();
+    let mut brackets: Vec<&str> = Vec::new();
+    let mut prev_after = true;
+
+    for (i, tok) in toks.iter().enumerate() {
+        let hint = glue.get(i).copied().unwrap_or_default();
+        let (before, after) = defaults(tok, brackets.last().copied());
+        let before = hint.before.unwrap_or(before);
+        if i > 0 && !prev_after && !before {
+            out.push(' ');
+        }
+        match tok.kind {
+            Kind::Param => {
+                out.push('$');
+                out.push_str(&tok.text);
+            }
+            Kind::Splice => parts.push(std::mem::take(&mut out)),
+            _ => out.push_str(&tok.text),
+        }
+        match tok.kind {
+            Kind::Open => brackets.push(&tok.text),
+            Kind::Close => {
+                brackets.pop();
+            }
+            _ => {}
+        }
+        prev_after = hint.after.unwrap_or(after);
+    }
+    parts.push(out);
+    parts
+}
+
+/// Default `(before, after)` gluing for a token, given the innermost open bracket.
+fn defaults(tok: &Tok, bracket: Option<&str>) -> (bool, bool) {
+    match tok.kind {
+        Kind::Open => (false, true),
+        Kind::Close => (true, false),
+        Kind::Op => match tok.text.as_str() {
+            "," | ";" => (true, false),
+            "." | ".." => (true, true),
+            "$" => (false, true),
+            // `{key: value}` inside maps, `n:Label` everywhere else.
+            ":" if bracket == Some("{") => (true, false),
+            ":" => (true, true),
+            _ => (false, false),
+        },
+        _ => (false, false),
+    }
+}
@@ -0,0 +1,193 @@
+//! Property-key constants shared by `#[derive(CyphrNode)]` and
+//! `#[derive(CyphrRelation)]`.
+//!
+//! Each derive emits a companion module named after the type in snake case
+//! with `_props` appended (`User` → `user_props`), holding one
+//! `SCREAMING_CASE` const per field whose value is the Neo4j property key.
+//!
+//! The `unique`, `index`, `required` and `fulltext = "..."` field attributes
+//! are collected here too and turned into a `schema()` override, along with
+//! each property's Neo4j type inferred from the field type.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::meta::ParseNestedMeta;
+use syn::{GenericArgument, Ident, LitStr, PathArguments, Type, Visibility};
+
+use crate::attr;
+
+/// A field and the property key it maps to.
+pub struct Prop {
+    pub field: Ident,
+    pub ty: Type,
+    pub key: String,
+    pub schema: SchemaAttrs,
+}
+
+/// Schema attributes declared on one field.
+#[derive(Default)]
+pub struct SchemaAttrs {
+    pub unique: bool,
+    pub index: bool,
+    pub required: bool,
+    pub fulltext: Vec<LitStr>,
+}
+
+/// Record `meta` if it is a schema attribute; returns whether it was one.
+pub fn schema_attr(meta: &ParseNestedMeta, attrs: &mut SchemaAttrs) -> syn::Result<bool> {
+    if meta.path.is_ident("unique") {
+        attrs.unique = true;
+    } else if meta.path.is_ident("index") {
+        attrs.index = true;
+    } else if meta.path.is_ident("required") {
+        attrs.required = true;
+    } else if meta.path.is_ident("fulltext") {
+        attrs.fulltext.push(attr::string(meta)?);
+    } else {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// The `schema()` override.
+///
+/// `def` is the `SchemaDef` constructor expression for the label or type.
+pub fn schema_fn(def: TokenStream2, props: &[Prop]) -> syn::Result<TokenStream2> {
+    let mut calls = Vec::new();
+    for p in props {
+        let key = &p.key;
+        let (neo4j_type, optional) = neo4j_type(&p.ty);
+        let neo4j_type = match neo4j_type {
+            Some(t) => quote! { Some(#t) },
+            None => quote! { None },
+        };
+        calls.push(quote! { .property(#key, #neo4j_type, #optional) });
+    }
+    for p in props {
+        let key = &p.key;
+        if p.schema.unique && p.schema.index {
+            return Err(syn::Error::new_spanned(
+                &p.field,
+                "`unique` already creates an index; remove `index`",
+            ));
+        }
+        if p.schema.unique {
+            calls.push(quote! { .unique(#key) });
+        }
+        if p.schema.required {
+            calls.push(quote! { .required(#key) });
+        }
+        if p.schema.index {
+            calls.push(quote! { .index(#key) });
+        }
+        for name in &p.schema.fulltext {
+            calls.push(quote! { .fulltext(#name, #key) });
+        }
+    }
+    Ok(quote! {
+        fn schema() -> cyphr_core::schema::SchemaDef {
+            #def #(#calls)*
+        }
+    })
+}
+
+/// The type `db.schema.nodeTypeProperties()` reports for values of `ty`, and
+/// whether the property is optional. Inferred from the type's name only;
+/// `None` for types it doesn't recognise.
+fn neo4j_type(ty: &Type) -> (Option<String>, bool) {
+    match last_segment(ty) {
+        Some((name, Some(inner))) if name == "Option" => (neo4j_type(inner).0, true),
+        Some((name, Some(inner))) if name == "Vec" => {
+            let inner = match last_segment(inner) {
+                Some((n, _)) if n == "u8" => return (Some("ByteArray".into()), false),
+                _ => neo4j_type(inner).0,
+            };
+            (inner.filter(|t| !t.ends_with("Array")).map(|t| format!("{t}Array")), false)
+        }
+        Some((name, _)) => {
+            let t = match name.as_str() {
+                "i64" | "i32" | "i16" | "i8" | "u64" | "u32" | "u16" | "u8" | "isize" | "usize" => "Long",
+                "f64" | "f32" => "Double",
+                "String" | "str" => "String",
+                "bool" => "Boolean",
+                "NaiveDate" => "Date",
+                "NaiveTime" => "LocalTime",
+                "NaiveDateTime" => "LocalDateTime",
+                "DateTime" => "DateTime",
+                "Duration" => "Duration",
+                "Point2D" | "Point3D" => "Point",
+                "CyphrBytes" => "ByteArray",
+                _ => return (None, false),
+            };
+            (Some(t.to_owned()), false)
+        }
+        None => (None, false),
+    }
+}
+
+/// The last path segment's name and its first type argument.
+fn last_segment(ty: &Type) -> Option<(String, Option<&Type>)> {
+    let ty = match ty {
+        Type::Reference(r) => &*r.elem,
+        ty => ty,
+    };
+    let Type::Path(path) = ty else { return None };
+    let segment = path.path.segments.last()?;
+    let arg = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().find_map(|a| match a {
+            GenericArgument::Type(t) => Some(t),
+            _ => None,
+        }),
+        _ => None,
+    };
+    Some((segment.ident.to_string(), arg))
+}
+
+/// The companion module for `name`.
+pub fn module(vis: &Visibility, name: &Ident, props: &[Prop]) -> TokenStream2 {
+    let module = module_ident(name);
+    let doc = format!("Property keys of [`{name}`], as stored in Neo4j.");
+    let consts = props.iter().map(|p| {
+        let field = p.field.unraw().to_string();
+        let ident = format_ident!("{}", field.to_uppercase(), span = p.field.span());
+        let key = &p.key;
+        let doc = format!("Key of `{name}::{field}`.");
+        quote! {
+            #[doc = #doc]
+            pub const #ident: &str = #key;
+        }
+    });
+    quote! {
+        #[doc = #doc]
+        #[allow(dead_code)]
+        #vis mod #module {
+            #(#consts)*
+        }
+    }
+}
+
+/// The `PROPS` slice, in field order.
+pub fn slice(props: &[Prop]) -> TokenStream2 {
+    let keys = props.iter().map(|p| &p.key);
+    quote! { &[#(#keys),*] }
+}
+
+/// `User` → `user_props`, `BlogPost` → `blog_post_props`, `HTTPLog` → `http_log_props`.
+pub fn module_ident(name: &Ident) -> Ident {
+    let chars: Vec<char> = name.unraw().to_string().chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_ascii_digit());
+            let before_lower = i > 0 && chars.get(i + 1).is_some_and(|n| n.is_lowercase()) && chars[i - 1].is_uppercase();
+            if after_lower || before_lower {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    format_ident!("{}_props", out, span = name.span())
+}
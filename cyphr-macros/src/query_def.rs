@@ -0,0 +1,202 @@
+//! Implementation of `#[derive(CyphrQueryDef)]`.
+//!
+//! Parses the `query` attribute with the same checker as `cypher!`, matches
+//! its `$placeholders` against the struct's fields, and generates
+//! `ToCyphrParams`, `CyphrQueryDef` and inherent `fetch_*` methods.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, DeriveInput, Data, Fields, Expr, ExprLit, Lit, LitStr};
+
+use crate::{attr, lex, parse};
+
+struct QueryAttrs {
+    query: Option<LitStr>,
+    returns: Option<syn::Type>,
+}
+
+fn get_attrs(ast: &DeriveInput) -> syn::Result<QueryAttrs> {
+    let mut attrs = QueryAttrs { query: None, returns: None };
+    for attr in &ast.attrs {
+        if attr.path().is_ident("cyphr") {
+            attr.parse_nested_meta(|meta| {
+                let value = meta.value()?;
+                let expr: Expr = value.parse()?;
+                let Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) = expr else {
+                    return Err(meta.error("expected a string literal"));
+                };
+                if meta.path.is_ident("query") {
+                    attrs.query = Some(s);
+                } else if meta.path.is_ident("returns") {
+                    attrs.returns = Some(s.parse()?);
+                } else {
+                    return Err(meta.error("expected `query` or `returns`"));
+                }
+                Ok(())
+            })?;
+        }
+    }
+    Ok(attrs)
+}
+
+struct FieldInfo {
+    ident: syn::Ident,
+    param: String,
+    skip: bool,
+}
+
+fn parse_field(f: &syn::Field) -> syn::Result<FieldInfo> {
+    let ident = f.ident.as_ref().unwrap().clone();
+    let mut param = ident.unraw().to_string();
+    let mut skip = false;
+
+    for a in &f.attrs {
+        if a.path().is_ident("cyphr") {
+            a.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                } else if meta.path.is_ident("prop") {
+                    param = attr::string(&meta)?.value();
+                } else {
+                    attr::skip(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+    }
+
+    Ok(FieldInfo { ident, param, skip })
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let name = &ast.ident;
+
+    let attrs = match get_attrs(&ast) {
+        Ok(attrs) => attrs,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let Some(query) = attrs.query else {
+        return syn::Error::new_spanned(&ast.ident, "CyphrQueryDef requires #[cyphr(query = \"...\")]")
+            .to_compile_error()
+            .into();
+    };
+    let row = match attrs.returns {
+        Some(ty) => quote! { #ty },
+        None => quote! { cyphr_core::CyphrRow },
+    };
+
+    let fields = match &ast.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(named) => match named.named.iter().map(parse_field).collect::<syn::Result<Vec<_>>>() {
+                Ok(fields) => fields,
+                Err(err) => return err.to_compile_error().into(),
+            },
+            Fields::Unit => Vec::new(),
+            _ => {
+                return syn::Error::new_spanned(&ast, "CyphrQueryDef only supports structs with named fields")
+                    .to_compile_error()
+                    .into();
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(&ast, "CyphrQueryDef only supports structs")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    // Check the query and collect its placeholders.
+    let text = query.value();
+    let toks = match lex::lex_str(&text, query.span()).and_then(|toks| parse::parse(&toks).map(|_| toks)) {
+        Ok(toks) => toks,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let placeholders: Vec<&str> = toks.iter()
+        .filter(|t| t.kind == lex::Kind::Param)
+        .map(|t| t.text.as_str())
+        .collect();
+
+    let bound: Vec<&FieldInfo> = fields.iter().filter(|f| !f.skip).collect();
+    if let Some(missing) = placeholders.iter().find(|p| !bound.iter().any(|f| f.param == **p)) {
+        return syn::Error::new(query.span(), format!("`${missing}` has no matching field in `{name}`"))
+            .to_compile_error()
+            .into();
+    }
+    if let Some(unused) = bound.iter().find(|f| !placeholders.contains(&f.param.as_str())) {
+        return syn::Error::new_spanned(
+            &unused.ident,
+            format!("`${}` does not appear in the query; mark the field #[cyphr(skip)] to exclude it", unused.param),
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let inserts = bound.iter().map(|f| {
+        let ident = &f.ident;
+        let key = &f.param;
+        quote! {
+            map.insert(#key.to_string(), cyphr_core::traits::IntoCyphrValue::into_value(self.#ident));
+        }
+    });
+
+    let expanded = quote! {
+        impl cyphr_core::traits::ToCyphrParams for #name {
+            fn to_params(self) -> std::collections::HashMap<String, neo4rs::BoltType> {
+                let mut map = std::collections::HashMap::new();
+                #(#inserts)*
+                map
+            }
+        }
+
+        impl cyphr::query::CyphrQueryDef for #name {
+            type Row = #row;
+            const QUERY: &'static str = #text;
+        }
+
+        impl #name {
+            /// Build the query with every field bound as a parameter.
+            pub fn query(self) -> cyphr::query::CyphrQuery {
+                <Self as cyphr::query::CyphrQueryDef>::query(self)
+            }
+
+            /// Execute and map the first row; see [`CyphrQuery::fetch_one`](cyphr::query::CyphrQuery::fetch_one).
+            pub async fn fetch_one(self, graph: &neo4rs::Graph) -> Result<#row, cyphr_core::CyphrError> {
+                self.query().fetch_one(graph).await
+            }
+
+            /// Execute and map the first row, if any.
+            pub async fn fetch_optional(self, graph: &neo4rs::Graph) -> Result<Option<#row>, cyphr_core::CyphrError> {
+                self.query().fetch_optional(graph).await
+            }
+
+            /// Execute and map every row.
+            pub async fn fetch_all(self, graph: &neo4rs::Graph) -> Result<Vec<#row>, cyphr_core::CyphrError> {
+                self.query().fetch_all(graph).await
+            }
+
+            /// Execute and stream the rows.
+            pub async fn fetch_stream(self, graph: &neo4rs::Graph) -> Result<cyphr::stream::CyphrStream<#row>, cyphr_core::CyphrError> {
+                self.query().fetch_stream(graph).await
+            }
+
+            /// Like `fetch_one`, inside a transaction.
+            pub async fn fetch_one_in(self, txn: &mut neo4rs::Txn) -> Result<#row, cyphr_core::CyphrError> {
+                self.query().fetch_one_in(txn).await
+            }
+
+            /// Like `fetch_optional`, inside a transaction.
+            pub async fn fetch_optional_in(self, txn: &mut neo4rs::Txn) -> Result<Option<#row>, cyphr_core::CyphrError> {
+                self.query().fetch_optional_in(txn).await
+            }
+
+            /// Like `fetch_all`, inside a transaction.
+            pub async fn fetch_all_in(self, txn: &mut neo4rs::Txn) -> Result<Vec<#row>, cyphr_core::CyphrError> {
+                self.query().fetch_all_in(txn).await
+            }
+        }
+    };
+
+    expanded.into()
+}
@@ -1,49 +1,66 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Data, Fields, Lit, Expr, ExprLit};
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, DeriveInput, Data, Fields, Type};
+
+use crate::attr;
+use crate::props::{self, Prop, SchemaAttrs};
+
+/// Struct-level `#[cyphr(...)]` settings.
+struct RelMeta {
+    rel_type: String,
+    from: Option<String>,
+    to: Option<String>,
+    from_type: Option<Type>,
+    to_type: Option<Type>,
+}
 
-fn get_rel_meta(ast: &DeriveInput) -> (String, Option<String>, Option<String>) {
+fn get_rel_meta(ast: &DeriveInput) -> syn::Result<RelMeta> {
     let mut rel_type: Option<String> = None;
     let mut from: Option<String> = None;
     let mut to: Option<String> = None;
+    let mut from_type: Option<Type> = None;
+    let mut to_type: Option<Type> = None;
 
-    for attr in &ast.attrs {
-        if attr.path().is_ident("cyphr") {
-            let _ = attr.parse_nested_meta(|meta| {
+    for a in &ast.attrs {
+        if a.path().is_ident("cyphr") {
+            a.parse_nested_meta(|meta| {
                 if meta.path.is_ident("type") {
-                    let value = meta.value()?;
-                    let expr: Expr = value.parse()?;
-                    if let Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) = expr {
-                        rel_type = Some(s.value());
-                    }
+                    rel_type = Some(attr::string(&meta)?.value());
                 } else if meta.path.is_ident("from") {
-                    let value = meta.value()?;
-                    let expr: Expr = value.parse()?;
-                    if let Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) = expr {
-                        from = Some(s.value());
-                    }
+                    from = Some(attr::string(&meta)?.value());
                 } else if meta.path.is_ident("to") {
-                    let value = meta.value()?;
-                    let expr: Expr = value.parse()?;
-                    if let Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) = expr {
-                        to = Some(s.value());
-                    }
+                    to = Some(attr::string(&meta)?.value());
+                } else if meta.path.is_ident("from_type") {
+                    from_type = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("to_type") {
+                    to_type = Some(meta.value()?.parse()?);
+                } else {
+                    attr::skip(&meta)?;
                 }
                 Ok(())
-            });
+            })?;
         }
     }
 
-    let ty = rel_type.unwrap_or_else(|| ast.ident.to_string());
-    (ty, from, to)
+    Ok(RelMeta {
+        rel_type: rel_type.unwrap_or_else(|| ast.ident.unraw().to_string()),
+        from,
+        to,
+        from_type,
+        to_type,
+    })
 }
 
 pub fn expand(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     let name = &ast.ident;
 
-    let (rel_type, from_label, to_label) = get_rel_meta(&ast);
+    let RelMeta { rel_type, from, to, from_type, to_type } = match get_rel_meta(&ast) {
+        Ok(meta) => meta,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     let fields = match &ast.data {
         Data::Struct(s) => match &s.fields {
@@ -62,45 +79,65 @@ pub fn expand(input: TokenStream) -> TokenStream {
     };
 
     let mut inits = Vec::new();
+    let mut props = Vec::new();
 
     for f in fields {
         let ident = f.ident.as_ref().unwrap();
-        let key = ident.to_string();
+        let key = ident.unraw().to_string();
         let ty = &f.ty;
 
         let mut prop_key = key.clone();
-        for attr in &f.attrs {
-            if attr.path().is_ident("cyphr") {
-                let _ = attr.parse_nested_meta(|meta| {
-                    if meta.path.is_ident("prop") {
-                        let value = meta.value()?;
-                        let expr: Expr = value.parse()?;
-                        if let Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) = expr {
-                            prop_key = s.value();
-                        }
-                    }
-                    Ok(())
-                });
+        let mut schema = SchemaAttrs::default();
+        for a in &f.attrs {
+            if !a.path().is_ident("cyphr") {
+                continue;
+            }
+            let parsed = a.parse_nested_meta(|meta| {
+                if props::schema_attr(&meta, &mut schema)? {
+                    // collected for schema()
+                } else if meta.path.is_ident("prop") {
+                    prop_key = attr::string(&meta)?.value();
+                } else {
+                    attr::skip(&meta)?;
+                }
+                Ok(())
+            });
+            if let Err(err) = parsed {
+                return err.to_compile_error().into();
             }
         }
 
+        props.push(Prop { field: ident.clone(), ty: ty.clone(), key: prop_key.clone(), schema });
         inits.push(quote! {
             #ident: {
                 let v = cyphr_core::props::rel_prop(rel, #prop_key)
-                    .ok_or_else(|| cyphr_core::error::CyphrError::missing_property(#prop_key, <Self as cyphr_core::traits::CyphrRelation>::TYPE))?;
+                    .ok_or_else(|| cyphr_core::error::CyphrError::missing_property(#prop_key, #rel_type))?;
                 <#ty as cyphr_core::traits::FromCyphrValue>::from_value(v)
                     .map_err(|e| e.with_context(format!("{}::{} (prop '{}')", #rel_type, #key, #prop_key)))?
             }
         });
     }
 
-    let from_label_tokens = match from_label {
-        Some(s) => quote! { Some(#s) },
-        None => quote! { None },
+    let from_label_tokens = label_tokens(from, from_type.as_ref());
+    let to_label_tokens = label_tokens(to, to_type.as_ref());
+    // Without a declared endpoint type the relationship may touch any node type.
+    let rel_from = match &from_type {
+        Some(ty) => quote! { impl cyphr_core::traits::RelFrom<#ty> for #name {} },
+        None => quote! { impl<N: cyphr_core::traits::CyphrNode> cyphr_core::traits::RelFrom<N> for #name {} },
     };
-    let to_label_tokens = match to_label {
-        Some(s) => quote! { Some(#s) },
-        None => quote! { None },
+    let rel_to = match &to_type {
+        Some(ty) => quote! { impl cyphr_core::traits::RelTo<#ty> for #name {} },
+        None => quote! { impl<N: cyphr_core::traits::CyphrNode> cyphr_core::traits::RelTo<N> for #name {} },
+    };
+
+    let props_slice = props::slice(&props);
+    let props_module = props::module(&ast.vis, name, &props);
+    let schema_fn = match props::schema_fn(
+        quote! { cyphr_core::schema::SchemaDef::relationship(<Self as cyphr_core::traits::CyphrRelation>::TYPE) },
+        &props,
+    ) {
+        Ok(tokens) => tokens,
+        Err(err) => return err.to_compile_error().into(),
     };
 
     let expanded = quote! {
@@ -108,12 +145,15 @@ pub fn expand(input: TokenStream) -> TokenStream {
             const TYPE: &'static str = #rel_type;
             const FROM_LABEL: Option<&'static str> = #from_label_tokens;
             const TO_LABEL: Option<&'static str> = #to_label_tokens;
+            const PROPS: &'static [&'static str] = #props_slice;
 
             fn from_rel(rel: &neo4rs::BoltRelation) -> Result<Self, cyphr_core::error::CyphrError> {
                 Ok(Self {
                     #(#inits,)*
                 })
             }
+
+            #schema_fn
         }
 
         impl cyphr_core::traits::FromCyphrValue for #name {
@@ -126,7 +166,22 @@ pub fn expand(input: TokenStream) -> TokenStream {
                 }
             }
         }
+
+        #rel_from
+        #rel_to
+
+        #props_module
     };
 
     expanded.into()
 }
+
+/// `FROM_LABEL` / `TO_LABEL`: the declared label, else the label of the
+/// declared node type.
+fn label_tokens(label: Option<String>, ty: Option<&Type>) -> proc_macro2::TokenStream {
+    match (label, ty) {
+        (Some(label), _) => quote! { Some(#label) },
+        (None, Some(ty)) => quote! { Some(<#ty as cyphr_core::traits::CyphrNode>::LABEL) },
+        (None, None) => quote! { None },
+    }
+}
@@ -1,7 +1,10 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Data, Fields, Expr, ExprLit, Lit};
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, DeriveInput, Data, Fields};
+
+use crate::attr;
 
 struct FieldInfo {
     ident: syn::Ident,
@@ -9,29 +12,28 @@ struct FieldInfo {
     skip: bool,
 }
 
-fn parse_field(f: &syn::Field) -> FieldInfo {
+fn parse_field(f: &syn::Field) -> syn::Result<FieldInfo> {
     let ident = f.ident.as_ref().unwrap().clone();
-    let mut prop_key = ident.to_string();
+    let mut prop_key = ident.unraw().to_string();
     let mut skip = false;
 
-    for attr in &f.attrs {
-        if attr.path().is_ident("cyphr") {
-            let _ = attr.parse_nested_meta(|meta| {
+    for a in &f.attrs {
+        if a.path().is_ident("cyphr") {
+            a.parse_nested_meta(|meta| {
                 if meta.path.is_ident("skip") || meta.path.is_ident("id") {
                     skip = true;
                 } else if meta.path.is_ident("prop") {
-                    let value = meta.value()?;
-                    let expr: Expr = value.parse()?;
-                    if let Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) = expr {
-                        prop_key = s.value();
-                    }
+                    prop_key = attr::string(&meta)?.value();
+                } else {
+                    // e.g. `fulltext = "..."` for `CyphrNode`
+                    attr::skip(&meta)?;
                 }
                 Ok(())
-            });
+            })?;
         }
     }
 
-    FieldInfo { ident, prop_key, skip }
+    Ok(FieldInfo { ident, prop_key, skip })
 }
 
 pub fn expand(input: TokenStream) -> TokenStream {
@@ -57,7 +59,10 @@ pub fn expand(input: TokenStream) -> TokenStream {
     let mut inserts = Vec::new();
 
     for f in fields {
-        let info = parse_field(f);
+        let info = match parse_field(f) {
+            Ok(info) => info,
+            Err(err) => return err.to_compile_error().into(),
+        };
         if info.skip {
             continue;
         }
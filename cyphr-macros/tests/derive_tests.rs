@@ -43,6 +43,17 @@ fn test_relation_trait_impl() {
     assert_eq!(Friend::TYPE, "FRIEND");
 }
 
+#[derive(CyphrRelation)]
+#[cyphr(type = "AUTHORED", from = "Person", from_type = BlogAuthor, to_type = User)]
+struct Authored {}
+
+#[test]
+fn test_relation_endpoint_labels() {
+    assert_eq!((Friend::FROM_LABEL, Friend::TO_LABEL), (None, None));
+    // `from` stays the declared label; `to_type` alone supplies its label.
+    assert_eq!((Authored::FROM_LABEL, Authored::TO_LABEL), (Some("Person"), Some("User")));
+}
+
 // --- Auto-derived FromCyphrValue for CyphrNode ---
 
 #[test]
@@ -186,3 +197,167 @@ fn test_to_cyphr_params_prop_override() {
         other => panic!("expected String, got: {other:?}"),
     }
 }
+
+#[derive(CyphrNode, ToCyphrParams, FromCyphr)]
+#[allow(dead_code)]
+struct Profile {
+    #[cyphr(fulltext = "bio_idx", prop = "biography")]
+    bio: String,
+    #[cyphr(index)]
+    handle: String,
+}
+
+#[test]
+fn test_to_cyphr_params_skips_other_derives_keys() {
+    let map = Profile { bio: "hi".into(), handle: "ann".into() }.to_params();
+    let mut keys: Vec<_> = map.keys().collect();
+    keys.sort();
+    assert_eq!(keys, ["biography", "handle"]);
+    assert!(Profile::schema().statements().iter().any(|s| s.contains("bio_idx")));
+}
+
+// --- Property-key constants ---
+
+#[derive(Debug, CyphrNode)]
+#[cyphr(label = "Account")]
+#[allow(dead_code)]
+struct BlogAuthor {
+    id: i64,
+    #[cyphr(prop = "email_address")]
+    email: String,
+    r#type: String,
+}
+
+#[derive(Debug, CyphrRelation)]
+#[cyphr(type = "WROTE")]
+#[allow(dead_code)]
+struct HTTPLink {
+    #[cyphr(prop = "created_at")]
+    at: i64,
+}
+
+#[test]
+fn test_node_props() {
+    assert_eq!(User::PROPS, &["id", "name"]);
+    assert_eq!(user_props::ID, "id");
+    assert_eq!(user_props::NAME, "name");
+}
+
+#[test]
+fn test_node_props_renamed() {
+    assert_eq!(blog_author_props::EMAIL, "email_address");
+    assert_eq!(blog_author_props::TYPE, "type");
+    assert_eq!(BlogAuthor::PROPS[..2], ["id", "email_address"]);
+}
+
+#[test]
+fn test_relation_props() {
+    assert_eq!(Friend::PROPS, &["since"]);
+    assert_eq!(friend_props::SINCE, "since");
+    assert_eq!(HTTPLink::PROPS, &["created_at"]);
+    assert_eq!(http_link_props::AT, "created_at");
+}
+
+// --- Enums of node types ---
+
+#[derive(Debug, CyphrNode)]
+#[cyphr(label = "Post")]
+#[allow(dead_code)]
+struct Post {
+    title: String,
+}
+
+#[derive(Debug, CyphrNode)]
+#[allow(dead_code)]
+enum Content {
+    User(User),
+    Post(Post),
+}
+
+fn labelled(label: &str, props: Vec<(&str, BoltType)>) -> BoltType {
+    BoltType::Node(neo4rs::BoltNode::new(
+        neo4rs::BoltInteger::new(1),
+        vec![BoltType::from(label)].into(),
+        props.into_iter().map(|(k, v)| (neo4rs::BoltString::from(k), v)).collect(),
+    ))
+}
+
+#[test]
+fn test_node_enum_dispatches_by_label() {
+    let post = Content::from_value(labelled("Post", vec![("title", BoltType::from("Hello"))])).unwrap();
+    assert!(matches!(post, Content::Post(Post { ref title }) if title == "Hello"));
+
+    let user = labelled("User", vec![("id", BoltType::from(7)), ("name", BoltType::from("Ann"))]);
+    assert!(matches!(Content::from_value(user).unwrap(), Content::User(User { id: 7, .. })));
+}
+
+#[test]
+fn test_node_enum_unknown_label() {
+    let err = Content::from_value(labelled("Tag", vec![])).unwrap_err();
+    assert_eq!(err.to_string(), "mapping error: no variant of `Content` matches labels [\"Tag\"]");
+}
+
+#[test]
+fn test_node_enum_variant_error_context() {
+    let err = Content::from_value(labelled("Post", vec![])).unwrap_err();
+    assert!(err.to_string().starts_with("Content: "), "{err}");
+}
+
+// --- Raw identifiers ---
+
+#[derive(ToCyphrParams, FromCyphr)]
+#[allow(dead_code)]
+struct Tagged {
+    r#type: String,
+}
+
+#[test]
+fn test_raw_ident_round_trip() {
+    let map = Tagged { r#type: "admin".into() }.to_params();
+    assert_eq!(map.keys().collect::<Vec<_>>(), ["type"]);
+
+    let (keys, values): (Vec<_>, Vec<_>) = map.into_iter().map(|(k, v)| (BoltType::from(k), v)).unzip();
+    let row = Row::new(BoltList::from(keys), BoltList::from(values));
+    assert_eq!(Tagged::from_record(&row).unwrap().r#type, "admin");
+}
+
+// --- Id fields ---
+
+#[derive(CyphrNode)]
+#[allow(dead_code)]
+struct Member {
+    #[cyphr(id, prop = "memberId")]
+    id: String,
+    name: String,
+}
+
+/// Readable from a node but not bindable as a parameter.
+struct Code(#[allow(dead_code)] String);
+
+impl FromCyphrValue for Code {
+    fn from_value(value: BoltType) -> Result<Self, CyphrError> {
+        String::from_value(value).map(Code)
+    }
+}
+
+#[derive(CyphrNode)]
+#[allow(dead_code)]
+struct Ticket {
+    #[cyphr(id, no_bind)]
+    code: Code,
+}
+
+#[test]
+fn test_node_has_id() {
+    assert_eq!(Member::ID, Some("memberId"));
+    assert_eq!(<Member as cyphr_core::traits::HasId>::ID_KEY, "memberId");
+    let member = Member { id: "m-1".into(), name: "Ann".into() };
+    assert_eq!(cyphr_core::traits::HasId::id_value(&member), BoltType::from("m-1"));
+}
+
+#[test]
+fn test_node_id_without_has_id() {
+    // `no_bind`: `Ticket` gets `ID` but not `HasId`.
+    assert_eq!(Ticket::ID, Some("code"));
+    assert_eq!(User::ID, None);
+}
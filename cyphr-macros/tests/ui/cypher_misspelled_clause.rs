@@ -0,0 +1,3 @@
+fn main() {
+    let _: &str = cyphr_macros::cypher! { MATCH (u:User) RETRUN u };
+}
@@ -0,0 +1,3 @@
+fn main() {
+    let _ = cyphr_macros::cypher_query! { MATCH (u:User {id: $id(1)}) RETURN u; id = 2 };
+}
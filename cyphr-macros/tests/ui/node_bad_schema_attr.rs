@@ -0,0 +1,9 @@
+use cyphr_macros::CyphrNode;
+
+#[derive(CyphrNode)]
+struct User {
+    #[cyphr(fulltext = 5, unique)]
+    bio: String,
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use cyphr_core::traits::FromCyphrValue;
+use cyphr_macros::CyphrNode;
+
+struct Code(String);
+
+impl FromCyphrValue for Code {
+    fn from_value(value: neo4rs::BoltType) -> Result<Self, cyphr_core::error::CyphrError> {
+        String::from_value(value).map(Code)
+    }
+}
+
+impl From<Code> for neo4rs::BoltType {
+    fn from(code: Code) -> Self {
+        code.0.into()
+    }
+}
+
+#[derive(CyphrNode)]
+struct Ticket {
+    #[cyphr(id)]
+    code: Code,
+}
+
+fn main() {}
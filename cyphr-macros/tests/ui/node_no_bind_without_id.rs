@@ -0,0 +1,9 @@
+use cyphr_macros::CyphrNode;
+
+#[derive(CyphrNode)]
+struct Ticket {
+    #[cyphr(no_bind)]
+    code: String,
+}
+
+fn main() {}
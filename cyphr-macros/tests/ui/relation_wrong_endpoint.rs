@@ -0,0 +1,28 @@
+use cyphr_core::traits::{CyphrNode, RelFrom, RelTo};
+use cyphr_macros::{CyphrNode, CyphrRelation};
+
+#[derive(CyphrNode)]
+struct User {
+    name: String,
+}
+
+#[derive(CyphrNode)]
+struct Post {
+    title: String,
+}
+
+#[derive(CyphrRelation)]
+#[cyphr(type = "WROTE", from_type = User, to_type = Post)]
+struct Wrote {}
+
+#[derive(CyphrRelation)]
+#[cyphr(type = "TAGGED")]
+struct Tagged {}
+
+fn connects<R: RelFrom<A> + RelTo<B>, A: CyphrNode, B: CyphrNode>() {}
+
+fn main() {
+    connects::<Wrote, User, Post>();
+    connects::<Tagged, Post, User>();
+    connects::<Wrote, Post, User>();
+}
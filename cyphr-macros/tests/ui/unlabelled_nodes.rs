@@ -0,0 +1,28 @@
+use cyphr_core::traits::{CyphrNode, CyphrRelation};
+use cyphr_core::value::{GraphNode, Raw};
+use cyphr_macros::CyphrNode;
+
+#[derive(CyphrNode)]
+struct User {
+    name: String,
+}
+
+#[derive(CyphrNode)]
+enum Content {
+    User(User),
+}
+
+fn label<T: CyphrNode>() -> &'static str {
+    T::LABEL
+}
+
+fn rel_type<T: CyphrRelation>() -> &'static str {
+    T::TYPE
+}
+
+fn main() {
+    label::<User>();
+    label::<Content>();
+    label::<GraphNode>();
+    rel_type::<Raw>();
+}
@@ -0,0 +1,95 @@
+//! Support code for macro expansions. Not public API.
+
+/// A piece of spliced query text.
+pub enum Part {
+    /// Query text, copied as-is.
+    Text(&'static str),
+    /// A label or relationship type, backtick-quoted unless it is plain.
+    Name(&'static str),
+}
+
+/// Whether `name` can be written without backticks. Like
+/// [`cyphr_core::ident::is_plain`], but any non-ASCII name is quoted, since
+/// Unicode letters can't be classified in a `const fn`. Quoting is always
+/// valid, so this only ever adds backticks the runtime would leave out.
+const fn is_plain(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    if bytes.is_empty() || bytes[0].is_ascii_digit() {
+        return false;
+    }
+    let mut i = 0;
+    while i < bytes.len() {
+        if !(bytes[i] == b'_' || bytes[i].is_ascii_alphanumeric()) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn part_len(part: &Part) -> usize {
+    match *part {
+        Part::Text(text) => text.len(),
+        Part::Name(name) if is_plain(name) => name.len(),
+        Part::Name(name) => {
+            let bytes = name.as_bytes();
+            let mut len = name.len() + 2;
+            let mut i = 0;
+            while i < bytes.len() {
+                if bytes[i] == b'`' {
+                    len += 1;
+                }
+                i += 1;
+            }
+            len
+        }
+    }
+}
+
+/// Total byte length of `parts`, with names escaped.
+pub const fn concat_len(parts: &[Part]) -> usize {
+    let mut len = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        len += part_len(&parts[i]);
+        i += 1;
+    }
+    len
+}
+
+/// Concatenate `parts` into a byte array of length `N` at compile time,
+/// escaping names as [`cyphr_core::ident::escape`] does.
+///
+/// `N` must equal [`concat_len`] of the same parts; `cypher!` expands
+/// `#Type` splices into a call of this in a `const` item.
+pub const fn concat_bytes<const N: usize>(parts: &[Part]) -> [u8; N] {
+    let mut out = [0u8; N];
+    let mut pos = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        let (bytes, quote) = match parts[i] {
+            Part::Text(text) => (text.as_bytes(), false),
+            Part::Name(name) => (name.as_bytes(), !is_plain(name)),
+        };
+        if quote {
+            out[pos] = b'`';
+            pos += 1;
+        }
+        let mut j = 0;
+        while j < bytes.len() {
+            out[pos] = bytes[j];
+            pos += 1;
+            if quote && bytes[j] == b'`' {
+                out[pos] = b'`';
+                pos += 1;
+            }
+            j += 1;
+        }
+        if quote {
+            out[pos] = b'`';
+            pos += 1;
+        }
+        i += 1;
+    }
+    out
+}
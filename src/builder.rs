@@ -0,0 +1,278 @@
+//! Composing a [`CyphrQuery`] from fragments.
+//!
+//! Search endpoints often need filters that only apply when a value is
+//! present. [`CyphrQueryBuilder`] assembles clauses, `WHERE` conditions and
+//! paging from [`Fragment`]s, each carrying its own parameters. When two
+//! fragments use the same parameter name, the later one is renamed (`$name`
+//! becomes `$name_2`) so values never collide. The result is plain Cypher,
+//! one clause per line, that can be pasted into Neo4j Browser.
+//!
+//! ```rust
+//! use cyphr::builder::Fragment;
+//! use cyphr::query::CyphrQuery;
+//!
+//! let name: Option<&str> = Some("Al");
+//! let min_age: Option<i64> = None;
+//!
+//! let q = CyphrQuery::builder()
+//!     .clause("MATCH (u:User)")
+//!     .where_and("u.active = $flag", [("flag", true)])
+//!     .optional(name, |n| Fragment::new("u.name STARTS WITH $name").param("name", n))
+//!     .optional(min_age, |a| Fragment::new("u.age >= $age").param("age", a))
+//!     .clause("RETURN u")
+//!     .order_by("u.name")
+//!     .limit(20)
+//!     .build();
+//!
+//! assert_eq!(
+//!     q.text(),
+//!     "MATCH (u:User)\nWHERE u.active = $flag AND u.name STARTS WITH $name\nRETURN u\nORDER BY u.name\nLIMIT $limit",
+//! );
+//! ```
+
+use std::collections::HashMap;
+
+use neo4rs::BoltType as Value;
+
+use crate::lexer::{self, TokenKind};
+use crate::query::CyphrQuery;
+
+/// A piece of Cypher text together with the parameters it uses.
+#[derive(Debug, Clone)]
+pub struct Fragment {
+    text: String,
+    params: Vec<(String, Value)>,
+}
+
+impl Fragment {
+    /// Create a fragment with no parameters.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into(), params: Vec::new() }
+    }
+
+    /// Bind a parameter used by this fragment.
+    pub fn param(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.params.push((key.into(), value.into()));
+        self
+    }
+
+    /// The fragment text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl From<&str> for Fragment {
+    fn from(text: &str) -> Self {
+        Fragment::new(text)
+    }
+}
+
+impl From<String> for Fragment {
+    fn from(text: String) -> Self {
+        Fragment::new(text)
+    }
+}
+
+/// Builder returned by [`CyphrQuery::builder`].
+///
+/// Clauses are emitted in the order they are added. `WHERE` conditions are
+/// joined with `AND` and attached after the most recent clause; `ORDER BY`,
+/// `SKIP` and `LIMIT` always come last.
+///
+/// The builder does not parse clauses, so it is up to the caller to add
+/// conditions only after a clause that takes a `WHERE`: `MATCH`,
+/// `OPTIONAL MATCH`, or a `WITH` without its own `ORDER BY`, `SKIP` or
+/// `LIMIT`. Conditions after `RETURN`, `CREATE` or `UNWIND` produce a
+/// query the server rejects; filter through a `WITH` clause instead.
+#[derive(Debug, Clone, Default)]
+pub struct CyphrQueryBuilder {
+    lines: Vec<String>,
+    conditions: Vec<String>,
+    order_by: Vec<String>,
+    skip: Option<String>,
+    limit: Option<String>,
+    params: Vec<(String, Value)>,
+}
+
+impl CyphrQueryBuilder {
+    /// Create an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a clause such as `MATCH (u:User)` or `RETURN u`.
+    pub fn clause(mut self, fragment: impl Into<Fragment>) -> Self {
+        self.flush_where();
+        let text = self.merge(fragment.into());
+        self.lines.push(text);
+        self
+    }
+
+    /// Add a condition to the `WHERE` of the most recent clause, which must
+    /// be one that accepts `WHERE`; see [`CyphrQueryBuilder`].
+    pub fn where_(mut self, fragment: impl Into<Fragment>) -> Self {
+        let text = self.merge(fragment.into());
+        self.conditions.push(text);
+        self
+    }
+
+    /// Add a condition with its parameters; see [`where_`](Self::where_).
+    pub fn where_and<K, V>(self, cond: impl Into<String>, params: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<Value>,
+    {
+        let fragment = params
+            .into_iter()
+            .fold(Fragment::new(cond), |f, (k, v)| f.param(k, v));
+        self.where_(fragment)
+    }
+
+    /// Add the condition built by `fragment` only when `value` is `Some`.
+    pub fn optional<V>(self, value: Option<V>, fragment: impl FnOnce(V) -> Fragment) -> Self {
+        match value {
+            Some(v) => self.where_(fragment(v)),
+            None => self,
+        }
+    }
+
+    /// Add a sort key, e.g. `u.name DESC`. Repeated calls add further keys.
+    pub fn order_by(mut self, fragment: impl Into<Fragment>) -> Self {
+        let text = self.merge(fragment.into());
+        self.order_by.push(text);
+        self
+    }
+
+    /// Skip the first `n` rows, bound as `$skip`. A later call replaces `n`.
+    pub fn skip(mut self, n: i64) -> Self {
+        let current = self.skip.take();
+        self.skip = Some(self.paging(current, "skip", n));
+        self
+    }
+
+    /// Return at most `n` rows, bound as `$limit`. A later call replaces `n`.
+    pub fn limit(mut self, n: i64) -> Self {
+        let current = self.limit.take();
+        self.limit = Some(self.paging(current, "limit", n));
+        self
+    }
+
+    /// Assemble the query text and bind every parameter.
+    pub fn build(mut self) -> CyphrQuery {
+        self.flush_where();
+        let mut lines = self.lines;
+        if !self.order_by.is_empty() {
+            lines.push(format!("ORDER BY {}", self.order_by.join(", ")));
+        }
+        if let Some(skip) = self.skip {
+            lines.push(format!("SKIP {skip}"));
+        }
+        if let Some(limit) = self.limit {
+            lines.push(format!("LIMIT {limit}"));
+        }
+        self.params
+            .into_iter()
+            .fold(CyphrQuery::new(lines.join("\n")), |q, (k, v)| q.param(k, v))
+    }
+
+    fn flush_where(&mut self) {
+        if self.conditions.is_empty() {
+            return;
+        }
+        let grouped = self.conditions.len() > 1;
+        let conditions: Vec<String> = self
+            .conditions
+            .drain(..)
+            .map(|c| if grouped && has_top_level_or(&c) { format!("({c})") } else { c })
+            .collect();
+        self.lines.push(format!("WHERE {}", conditions.join(" AND ")));
+    }
+
+    /// Bind a `SKIP` / `LIMIT` value, rebinding the placeholder in `current`
+    /// if there is one.
+    fn paging(&mut self, current: Option<String>, key: &str, n: i64) -> String {
+        let Some(text) = current else {
+            return self.merge(Fragment::new(format!("${key}")).param(key, n));
+        };
+        if let Some(slot) = self.params.iter_mut().find(|(k, _)| *k == text[1..]) {
+            slot.1 = n.into();
+        }
+        text
+    }
+
+    /// Take over `fragment`'s parameters, renaming any that are already
+    /// bound, and return its (possibly rewritten) text.
+    fn merge(&mut self, fragment: Fragment) -> String {
+        merge_params(&mut self.params, fragment.text, fragment.params)
+    }
+}
+
+/// Append `incoming` to `params`, renaming any key that is already taken
+/// (`$name` becomes `$name_2`), and return `text` with the renames applied.
+///
+/// A key bound twice in `incoming` keeps its last value, as with
+/// [`CyphrQuery::param`]. New names avoid both the existing keys and every
+/// key of `incoming`, and `text` is rewritten in one pass, so a rename never
+/// captures another placeholder of the same fragment.
+pub(crate) fn merge_params(
+    params: &mut Vec<(String, Value)>,
+    text: String,
+    incoming: Vec<(String, Value)>,
+) -> String {
+    let mut own: Vec<(String, Value)> = Vec::with_capacity(incoming.len());
+    for (key, value) in incoming {
+        match own.iter_mut().find(|(k, _)| *k == key) {
+            Some(slot) => slot.1 = value,
+            None => own.push((key, value)),
+        }
+    }
+    let keys: Vec<String> = own.iter().map(|(k, _)| k.clone()).collect();
+    let mut renames = HashMap::new();
+    for (key, value) in own {
+        let mut name = key.clone();
+        let mut n = 2;
+        while params.iter().any(|(k, _)| *k == name) || (name != key && keys.contains(&name)) {
+            name = format!("{key}_{n}");
+            n += 1;
+        }
+        if name != key {
+            renames.insert(key, name.clone());
+        }
+        params.push((name, value));
+    }
+    if renames.is_empty() {
+        text
+    } else {
+        lexer::rename_params(&text, &renames)
+    }
+}
+
+/// Whether `cond` contains `OR`/`XOR` outside brackets, strings and comments,
+/// so that it needs parentheses before being joined with `AND`.
+fn has_top_level_or(cond: &str) -> bool {
+    let mut depth = 0i32;
+    for token in lexer::tokenize(cond) {
+        if token.kind != TokenKind::Text {
+            continue;
+        }
+        let text = &cond[token.range];
+        let mut word = String::new();
+        for c in text.chars().chain(std::iter::once(' ')) {
+            if lexer::is_ident_char(c) {
+                word.push(c);
+                continue;
+            }
+            if depth == 0 && (word.eq_ignore_ascii_case("OR") || word.eq_ignore_ascii_case("XOR")) {
+                return true;
+            }
+            word.clear();
+            match c {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+    false
+}
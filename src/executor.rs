@@ -0,0 +1,51 @@
+//! Running queries on either a [`Graph`] or a [`Txn`].
+//!
+//! Code that should work both inside and outside a transaction takes an
+//! `E: Executor` and passes `&graph` or `&mut txn`:
+//!
+//! ```rust,no_run
+//! # use cyphr::executor::Executor;
+//! # use cyphr::query::CyphrQuery;
+//! # use cyphr::CyphrError;
+//! async fn user_count<E: Executor>(exec: E) -> Result<i64, CyphrError> {
+//!     let rows = exec.fetch_rows(CyphrQuery::new("MATCH (u:User) RETURN count(u) AS n")).await?;
+//!     rows[0].get("n")
+//! }
+//!
+//! # async fn example(graph: &neo4rs::Graph) -> Result<(), CyphrError> {
+//! let total = user_count(graph).await?;
+//! let mut txn = graph.start_txn().await?;
+//! let in_txn = user_count(&mut txn).await?;
+//! txn.commit().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+
+use neo4rs::{Graph, Txn};
+use cyphr_core::error::CyphrError;
+use cyphr_core::record::CyphrRow;
+
+use crate::query::CyphrQuery;
+
+/// Something a [`CyphrQuery`] can run on.
+///
+/// Implemented for `&Graph` and `&mut Txn`. Implement it for a test double
+/// to exercise query-building code without a server.
+pub trait Executor: Send {
+    /// Run `query` and collect every row.
+    fn fetch_rows(self, query: CyphrQuery) -> impl Future<Output = Result<Vec<CyphrRow>, CyphrError>> + Send;
+}
+
+impl Executor for &Graph {
+    fn fetch_rows(self, query: CyphrQuery) -> impl Future<Output = Result<Vec<CyphrRow>, CyphrError>> + Send {
+        query.fetch_rows(self)
+    }
+}
+
+impl Executor for &mut Txn {
+    fn fetch_rows(self, query: CyphrQuery) -> impl Future<Output = Result<Vec<CyphrRow>, CyphrError>> + Send {
+        query.fetch_rows_in(self)
+    }
+}
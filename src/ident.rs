@@ -0,0 +1,107 @@
+//! Validated Cypher identifiers for dynamic labels, types and keys.
+//!
+//! Labels, relationship types and property keys cannot be passed as
+//! parameters, so a query that picks them at runtime has to put them in the
+//! text. [`CypherIdent`] makes that safe: a name that is a plain identifier
+//! is used as is, anything else is wrapped in backticks with embedded
+//! backticks doubled, so the value can never end the identifier early.
+//!
+//! ```rust
+//! use cyphr::ident::CypherIdent;
+//! use cyphr::query::CyphrQuery;
+//!
+//! let label = CypherIdent::new("Blog Post").unwrap();
+//! let q = CyphrQuery::new("MATCH (n:{label}) WHERE n.id = $id RETURN n")
+//!     .ident("label", &label)
+//!     .param("id", 7_i64);
+//!
+//! assert_eq!(q.text(), "MATCH (n:`Blog Post`) WHERE n.id = $id RETURN n");
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use cyphr_core::error::CyphrError;
+pub(crate) use cyphr_core::ident::escape;
+use cyphr_core::ident::is_plain;
+
+/// A name that can be spliced into Cypher text as a label, relationship
+/// type or property key.
+///
+/// [`Display`](fmt::Display) writes the escaped form; [`as_str`](Self::as_str)
+/// returns the name unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CypherIdent {
+    name: String,
+}
+
+impl CypherIdent {
+    /// Accept any non-empty name, quoting it with backticks when needed.
+    ///
+    /// Fails only for names Neo4j cannot store: the empty string and names
+    /// containing NUL.
+    ///
+    /// ```rust
+    /// # use cyphr::ident::CypherIdent;
+    /// assert_eq!(CypherIdent::new("User").unwrap().to_string(), "User");
+    /// assert_eq!(CypherIdent::new("has-tag").unwrap().to_string(), "`has-tag`");
+    /// assert_eq!(CypherIdent::new("a`b").unwrap().to_string(), "`a``b`");
+    /// assert!(CypherIdent::new("").is_err());
+    /// ```
+    pub fn new(name: impl Into<String>) -> Result<Self, CyphrError> {
+        let name = name.into();
+        if name.is_empty() {
+            return Err(CyphrError::invalid_ident(&name, "must not be empty"));
+        }
+        if name.contains('\0') {
+            return Err(CyphrError::invalid_ident(&name, "must not contain NUL"));
+        }
+        Ok(Self { name })
+    }
+
+    /// Accept only names that need no quoting: a letter or `_` followed by
+    /// letters, ASCII digits and `_`.
+    ///
+    /// Use this when the name comes from outside and anything unusual
+    /// should be refused rather than escaped.
+    ///
+    /// ```rust
+    /// # use cyphr::ident::CypherIdent;
+    /// assert!(CypherIdent::strict("Café").is_ok());
+    /// assert!(CypherIdent::strict("User) DETACH DELETE (n").is_err());
+    /// ```
+    pub fn strict(name: impl Into<String>) -> Result<Self, CyphrError> {
+        let ident = Self::new(name)?;
+        if ident.is_quoted() {
+            return Err(CyphrError::invalid_ident(
+                &ident.name,
+                "must start with a letter or `_` and contain only letters, digits and `_`",
+            ));
+        }
+        Ok(ident)
+    }
+
+    /// The name as given, without quoting.
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the name is written in backticks.
+    pub fn is_quoted(&self) -> bool {
+        !is_plain(&self.name)
+    }
+}
+
+impl fmt::Display for CypherIdent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&escape(&self.name))
+    }
+}
+
+impl FromStr for CypherIdent {
+    type Err = CyphrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
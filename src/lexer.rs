@@ -0,0 +1,212 @@
+//! A minimal Cypher scanner for runtime query inspection.
+//!
+//! Splits query text into string literals, quoted identifiers, comments,
+//! `$parameters` and everything else, so callers can reason about
+//! placeholders without being fooled by `'$not_a_param'` or
+//! `// $commented_out`. This is not a parser — it never rejects input.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// What a [`Token`] covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    /// A `$name` or `` $`quoted name` `` placeholder; holds the bare name.
+    Param(String),
+    /// A `'...'` or `"..."` string literal, quotes included.
+    StringLit,
+    /// A `` `...` `` quoted identifier, backticks included.
+    QuotedIdent,
+    /// A `// ...` or `/* ... */` comment.
+    Comment,
+    /// Any other run of query text.
+    Text,
+}
+
+/// A classified byte range of the query text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Token {
+    pub kind: TokenKind,
+    pub range: Range<usize>,
+}
+
+/// Split `text` into classified tokens covering every byte exactly once.
+pub(crate) fn tokenize(text: &str) -> Vec<Token> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::new();
+    let mut text_start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let (kind, end) = match bytes[i] {
+            q @ (b'\'' | b'"') => (TokenKind::StringLit, skip_string(bytes, i, q)),
+            b'`' => (TokenKind::QuotedIdent, skip_quoted_ident(bytes, i)),
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                let end = text[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                (TokenKind::Comment, end)
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = text[i + 2..].find("*/").map_or(bytes.len(), |n| i + 2 + n + 2);
+                (TokenKind::Comment, end)
+            }
+            b'$' => match scan_param(text, i) {
+                Some((name, end)) => (TokenKind::Param(name), end),
+                None => {
+                    i += 1;
+                    continue;
+                }
+            },
+            _ => {
+                i += text[i..].chars().next().map_or(1, char::len_utf8);
+                continue;
+            }
+        };
+        if text_start < i {
+            out.push(Token { kind: TokenKind::Text, range: text_start..i });
+        }
+        out.push(Token { kind, range: i..end });
+        i = end;
+        text_start = end;
+    }
+    if text_start < bytes.len() {
+        out.push(Token { kind: TokenKind::Text, range: text_start..bytes.len() });
+    }
+    out
+}
+
+/// Parameter names in order of first appearance, deduplicated.
+pub(crate) fn param_names(text: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for token in tokenize(text) {
+        if let TokenKind::Param(name) = token.kind {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// Replace every `$from` placeholder in `text` with `$to` for each entry of
+/// `renames`, in a single pass, leaving string literals, quoted identifiers
+/// and comments alone.
+pub(crate) fn rename_params(text: &str, renames: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    for token in tokenize(text) {
+        match token.kind {
+            TokenKind::Param(ref name) if renames.contains_key(name) => {
+                let to = &renames[name];
+                if !to.is_empty() && to.chars().all(is_ident_char) {
+                    out.push('$');
+                    out.push_str(to);
+                } else {
+                    out.push_str("$`");
+                    out.push_str(&to.replace('`', "``"));
+                    out.push('`');
+                }
+            }
+            _ => out.push_str(&text[token.range]),
+        }
+    }
+    out
+}
+
+/// Replace every `{slot}` in `text` with `with`, leaving string literals,
+/// quoted identifiers and comments alone.
+pub(crate) fn fill_slot(text: &str, slot: &str, with: &str) -> String {
+    let pattern = format!("{{{slot}}}");
+    let mut out = String::with_capacity(text.len());
+    for token in tokenize(text) {
+        let part = &text[token.range];
+        match token.kind {
+            TokenKind::Text => out.push_str(&part.replace(&pattern, with)),
+            _ => out.push_str(part),
+        }
+    }
+    out
+}
+
+/// Split `text` into statements at `;`, ignoring semicolons inside string
+/// literals, quoted identifiers and comments. Statements that are empty or
+/// only comments are dropped.
+pub(crate) fn split_statements(text: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    let mut has_code = false;
+    for token in tokenize(text) {
+        if token.kind == TokenKind::Text {
+            let part = &text[token.range.clone()];
+            let mut offset = token.range.start;
+            for piece in part.split_inclusive(';') {
+                let end = offset + piece.len();
+                let body = piece.strip_suffix(';');
+                has_code |= !body.unwrap_or(piece).trim().is_empty();
+                if body.is_some() {
+                    if has_code {
+                        out.push(text[start..end - 1].trim().to_owned());
+                    }
+                    start = end;
+                    has_code = false;
+                }
+                offset = end;
+            }
+        } else if token.kind != TokenKind::Comment {
+            has_code = true;
+        }
+    }
+    if has_code {
+        out.push(text[start..].trim().to_owned());
+    }
+    out
+}
+
+/// Whether `c` may appear in an unquoted Cypher identifier.
+pub(crate) fn is_ident_char(c: char) -> bool {
+    c == '_' || c.is_alphanumeric()
+}
+
+fn skip_string(bytes: &[u8], start: usize, quote: u8) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b if b == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+fn skip_quoted_ident(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        if bytes[i] == b'`' {
+            if bytes.get(i + 1) == Some(&b'`') {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+fn scan_param(text: &str, dollar: usize) -> Option<(String, usize)> {
+    let rest = &text[dollar + 1..];
+    if rest.starts_with('`') {
+        let end = skip_quoted_ident(text.as_bytes(), dollar + 1);
+        let body = &text[dollar + 2..end];
+        let body = body.strip_suffix('`').unwrap_or(body);
+        return Some((body.replace("``", "`"), end));
+    }
+    let len: usize = rest
+        .chars()
+        .take_while(|c| is_ident_char(*c))
+        .map(char::len_utf8)
+        .sum();
+    if len == 0 {
+        return None;
+    }
+    Some((rest[..len].to_owned(), dollar + 1 + len))
+}
@@ -99,7 +99,7 @@ use cyphr::query;
 # #[derive(FromCyphr)]
 # struct UserRow { u: User }
 # async fn example(graph: &neo4rs::Graph) -> Result<(), CyphrError> {
-// Exactly one row (error if empty):
+// First row (CyphrError::NoRows if empty):
 let user: UserRow = query::query("MATCH (u:User) RETURN u LIMIT 1")
     .fetch_one(graph)
     .await?;
@@ -171,12 +171,12 @@ the parameter map. Use `#[cyphr(prop = "...")]` to override the key name.
 | List | `Vec<T>`, `(A, B)`, `(A, B, C)` |
 | Map | `HashMap<String, V>` |
 | Null | `Option<T>` |
-| Node | `NodeWrapper<T>`, or directly via `#[derive(CyphrNode)]` |
+| Node | `NodeWrapper<T>`, [`GraphNode`], or directly via `#[derive(CyphrNode)]` |
 | Relationship | `RelationWrapper<T>`, or directly via `#[derive(CyphrRelation)]` |
 | Point2D | [`Point2D`] |
 | Point3D | [`Point3D`] |
 | Bytes | [`CyphrBytes`] |
-| Path | [`CyphrPath<N>`] |
+| Path | [`CyphrPath<N, R>`] |
 | Date | `chrono::NaiveDate` |
 | LocalTime | `chrono::NaiveTime` |
 | Time | `(chrono::NaiveTime, chrono::FixedOffset)` |
@@ -198,15 +198,27 @@ User::name (prop 'name'): type mismatch: expected String, got Integer (String)
 [`Point2D`]: cyphr_core::Point2D
 [`Point3D`]: cyphr_core::Point3D
 [`CyphrBytes`]: cyphr_core::CyphrBytes
-[`CyphrPath<N>`]: cyphr_core::CyphrPath
+[`CyphrPath<N, R>`]: cyphr_core::CyphrPath
+[`GraphNode`]: cyphr_core::GraphNode
 "#]
 
+mod lexer;
+#[doc(hidden)]
+pub mod __private;
+pub mod builder;
+pub mod executor;
+pub mod ident;
+pub mod migrate;
+pub mod pattern;
 pub mod prelude;
 pub mod query;
+pub mod relation;
+pub mod repository;
+pub mod schema;
 pub mod stream;
 
 pub use cyphr_core as core;
-pub use cyphr_macros::{CyphrNode, CyphrRelation, FromCyphr, ToCyphrParams, cypher, cypher_query};
+pub use cyphr_macros::{CyphrNode, CyphrQueryDef, CyphrRelation, FromCyphr, ToCyphrParams, cypher, cypher_query, embed_migrations, include_cypher};
 
-pub use cyphr_core::traits::{CyphrNode as CyphrNodeTrait, CyphrRelation as CyphrRelationTrait, FromCyphr as FromCyphrTrait};
+pub use cyphr_core::traits::{CyphrNode as CyphrNodeTrait, CyphrRelation as CyphrRelationTrait, FromCyphr as FromCyphrTrait, FromCyphrNode, FromCyphrRel, HasId, RelFrom, RelTo};
 pub use cyphr_core::CyphrError;
@@ -0,0 +1,399 @@
+//! Versioned Cypher migrations.
+//!
+//! Migrations are `.cypher` files named `{version}_{name}.cypher`, e.g.
+//! `0001_create_users.cypher`, holding one or more statements separated by
+//! `;`. A [`Migrator`] loads them from a directory at runtime
+//! ([`Migrator::from_dir`]) or embeds them at compile time with
+//! [`embed_migrations!`](crate::embed_migrations), which also checks every
+//! statement's syntax.
+//!
+//! [`Migrator::run`] applies pending migrations in version order, each in
+//! its own transaction, and records it as a `(:__CyphrMigration {version,
+//! name, checksum, applied_at})` node. Before anything runs, every applied
+//! migration's checksum is compared with the file; if one was edited after
+//! it was applied, nothing is run and [`CyphrError::Migration`] is
+//! returned.
+//!
+//! Only one runner applies migrations at a time. `run` first claims a
+//! single `(:__CyphrMigrationLock)` node, backed by a uniqueness
+//! constraint, and fails if another runner holds it. The lock is removed
+//! when `run` returns; if a runner dies while holding it, clear it with
+//! [`Migrator::unlock`].
+//!
+//! ```rust,no_run
+//! # async fn example(graph: &neo4rs::Graph) -> Result<(), cyphr::CyphrError> {
+//! let migrator = cyphr::embed_migrations!("tests/migrations");
+//!
+//! for entry in migrator.status(graph).await? {
+//!     println!("{:>4} {:<30} {:?}", entry.version, entry.name, entry.state);
+//! }
+//! let applied = migrator.run(graph).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A data migration is recorded in the same transaction as its statements,
+//! so it is applied exactly once. Neo4j does not allow schema changes and
+//! data writes in one transaction, so a schema migration (see
+//! [`Migration::is_schema`]) is recorded just after it commits. If the
+//! process stops in between, it runs again next time; write schema
+//! statements with `IF NOT EXISTS` / `IF EXISTS`.
+
+use std::path::Path;
+
+use neo4rs::Graph;
+use cyphr_core::error::CyphrError;
+
+use crate::lexer;
+use crate::query::CyphrQuery;
+
+/// Label of the nodes recording applied migrations.
+pub const MIGRATION_LABEL: &str = "__CyphrMigration";
+
+/// Label of the node held by a running [`Migrator::run`].
+pub const LOCK_LABEL: &str = "__CyphrMigrationLock";
+
+/// One migration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    version: i64,
+    name: String,
+    text: String,
+    checksum: String,
+}
+
+impl Migration {
+    /// A migration with the given version, name and Cypher text.
+    pub fn new(version: i64, name: impl Into<String>, text: impl Into<String>) -> Self {
+        let text = text.into();
+        let checksum = checksum(&text);
+        Self { version, name: name.into(), text, checksum }
+    }
+
+    /// Parse `version` and `name` from a file name such as
+    /// `0001_create_users.cypher`.
+    pub fn from_file(file_name: &str, text: impl Into<String>) -> Result<Self, CyphrError> {
+        let (version, name) = parse_file_name(file_name).ok_or_else(|| {
+            CyphrError::Migration(format!(
+                "`{file_name}` is not named like `0001_description.cypher`"
+            ))
+        })?;
+        Ok(Self::new(version, name, text))
+    }
+
+    /// The version number from the file name.
+    pub fn version(&self) -> i64 {
+        self.version
+    }
+
+    /// The description from the file name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The file contents.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Checksum of the file contents, ignoring line-ending differences.
+    pub fn checksum(&self) -> &str {
+        &self.checksum
+    }
+
+    /// The statements in the file, split at `;`.
+    pub fn statements(&self) -> Vec<String> {
+        lexer::split_statements(&self.text)
+    }
+
+    /// Whether any statement creates or drops a constraint or index.
+    ///
+    /// Such a migration can't write its record in the same transaction.
+    pub fn is_schema(&self) -> bool {
+        self.statements().iter().any(|s| is_schema_statement(s))
+    }
+}
+
+/// A migration as recorded in the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub name: String,
+    pub checksum: String,
+}
+
+/// Where a migration stands; see [`Migrator::status`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationState {
+    /// Applied, and the file is unchanged.
+    Applied,
+    /// Not applied yet.
+    Pending,
+    /// Applied, but the file has changed since; holds the recorded checksum.
+    Drifted { recorded: String },
+    /// Recorded in the database but no longer present locally.
+    Missing,
+}
+
+/// One line of a [`Migrator::status`] report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: String,
+    pub state: MigrationState,
+}
+
+/// An ordered set of migrations.
+#[derive(Debug, Clone, Default)]
+pub struct Migrator {
+    migrations: Vec<Migration>,
+}
+
+impl Migrator {
+    /// A migrator for `migrations`, sorted by version.
+    pub fn new(mut migrations: Vec<Migration>) -> Self {
+        migrations.sort_by_key(Migration::version);
+        Self { migrations }
+    }
+
+    /// Load every `.cypher` file in `dir`.
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self, CyphrError> {
+        let dir = dir.as_ref();
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| CyphrError::Migration(format!("cannot read `{}`: {e}", dir.display())))?;
+        let mut migrations = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|e| CyphrError::Migration(format!("cannot read `{}`: {e}", dir.display())))?
+                .path();
+            if path.extension().is_none_or(|ext| ext != "cypher") {
+                continue;
+            }
+            let text = std::fs::read_to_string(&path)
+                .map_err(|e| CyphrError::Migration(format!("cannot read `{}`: {e}", path.display())))?;
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+            migrations.push(Migration::from_file(&file_name, text)?);
+        }
+        Ok(Self::new(migrations))
+    }
+
+    /// The migrations, in version order.
+    pub fn migrations(&self) -> &[Migration] {
+        &self.migrations
+    }
+
+    /// Compare the local migrations with those recorded in the database.
+    pub async fn status(&self, graph: &Graph) -> Result<Vec<MigrationStatus>, CyphrError> {
+        let applied = applied(graph).await?;
+        self.compare(&applied)
+    }
+
+    /// Compare the local migrations with `applied`, without a database.
+    ///
+    /// The report is in version order. Fails if two local migrations share
+    /// a version.
+    pub fn compare(&self, applied: &[AppliedMigration]) -> Result<Vec<MigrationStatus>, CyphrError> {
+        if let Some(pair) = self.migrations.windows(2).find(|w| w[0].version == w[1].version) {
+            return Err(CyphrError::Migration(format!(
+                "`{}` and `{}` both have version {}",
+                pair[0].name, pair[1].name, pair[0].version
+            )));
+        }
+        let mut report: Vec<MigrationStatus> = self
+            .migrations
+            .iter()
+            .map(|m| {
+                let state = match applied.iter().find(|a| a.version == m.version) {
+                    None => MigrationState::Pending,
+                    Some(a) if a.checksum == m.checksum => MigrationState::Applied,
+                    Some(a) => MigrationState::Drifted { recorded: a.checksum.clone() },
+                };
+                MigrationStatus { version: m.version, name: m.name.clone(), state }
+            })
+            .collect();
+        for a in applied {
+            if !self.migrations.iter().any(|m| m.version == a.version) {
+                report.push(MigrationStatus {
+                    version: a.version,
+                    name: a.name.clone(),
+                    state: MigrationState::Missing,
+                });
+            }
+        }
+        report.sort_by_key(|s| s.version);
+        Ok(report)
+    }
+
+    /// Apply every pending migration in version order and return their
+    /// versions.
+    ///
+    /// Refuses to run anything if an applied migration has drifted, or if
+    /// another runner holds the migration lock.
+    pub async fn run(&self, graph: &Graph) -> Result<Vec<i64>, CyphrError> {
+        for (label, key) in [(MIGRATION_LABEL, "version"), (LOCK_LABEL, "id")] {
+            graph
+                .run(neo4rs::query(&format!(
+                    "CREATE CONSTRAINT {label}_{key}_unique IF NOT EXISTS \
+                     FOR (m:{label}) REQUIRE m.{key} IS UNIQUE"
+                )))
+                .await?;
+        }
+
+        let token = lock_token();
+        lock(graph, &token).await?;
+        let result = self.apply(graph).await;
+        let released = unlock(graph, Some(&token)).await;
+        let done = result?;
+        released?;
+        Ok(done)
+    }
+
+    /// Remove the migration lock left by a runner that stopped while
+    /// holding it.
+    ///
+    /// Only call this when no runner is active.
+    pub async fn unlock(&self, graph: &Graph) -> Result<(), CyphrError> {
+        unlock(graph, None).await
+    }
+
+    /// Apply the pending migrations; the caller holds the lock.
+    async fn apply(&self, graph: &Graph) -> Result<Vec<i64>, CyphrError> {
+        let report = self.status(graph).await?;
+        if let Some(drifted) = report.iter().find(|s| matches!(s.state, MigrationState::Drifted { .. })) {
+            return Err(CyphrError::Migration(format!(
+                "migration {} ({}) has changed since it was applied",
+                drifted.version, drifted.name
+            )));
+        }
+
+        let mut done = Vec::new();
+        for status in report.iter().filter(|s| s.state == MigrationState::Pending) {
+            let Some(migration) = self.migrations.iter().find(|m| m.version == status.version) else {
+                continue;
+            };
+            let record = neo4rs::query(&format!(
+                "CREATE (:{MIGRATION_LABEL} {{version: $version, name: $name, checksum: $checksum, applied_at: datetime()}})"
+            ))
+            .param("version", migration.version)
+            .param("name", migration.name.as_str())
+            .param("checksum", migration.checksum.as_str());
+
+            let mut txn = graph.start_txn().await?;
+            for statement in migration.statements() {
+                txn.run(neo4rs::query(&statement)).await?;
+            }
+            if migration.is_schema() {
+                txn.commit().await?;
+                graph.run(record).await?;
+            } else {
+                txn.run(record).await?;
+                txn.commit().await?;
+            }
+            done.push(migration.version);
+        }
+        Ok(done)
+    }
+}
+
+/// Claim the migration lock for `token`, failing if another runner holds it.
+///
+/// The uniqueness constraint on the lock's `id` makes concurrent `MERGE`s
+/// agree on a single node, so only one runner sees its own token.
+async fn lock(graph: &Graph, token: &str) -> Result<(), CyphrError> {
+    let rows = CyphrQuery::new(format!(
+        "MERGE (l:{LOCK_LABEL} {{id: 1}}) \
+         ON CREATE SET l.token = $token, l.locked_at = datetime() \
+         RETURN l.token = $token AS claimed, toString(l.locked_at) AS since"
+    ))
+    .param("token", token)
+    .fetch_rows(graph)
+    .await?;
+    let row = rows.first().ok_or(CyphrError::NoRows)?;
+    if row.get::<bool>("claimed")? {
+        return Ok(());
+    }
+    let since: String = row.get("since")?;
+    Err(CyphrError::Migration(format!(
+        "another runner has held the migration lock since {since}; \
+         if none is running, clear it with `Migrator::unlock`"
+    )))
+}
+
+/// Remove the lock, only if it still holds `token` when one is given.
+async fn unlock(graph: &Graph, token: Option<&str>) -> Result<(), CyphrError> {
+    let query = match token {
+        Some(token) => neo4rs::query(&format!("MATCH (l:{LOCK_LABEL} {{token: $token}}) DELETE l")).param("token", token),
+        None => neo4rs::query(&format!("MATCH (l:{LOCK_LABEL}) DELETE l")),
+    };
+    graph.run(query).await?;
+    Ok(())
+}
+
+/// A token identifying this runner's claim on the lock.
+fn lock_token() -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    format!("{}-{nanos:x}", std::process::id())
+}
+
+/// Whether `statement` is `CREATE` / `DROP` of a constraint or index.
+fn is_schema_statement(statement: &str) -> bool {
+    let mut head = String::new();
+    for token in lexer::tokenize(statement) {
+        match token.kind {
+            lexer::TokenKind::Text => head.push_str(&statement[token.range]),
+            lexer::TokenKind::Comment => head.push(' '),
+            _ => break,
+        }
+    }
+    let words: Vec<String> = head.split_whitespace().take(3).map(str::to_ascii_uppercase).collect();
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+    matches!(
+        words.as_slice(),
+        ["CREATE" | "DROP", "CONSTRAINT" | "INDEX", ..]
+            | ["CREATE" | "DROP", "FULLTEXT" | "RANGE" | "TEXT" | "POINT" | "LOOKUP" | "VECTOR" | "BTREE", "INDEX", ..]
+    )
+}
+
+/// Read the `:__CyphrMigration` records.
+async fn applied(graph: &Graph) -> Result<Vec<AppliedMigration>, CyphrError> {
+    let rows = CyphrQuery::new(format!(
+        "MATCH (m:{MIGRATION_LABEL}) RETURN m.version AS version, m.name AS name, m.checksum AS checksum"
+    ))
+    .fetch_rows(graph)
+    .await?;
+    rows.iter()
+        .map(|row| {
+            Ok(AppliedMigration {
+                version: row.get("version")?,
+                name: row.get("name")?,
+                checksum: row.get("checksum")?,
+            })
+        })
+        .collect()
+}
+
+/// `0001_create_users.cypher` → `(1, "create_users")`.
+fn parse_file_name(file_name: &str) -> Option<(i64, String)> {
+    let stem = file_name.strip_suffix(".cypher")?;
+    let (version, name) = stem.split_once('_')?;
+    if version.is_empty() || !version.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((version.parse().ok()?, name.trim_start_matches('_').to_owned()))
+}
+
+/// 64-bit FNV-1a of `text` with `\r\n` read as `\n`, as 16 hex digits.
+fn checksum(text: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let bytes = text.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        if b == b'\r' && bytes.get(i + 1) == Some(&b'\n') {
+            continue;
+        }
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{hash:016x}")
+}
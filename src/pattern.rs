@@ -0,0 +1,444 @@
+//! A typed builder for `MATCH` patterns.
+//!
+//! Patterns are assembled from `#[derive(CyphrNode)]` and
+//! `#[derive(CyphrRelation)]` types, so labels and relationship types come
+//! from [`CyphrNode::LABEL`] and [`CyphrRelation::TYPE`] instead of being
+//! retyped by hand. When a relationship declares `from_type` / `to_type`,
+//! connecting it to a different node type is a type error (see [`RelFrom`] /
+//! [`RelTo`]).
+//!
+//! The result is plain Cypher (see [`Statement`]'s `Display`) built on
+//! [`CyphrQueryBuilder`], so parameters are bound and renamed the same way.
+//!
+//! ```rust
+//! use cyphr::prelude::*;
+//! use cyphr::pattern::{param, Match, Var};
+//!
+//! #[derive(CyphrNode)]
+//! #[cyphr(label = "User")]
+//! struct User { name: String }
+//!
+//! #[derive(CyphrRelation)]
+//! #[cyphr(type = "FOLLOWS", from_type = User, to_type = User)]
+//! struct Follows { since: i64 }
+//!
+//! let u = Var::<User>::new("u");
+//! let f = Var::<Follows>::new("f");
+//! let v = Var::<User>::new("v");
+//!
+//! let stmt = Match::node(&u)
+//!     .rel(&f)
+//!     .node(&v)
+//!     .where_(u.prop(user_props::NAME).eq(param("name", "Alice")))
+//!     .ret([v.prop("name").as_("name"), f.prop("since").as_("since")]);
+//!
+//! assert_eq!(
+//!     stmt.to_string(),
+//!     "MATCH (u:User)-[f:FOLLOWS]->(v:User)\nWHERE u.name = $name\nRETURN v.name AS name, f.since AS since",
+//! );
+//! let query = stmt.query();
+//! ```
+//!
+//! A relationship whose declared endpoints don't match does not compile:
+//!
+//! ```compile_fail
+//! use cyphr::prelude::*;
+//! use cyphr::pattern::Match;
+//!
+//! #[derive(CyphrNode)]
+//! #[cyphr(label = "User")]
+//! struct User { name: String }
+//!
+//! #[derive(CyphrNode)]
+//! #[cyphr(label = "Product")]
+//! struct Product { sku: String }
+//!
+//! #[derive(CyphrRelation)]
+//! #[cyphr(type = "FOLLOWS", from_type = User, to_type = User)]
+//! struct Follows { since: i64 }
+//!
+//! let stmt = Match::node::<Product>("p").rel::<Follows>("f").node::<User>("u");
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use cyphr_core::traits::{CyphrNode, CyphrRelation, RelFrom, RelTo};
+use neo4rs::BoltType as Value;
+
+use crate::builder::{self, CyphrQueryBuilder, Fragment};
+use crate::ident::escape;
+use crate::query::CyphrQuery;
+
+/// A pattern variable bound to a node or relationship type.
+pub struct Var<T> {
+    name: String,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> Var<T> {
+    /// Create a variable called `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), _type: PhantomData }
+    }
+
+    /// The variable name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The property `key` of this variable, e.g. `u.name`.
+    ///
+    /// Prefer the derive's property consts (`user_props::NAME`) over string
+    /// literals so renamed properties are picked up.
+    pub fn prop(&self, key: &str) -> Expr {
+        Expr::atom(format!("{}.{}", escape(&self.name), escape(key)))
+    }
+}
+
+impl<T> Clone for Var<T> {
+    fn clone(&self) -> Self {
+        Var::new(self.name.clone())
+    }
+}
+
+impl<T> From<&str> for Var<T> {
+    fn from(name: &str) -> Self {
+        Var::new(name)
+    }
+}
+
+impl<T> From<&Var<T>> for Var<T> {
+    fn from(var: &Var<T>) -> Self {
+        var.clone()
+    }
+}
+
+impl<T> From<&Var<T>> for Expr {
+    fn from(var: &Var<T>) -> Self {
+        Expr::atom(escape(&var.name).into_owned())
+    }
+}
+
+/// Entry point for a typed `MATCH`; see the [module docs](self).
+pub enum Match {}
+
+impl Match {
+    /// Start a pattern at a node, e.g. `(u:User)`.
+    pub fn node<N: CyphrNode>(var: impl Into<Var<N>>) -> Pattern<N> {
+        Pattern { text: node_text(&var.into()), conditions: Vec::new(), _last: PhantomData }
+    }
+}
+
+/// A pattern ending in a node of type `N`.
+pub struct Pattern<N> {
+    text: String,
+    conditions: Vec<Expr>,
+    _last: PhantomData<fn() -> N>,
+}
+
+impl<N: CyphrNode> Pattern<N> {
+    /// Continue with an outgoing relationship, `-[f:FOLLOWS]->`.
+    ///
+    /// `N` must be the relationship's `from_type`, if it declares one.
+    pub fn rel<R: RelFrom<N>>(self, var: impl Into<Var<R>>) -> PatternRel<N, R, Outgoing> {
+        self.step(var.into(), "-", "->")
+    }
+
+    /// Continue with an incoming relationship, `<-[f:FOLLOWS]-`.
+    ///
+    /// `N` must be the relationship's `to_type`, if it declares one.
+    pub fn rel_in<R: RelTo<N>>(self, var: impl Into<Var<R>>) -> PatternRel<N, R, Incoming> {
+        self.step(var.into(), "<-", "-")
+    }
+
+    /// Add a `WHERE` condition; repeated calls are joined with `AND`.
+    pub fn where_(mut self, cond: Expr) -> Self {
+        self.conditions.push(cond);
+        self
+    }
+
+    /// Finish with `RETURN`.
+    pub fn ret(self, items: impl IntoIterator<Item = Expr>) -> Statement {
+        let mut builder = CyphrQueryBuilder::new().clause(format!("MATCH {}", self.text));
+        for cond in self.conditions {
+            builder = builder.where_(cond);
+        }
+        let items = items.into_iter().reduce(|a, b| a.concat(", ", b)).unwrap_or_else(|| Expr::atom("*"));
+        Statement { builder: builder.clause(Expr::atom("RETURN ").concat("", items)) }
+    }
+
+    fn step<R: CyphrRelation, D>(mut self, var: Var<R>, left: &str, right: &str) -> PatternRel<N, R, D> {
+        self.text.push_str(&format!("{left}[{}:{}]{right}", escape(&var.name), escape(R::TYPE)));
+        PatternRel { text: self.text, conditions: self.conditions, _types: PhantomData, _direction: PhantomData }
+    }
+}
+
+/// A pattern ending in a relationship of type `R`, traversed in direction
+/// `D`, that started at a node of type `N`; finish it with
+/// [`node`](Self::node).
+pub struct PatternRel<N, R, D> {
+    text: String,
+    conditions: Vec<Expr>,
+    _types: PhantomData<fn() -> (N, R)>,
+    _direction: PhantomData<D>,
+}
+
+impl<N: CyphrNode, R: CyphrRelation> PatternRel<N, R, Outgoing> {
+    /// Close the relationship at a node of type `M`, which must be the
+    /// relationship's `to_type` if it declares one.
+    pub fn node<M: CyphrNode>(self, var: impl Into<Var<M>>) -> Pattern<M>
+    where
+        R: RelTo<M>,
+    {
+        self.close(var.into())
+    }
+}
+
+impl<N: CyphrNode, R: CyphrRelation> PatternRel<N, R, Incoming> {
+    /// Close the relationship at a node of type `M`, which must be the
+    /// relationship's `from_type` if it declares one.
+    pub fn node<M: CyphrNode>(self, var: impl Into<Var<M>>) -> Pattern<M>
+    where
+        R: RelFrom<M>,
+    {
+        self.close(var.into())
+    }
+}
+
+impl<N, R, D> PatternRel<N, R, D> {
+    fn close<M: CyphrNode>(mut self, var: Var<M>) -> Pattern<M> {
+        self.text.push_str(&node_text(&var));
+        Pattern { text: self.text, conditions: self.conditions, _last: PhantomData }
+    }
+}
+
+/// Direction of a relationship in a [`PatternRel`]: [`Outgoing`] or
+/// [`Incoming`].
+pub trait Direction: sealed::Sealed {}
+
+/// `(n)-[r]->(m)`
+pub enum Outgoing {}
+
+/// `(n)<-[r]-(m)`
+pub enum Incoming {}
+
+impl Direction for Outgoing {}
+
+impl Direction for Incoming {}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::Outgoing {}
+    impl Sealed for super::Incoming {}
+}
+
+fn node_text<N: CyphrNode>(var: &Var<N>) -> String {
+    format!("({}:{})", escape(&var.name), escape(N::LABEL))
+}
+
+/// A Cypher expression together with the parameters it uses.
+///
+/// Built from [`Var::prop`], [`param`] and [`Expr::raw`], and combined with
+/// the comparison and boolean methods. Operands are parenthesized as needed.
+#[derive(Debug, Clone)]
+pub struct Expr {
+    text: String,
+    params: Vec<(String, Value)>,
+    /// Binding strength: 0 for lists, 1 `OR`, 2 `XOR`, 3 `AND`, 4 `NOT`,
+    /// 5 comparisons, 9 atoms.
+    prec: u8,
+}
+
+/// A `$name` placeholder bound to `value`.
+///
+/// If another part of the statement already uses `name`, this one is
+/// renamed when the statement is built.
+pub fn param(name: &str, value: impl Into<Value>) -> Expr {
+    Expr { text: format!("${name}"), params: vec![(name.to_owned(), value.into())], prec: 9 }
+}
+
+impl Expr {
+    /// Raw Cypher such as `count(*)`, used verbatim.
+    pub fn raw(text: impl Into<String>) -> Self {
+        Expr::atom(text)
+    }
+
+    fn atom(text: impl Into<String>) -> Self {
+        Expr { text: text.into(), params: Vec::new(), prec: 9 }
+    }
+
+    /// `self = rhs`
+    pub fn eq(self, rhs: impl Into<Expr>) -> Expr {
+        self.join(" = ", rhs.into(), 5)
+    }
+
+    /// `self <> rhs`
+    pub fn ne(self, rhs: impl Into<Expr>) -> Expr {
+        self.join(" <> ", rhs.into(), 5)
+    }
+
+    /// `self < rhs`
+    pub fn lt(self, rhs: impl Into<Expr>) -> Expr {
+        self.join(" < ", rhs.into(), 5)
+    }
+
+    /// `self <= rhs`
+    pub fn le(self, rhs: impl Into<Expr>) -> Expr {
+        self.join(" <= ", rhs.into(), 5)
+    }
+
+    /// `self > rhs`
+    pub fn gt(self, rhs: impl Into<Expr>) -> Expr {
+        self.join(" > ", rhs.into(), 5)
+    }
+
+    /// `self >= rhs`
+    pub fn ge(self, rhs: impl Into<Expr>) -> Expr {
+        self.join(" >= ", rhs.into(), 5)
+    }
+
+    /// `self IN rhs`
+    pub fn in_(self, rhs: impl Into<Expr>) -> Expr {
+        self.join(" IN ", rhs.into(), 5)
+    }
+
+    /// `self STARTS WITH rhs`
+    pub fn starts_with(self, rhs: impl Into<Expr>) -> Expr {
+        self.join(" STARTS WITH ", rhs.into(), 5)
+    }
+
+    /// `self ENDS WITH rhs`
+    pub fn ends_with(self, rhs: impl Into<Expr>) -> Expr {
+        self.join(" ENDS WITH ", rhs.into(), 5)
+    }
+
+    /// `self CONTAINS rhs`
+    pub fn contains(self, rhs: impl Into<Expr>) -> Expr {
+        self.join(" CONTAINS ", rhs.into(), 5)
+    }
+
+    /// `self IS NULL`
+    pub fn is_null(self) -> Expr {
+        self.postfix(" IS NULL")
+    }
+
+    /// `self IS NOT NULL`
+    pub fn is_not_null(self) -> Expr {
+        self.postfix(" IS NOT NULL")
+    }
+
+    /// `self AND rhs`
+    pub fn and(self, rhs: Expr) -> Expr {
+        self.join(" AND ", rhs, 3)
+    }
+
+    /// `self OR rhs`
+    pub fn or(self, rhs: Expr) -> Expr {
+        self.join(" OR ", rhs, 1)
+    }
+
+    /// `self XOR rhs`
+    pub fn xor(self, rhs: Expr) -> Expr {
+        self.join(" XOR ", rhs, 2)
+    }
+
+    /// `NOT self`
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Expr {
+        let inner = self.wrap(4);
+        Expr { text: format!("NOT {}", inner.text), params: inner.params, prec: 4 }
+    }
+
+    /// `self AS alias`, for `RETURN` items.
+    pub fn as_(self, alias: &str) -> Expr {
+        let inner = self.wrap(1);
+        Expr { text: format!("{} AS {}", inner.text, escape(alias)), params: inner.params, prec: 0 }
+    }
+
+    /// `self DESC`, for `ORDER BY`.
+    pub fn desc(self) -> Expr {
+        let inner = self.wrap(1);
+        Expr { text: format!("{} DESC", inner.text), params: inner.params, prec: 0 }
+    }
+
+    /// The expression text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn postfix(self, op: &str) -> Expr {
+        let inner = self.wrap(6);
+        Expr { text: format!("{}{op}", inner.text), params: inner.params, prec: 5 }
+    }
+
+    /// `self op rhs` at binding strength `prec`, merging parameters.
+    fn join(self, op: &str, rhs: Expr, prec: u8) -> Expr {
+        let Expr { text, params, .. } = self.wrap(prec).concat(op, rhs.wrap(prec + 1));
+        Expr { text, params, prec }
+    }
+
+    /// `self` and `rhs` separated by `sep`, with no parenthesizing.
+    fn concat(self, sep: &str, rhs: Expr) -> Expr {
+        let mut params = self.params;
+        let rhs_text = builder::merge_params(&mut params, rhs.text, rhs.params);
+        Expr { text: format!("{}{sep}{rhs_text}", self.text), params, prec: 0 }
+    }
+
+    /// Parenthesize unless this binds at least as tightly as `prec`.
+    fn wrap(self, prec: u8) -> Expr {
+        if self.prec >= prec {
+            self
+        } else {
+            Expr { text: format!("({})", self.text), params: self.params, prec: 9 }
+        }
+    }
+}
+
+impl From<Expr> for Fragment {
+    fn from(expr: Expr) -> Self {
+        expr.params
+            .into_iter()
+            .fold(Fragment::new(expr.text), |f, (k, v)| f.param(k, v))
+    }
+}
+
+/// A finished `MATCH ... RETURN` statement.
+#[derive(Debug, Clone)]
+pub struct Statement {
+    builder: CyphrQueryBuilder,
+}
+
+impl Statement {
+    /// Add a sort key, e.g. `u.prop("name").desc()`.
+    pub fn order_by(self, expr: Expr) -> Self {
+        Statement { builder: self.builder.order_by(expr) }
+    }
+
+    /// Skip the first `n` rows, bound as `$skip`.
+    pub fn skip(self, n: i64) -> Self {
+        Statement { builder: self.builder.skip(n) }
+    }
+
+    /// Return at most `n` rows, bound as `$limit`.
+    pub fn limit(self, n: i64) -> Self {
+        Statement { builder: self.builder.limit(n) }
+    }
+
+    /// Build the [`CyphrQuery`] with every parameter bound.
+    pub fn query(self) -> CyphrQuery {
+        self.builder.build()
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.builder.clone().build().text())
+    }
+}
+
+impl From<Statement> for CyphrQuery {
+    fn from(stmt: Statement) -> Self {
+        stmt.query()
+    }
+}
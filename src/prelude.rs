@@ -5,17 +5,22 @@
 //! ```
 //!
 //! This imports all derive macros (`CyphrNode`, `CyphrRelation`, `FromCyphr`,
-//! `ToCyphrParams`), both function-like macros (`cypher!`, `cypher_query!`),
-//! the core traits (`CyphrNodeTrait`, `CyphrRelationTrait`, `FromCyphrTrait`,
-//! `FromCyphrValue`, `IntoCyphrValue`, `ToCyphrParamsTrait`), the error type,
-//! spatial/binary/path wrapper types, and [`CyphrStream`].
+//! `ToCyphrParams`, `CyphrQueryDef`), the function-like macros (`cypher!`,
+//! `cypher_query!`, `include_cypher!`), the core traits (`CyphrNodeTrait`,
+//! `CyphrRelationTrait`, `FromCyphrTrait`, `FromCyphrValue`, `HasId`,
+//! `IntoCyphrValue`, `ToCyphrParamsTrait`, `CyphrQueryDefTrait`), the error type,
+//! spatial/binary/path wrapper types, [`GraphNode`], [`PathDirection`], [`Triple`], [`Subgraph`], [`CyphrRow`], [`CypherIdent`],
+//! [`RelationOps`] and [`CyphrStream`].
 
-pub use crate::{cypher, cypher_query, CyphrNode, CyphrRelation, FromCyphr, ToCyphrParams};
+pub use crate::{cypher, cypher_query, include_cypher, CyphrNode, CyphrQueryDef, CyphrRelation, FromCyphr, ToCyphrParams};
 pub use cyphr_core::traits::{
     CyphrNode as CyphrNodeTrait, CyphrRelation as CyphrRelationTrait,
-    FromCyphr as FromCyphrTrait, FromCyphrValue,
+    FromCyphr as FromCyphrTrait, FromCyphrValue, HasId,
     IntoCyphrValue, ToCyphrParams as ToCyphrParamsTrait,
 };
 pub use cyphr_core::CyphrError;
-pub use cyphr_core::{Point2D, Point3D, CyphrBytes, CyphrPath};
+pub use crate::query::CyphrQueryDef as CyphrQueryDefTrait;
+pub use cyphr_core::{Point2D, Point3D, CyphrBytes, CyphrPath, CyphrRow, GraphNode, PathDirection, Subgraph, Triple};
+pub use crate::ident::CypherIdent;
+pub use crate::relation::RelationOps;
 pub use crate::stream::CyphrStream;
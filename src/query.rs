@@ -1,8 +1,12 @@
 
-use neo4rs::{Graph, Query, Txn, BoltType as Value};
-use cyphr_core::traits::{FromCyphr, ToCyphrParams};
+use neo4rs::{Graph, Query, Row, Txn, BoltType as Value};
+use cyphr_core::record::{self, CyphrRow};
+use cyphr_core::traits::{FromCyphr, FromCyphrValue, ToCyphrParams};
 use cyphr_core::error::CyphrError;
-use crate::stream::CyphrStream;
+use crate::builder::CyphrQueryBuilder;
+use crate::ident::CypherIdent;
+use crate::lexer;
+use crate::stream::{self, CyphrStream};
 
 /// A typed query wrapper around [`neo4rs::Query`].
 ///
@@ -27,15 +31,105 @@ use crate::stream::CyphrStream;
 /// let name = "Alice";
 /// let query = cypher_query! { MATCH (u:User {name: $name}) RETURN u };
 /// ```
+///
+/// In debug builds every `fetch_*` method runs [`validate`](Self::validate)
+/// before sending the query, so unbound or unused parameters fail fast.
+/// [`checked`](Self::checked) keeps the check in release builds and
+/// [`unchecked`](Self::unchecked) turns it off.
 pub struct CyphrQuery {
-    inner: Query,
+    text: String,
+    params: Vec<(String, Value)>,
+    checked: bool,
 }
 
 impl CyphrQuery {
     /// Create a new query from a Cypher string.
     pub fn new(query: impl Into<String>) -> Self {
-        let q: String = query.into();
-        Self { inner: neo4rs::query(&q) }
+        let text: String = query.into();
+        Self { text, params: Vec::new(), checked: cfg!(debug_assertions) }
+    }
+
+    /// Start composing a query from clauses and optional conditions; see
+    /// [`CyphrQueryBuilder`].
+    pub fn builder() -> CyphrQueryBuilder {
+        CyphrQueryBuilder::new()
+    }
+
+    /// The Cypher text of this query.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Names of all parameters bound so far, in binding order.
+    pub fn param_keys(&self) -> impl Iterator<Item = &str> {
+        self.params.iter().map(|(k, _)| k.as_str())
+    }
+
+    /// The value bound to `key`, if any.
+    pub fn param_value(&self, key: &str) -> Option<&Value> {
+        self.params.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Check that every `$placeholder` in the text is bound and every bound
+    /// parameter is used.
+    ///
+    /// Placeholders inside string literals, quoted identifiers and comments
+    /// are ignored. Returns [`CyphrError::ParamMismatch`] listing the
+    /// offending names.
+    ///
+    /// ```rust
+    /// # use cyphr::query::CyphrQuery;
+    /// let q = CyphrQuery::new("MATCH (u:User {name: $name}) RETURN u")
+    ///     .param("nmae", "Alice");
+    /// let err = q.validate().unwrap_err();
+    /// assert_eq!(err.to_string(), "parameter mismatch: missing [name], unused [nmae]");
+    /// ```
+    pub fn validate(&self) -> Result<(), CyphrError> {
+        let used = lexer::param_names(&self.text);
+        let missing: Vec<String> = used.iter()
+            .filter(|name| !self.param_keys().any(|k| k == name.as_str()))
+            .cloned()
+            .collect();
+        let unused: Vec<String> = self.param_keys()
+            .filter(|key| !used.iter().any(|name| name == key))
+            .map(str::to_owned)
+            .collect();
+        if missing.is_empty() && unused.is_empty() {
+            Ok(())
+        } else {
+            Err(CyphrError::ParamMismatch { missing, unused })
+        }
+    }
+
+    /// Run [`validate`](Self::validate) before executing, failing with
+    /// [`CyphrError::ParamMismatch`] instead of sending a query whose
+    /// parameters don't line up. This is the default in debug builds; call
+    /// it to keep the check in release builds too.
+    pub fn checked(mut self) -> Self {
+        self.checked = true;
+        self
+    }
+
+    /// Skip the check before executing, for queries bound with
+    /// [`params_from`](Self::params_from) that deliberately use only some
+    /// of the struct's fields.
+    ///
+    /// ```rust
+    /// # use cyphr::query::CyphrQuery;
+    /// let q = CyphrQuery::new("MATCH (u:User {id: $id}) RETURN u")
+    ///     .param("id", 1)
+    ///     .param("name", "Alice")
+    ///     .unchecked();
+    /// assert!(!q.is_checked());
+    /// ```
+    pub fn unchecked(mut self) -> Self {
+        self.checked = false;
+        self
+    }
+
+    /// Whether parameters are validated before executing.
+    pub fn is_checked(&self) -> bool {
+        self.checked
     }
 
     /// Bind a named parameter. Accepts any type that converts to `BoltType`.
@@ -46,8 +140,7 @@ impl CyphrQuery {
     ///     .param("age", 30_i64);
     /// ```
     pub fn param(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
-        let k: String = key.into();
-        self.inner = self.inner.param(&k, value.into());
+        self.set_param(key.into(), value.into());
         self
     }
 
@@ -65,25 +158,106 @@ impl CyphrQuery {
     ///     .params_from(params);
     /// ```
     pub fn params_from(mut self, source: impl ToCyphrParams) -> Self {
-        for (k, v) in source.to_params() {
-            self.inner = self.inner.param(&k, v);
+        let mut params: Vec<(String, Value)> = source.to_params().into_iter().collect();
+        params.sort_by(|a, b| a.0.cmp(&b.0));
+        for (k, v) in params {
+            self.set_param(k, v);
         }
         self
     }
 
-    /// Execute against a [`Graph`] and return exactly one row, mapped to `T`.
+    /// Splice a label, relationship type or property key into every
+    /// `{slot}` in the text.
+    ///
+    /// Cypher cannot take these as parameters, so they have to be part of
+    /// the text; [`CypherIdent`] guarantees the value is escaped. Slots
+    /// inside string literals, quoted identifiers and comments are left
+    /// alone.
+    ///
+    /// ```rust
+    /// # use cyphr::query::CyphrQuery;
+    /// use cyphr::ident::CypherIdent;
+    ///
+    /// let rel = CypherIdent::new("LIKES").unwrap();
+    /// let key = CypherIdent::new("last seen").unwrap();
+    /// let q = CyphrQuery::new("MATCH (a)-[r:{rel}]->(b) RETURN r.{key}")
+    ///     .ident("rel", &rel)
+    ///     .ident("key", &key);
+    /// assert_eq!(q.text(), "MATCH (a)-[r:LIKES]->(b) RETURN r.`last seen`");
+    /// ```
+    pub fn ident(mut self, slot: &str, ident: &CypherIdent) -> Self {
+        self.text = lexer::fill_slot(&self.text, slot, &ident.to_string());
+        self
+    }
+
+    /// Validate `name` with [`CypherIdent::new`] and splice it into every
+    /// `{slot}`; see [`ident`](Self::ident).
+    pub fn try_ident(self, slot: &str, name: &str) -> Result<Self, CyphrError> {
+        let ident = CypherIdent::new(name)?;
+        Ok(self.ident(slot, &ident))
+    }
+
+    fn set_param(&mut self, key: String, value: Value) {
+        match self.params.iter_mut().find(|(k, _)| *k == key) {
+            Some(slot) => slot.1 = value,
+            None => self.params.push((key, value)),
+        }
+    }
+
+    /// Build the driver query, validating parameters first unless
+    /// [`unchecked`](Self::unchecked).
+    fn into_query(self) -> Result<Query, CyphrError> {
+        if self.checked {
+            self.validate()?;
+        }
+        Ok(neo4rs::query(&self.text).params(self.params))
+    }
+
+    /// Execute against a [`Graph`] and return the first row, mapped to `T`.
     ///
-    /// Returns [`CyphrError::MissingField`] if the result set is empty.
+    /// Returns [`CyphrError::NoRows`] if the result set is empty. Any further
+    /// rows are ignored; use [`fetch_exactly_one`](Self::fetch_exactly_one)
+    /// when the result must be unique.
     pub async fn fetch_one<T: FromCyphr>(self, graph: &Graph) -> Result<T, CyphrError> {
-        let mut stream = graph.execute(self.inner).await?;
-        let row = stream.next().await?
-            .ok_or_else(|| CyphrError::missing_field("row", "fetch_one"))?;
+        let mut stream = graph.execute(self.into_query()?).await?;
+        let row = stream.next().await?.ok_or(CyphrError::NoRows)?;
         T::from_record(&row)
     }
 
+    /// Execute against a [`Graph`] and return the only row, mapped to `T`.
+    ///
+    /// The whole result set is drained. Returns [`CyphrError::NoRows`] if it
+    /// is empty and [`CyphrError::TooManyRows`] if it has more than one row.
+    ///
+    /// ```rust,no_run
+    /// # use cyphr::query::CyphrQuery;
+    /// # use cyphr::CyphrError;
+    /// # #[derive(cyphr::FromCyphr)] struct UserRow { name: String }
+    /// # async fn example(graph: &neo4rs::Graph) -> Result<(), CyphrError> {
+    /// let user: UserRow = CyphrQuery::new("MATCH (u:User {email: $email}) RETURN u.name AS name")
+    ///     .param("email", "alice@example.com")
+    ///     .fetch_exactly_one(graph)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_exactly_one<T: FromCyphr>(self, graph: &Graph) -> Result<T, CyphrError> {
+        self.fetch_at_most_one(graph).await?.ok_or(CyphrError::NoRows)
+    }
+
+    /// Execute against a [`Graph`] and return zero or one row, mapped to `T`.
+    ///
+    /// The whole result set is drained. Returns [`CyphrError::TooManyRows`]
+    /// if it has more than one row.
+    pub async fn fetch_at_most_one<T: FromCyphr>(self, graph: &Graph) -> Result<Option<T>, CyphrError> {
+        use futures::TryStreamExt;
+        let detached = graph.execute(self.into_query()?).await?;
+        stream::at_most_one(detached.into_stream().into_stream()).await
+    }
+
     /// Execute against a [`Graph`] and collect all rows into `Vec<T>`.
     pub async fn fetch_all<T: FromCyphr>(self, graph: &Graph) -> Result<Vec<T>, CyphrError> {
-        let mut stream = graph.execute(self.inner).await?;
+        let mut stream = graph.execute(self.into_query()?).await?;
         let mut out = Vec::new();
         while let Some(row) = stream.next().await? {
             out.push(T::from_record(&row)?);
@@ -109,7 +283,7 @@ impl CyphrQuery {
     /// # }
     /// ```
     pub async fn fetch_optional<T: FromCyphr>(self, graph: &Graph) -> Result<Option<T>, CyphrError> {
-        let mut stream = graph.execute(self.inner).await?;
+        let mut stream = graph.execute(self.into_query()?).await?;
         match stream.next().await? {
             Some(row) => Ok(Some(T::from_record(&row)?)),
             None => Ok(None),
@@ -121,14 +295,14 @@ impl CyphrQuery {
     /// Like [`fetch_optional`](Self::fetch_optional) but runs inside an existing
     /// transaction.
     pub async fn fetch_optional_in<T: FromCyphr>(self, txn: &mut Txn) -> Result<Option<T>, CyphrError> {
-        let mut stream = txn.execute(self.inner).await?;
+        let mut stream = txn.execute(self.into_query()?).await?;
         match stream.next(txn.handle()).await? {
             Some(row) => Ok(Some(T::from_record(&row)?)),
             None => Ok(None),
         }
     }
 
-    /// Execute within a [`Txn`] and return exactly one row, mapped to `T`.
+    /// Execute within a [`Txn`] and return the first row, mapped to `T`.
     ///
     /// Like [`fetch_one`](Self::fetch_one) but runs inside an existing
     /// transaction. The stream is driven through `txn.handle()`.
@@ -147,18 +321,35 @@ impl CyphrQuery {
     /// # }
     /// ```
     pub async fn fetch_one_in<T: FromCyphr>(self, txn: &mut Txn) -> Result<T, CyphrError> {
-        let mut stream = txn.execute(self.inner).await?;
-        let row = stream.next(txn.handle()).await?
-            .ok_or_else(|| CyphrError::missing_field("row", "fetch_one_in"))?;
+        let mut stream = txn.execute(self.into_query()?).await?;
+        let row = stream.next(txn.handle()).await?.ok_or(CyphrError::NoRows)?;
         T::from_record(&row)
     }
 
+    /// Execute within a [`Txn`] and return the only row, mapped to `T`.
+    ///
+    /// Like [`fetch_exactly_one`](Self::fetch_exactly_one) but runs inside an
+    /// existing transaction.
+    pub async fn fetch_exactly_one_in<T: FromCyphr>(self, txn: &mut Txn) -> Result<T, CyphrError> {
+        self.fetch_at_most_one_in(txn).await?.ok_or(CyphrError::NoRows)
+    }
+
+    /// Execute within a [`Txn`] and return zero or one row, mapped to `T`.
+    ///
+    /// Like [`fetch_at_most_one`](Self::fetch_at_most_one) but runs inside an
+    /// existing transaction.
+    pub async fn fetch_at_most_one_in<T: FromCyphr>(self, txn: &mut Txn) -> Result<Option<T>, CyphrError> {
+        use futures::TryStreamExt;
+        let rows = txn.execute(self.into_query()?).await?;
+        stream::at_most_one(rows.into_stream(txn.handle()).into_stream()).await
+    }
+
     /// Execute within a [`Txn`] and collect all rows into `Vec<T>`.
     ///
     /// Like [`fetch_all`](Self::fetch_all) but runs inside an existing
     /// transaction.
     pub async fn fetch_all_in<T: FromCyphr>(self, txn: &mut Txn) -> Result<Vec<T>, CyphrError> {
-        let mut stream = txn.execute(self.inner).await?;
+        let mut stream = txn.execute(self.into_query()?).await?;
         let mut out = Vec::new();
         while let Some(row) = stream.next(txn.handle()).await? {
             out.push(T::from_record(&row)?);
@@ -166,6 +357,39 @@ impl CyphrQuery {
         Ok(out)
     }
 
+    /// Execute against a [`Graph`] and collect all rows as dynamically-typed
+    /// [`CyphrRow`]s.
+    ///
+    /// For ad-hoc queries where a [`FromCyphr`] struct is not worth
+    /// defining. Conversions through [`CyphrRow::get`] report the column name
+    /// on failure.
+    ///
+    /// ```rust,no_run
+    /// # use cyphr::query::CyphrQuery;
+    /// # use cyphr::CyphrError;
+    /// # async fn example(graph: &neo4rs::Graph) -> Result<(), CyphrError> {
+    /// let rows = CyphrQuery::new("MATCH (u:User) RETURN u.name AS name, u.age AS age")
+    ///     .fetch_rows(graph)
+    ///     .await?;
+    /// for row in &rows {
+    ///     let name: String = row.get("name")?;
+    ///     let age: Option<i64> = row.try_get("age")?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_rows(self, graph: &Graph) -> Result<Vec<CyphrRow>, CyphrError> {
+        self.fetch_all(graph).await
+    }
+
+    /// Execute within a [`Txn`] and collect all rows as [`CyphrRow`]s.
+    ///
+    /// Like [`fetch_rows`](Self::fetch_rows) but runs inside an existing
+    /// transaction.
+    pub async fn fetch_rows_in(self, txn: &mut Txn) -> Result<Vec<CyphrRow>, CyphrError> {
+        self.fetch_all_in(txn).await
+    }
+
     /// Execute against a [`Graph`] and return a streaming iterator of `T`.
     ///
     /// Unlike [`fetch_all`](Self::fetch_all), rows are converted one at a
@@ -191,10 +415,136 @@ impl CyphrQuery {
     /// ```
     pub async fn fetch_stream<T: FromCyphr>(self, graph: &Graph) -> Result<CyphrStream<T>, CyphrError> {
         use futures::TryStreamExt;
-        let detached = graph.execute(self.inner).await?;
+        let detached = graph.execute(self.into_query()?).await?;
         let stream = detached.into_stream().into_stream();
         Ok(CyphrStream::new(Box::pin(stream)))
     }
+
+    /// Execute against a [`Graph`] and map the single column of the first
+    /// row directly through [`FromCyphrValue`].
+    ///
+    /// Intended for queries like `RETURN count(*)` where a dedicated
+    /// [`FromCyphr`] struct would be noise. Returns [`CyphrError::NoRows`]
+    /// if the result set is empty and [`CyphrError::Mapping`] if the row has
+    /// more than one column.
+    ///
+    /// ```rust,no_run
+    /// # use cyphr::query::CyphrQuery;
+    /// # use cyphr::CyphrError;
+    /// # async fn example(graph: &neo4rs::Graph) -> Result<(), CyphrError> {
+    /// let total: i64 = CyphrQuery::new("MATCH (u:User) RETURN count(u)")
+    ///     .fetch_scalar(graph)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_scalar<V: FromCyphrValue>(self, graph: &Graph) -> Result<V, CyphrError> {
+        let mut stream = graph.execute(self.into_query()?).await?;
+        let row = stream.next().await?.ok_or(CyphrError::NoRows)?;
+        scalar_from_row(&row)
+    }
+
+    /// Execute against a [`Graph`] and map the single column of the first
+    /// row, or return `Ok(None)` if the result set is empty.
+    ///
+    /// See [`fetch_scalar`](Self::fetch_scalar).
+    pub async fn fetch_optional_scalar<V: FromCyphrValue>(self, graph: &Graph) -> Result<Option<V>, CyphrError> {
+        let mut stream = graph.execute(self.into_query()?).await?;
+        match stream.next().await? {
+            Some(row) => Ok(Some(scalar_from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Execute against a [`Graph`] and collect the column `name` from every
+    /// row into `Vec<V>`.
+    ///
+    /// Other columns in the row are ignored. Returns
+    /// [`CyphrError::MissingField`] if a row has no column called `name`.
+    ///
+    /// ```rust,no_run
+    /// # use cyphr::query::CyphrQuery;
+    /// # use cyphr::CyphrError;
+    /// # async fn example(graph: &neo4rs::Graph) -> Result<(), CyphrError> {
+    /// let emails: Vec<String> = CyphrQuery::new("MATCH (u:User) RETURN u.email AS email")
+    ///     .fetch_column("email", graph)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_column<V: FromCyphrValue>(self, name: &str, graph: &Graph) -> Result<Vec<V>, CyphrError> {
+        let mut stream = graph.execute(self.into_query()?).await?;
+        let mut out = Vec::new();
+        while let Some(row) = stream.next().await? {
+            out.push(column_from_row(&row, name)?);
+        }
+        Ok(out)
+    }
+
+    /// Execute within a [`Txn`] and map the single column of the first row.
+    ///
+    /// Like [`fetch_scalar`](Self::fetch_scalar) but runs inside an existing
+    /// transaction.
+    pub async fn fetch_scalar_in<V: FromCyphrValue>(self, txn: &mut Txn) -> Result<V, CyphrError> {
+        let mut stream = txn.execute(self.into_query()?).await?;
+        let row = stream.next(txn.handle()).await?.ok_or(CyphrError::NoRows)?;
+        scalar_from_row(&row)
+    }
+
+    /// Execute within a [`Txn`] and map the single column of the first row,
+    /// or return `Ok(None)` if the result set is empty.
+    ///
+    /// Like [`fetch_optional_scalar`](Self::fetch_optional_scalar) but runs
+    /// inside an existing transaction.
+    pub async fn fetch_optional_scalar_in<V: FromCyphrValue>(self, txn: &mut Txn) -> Result<Option<V>, CyphrError> {
+        let mut stream = txn.execute(self.into_query()?).await?;
+        match stream.next(txn.handle()).await? {
+            Some(row) => Ok(Some(scalar_from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Execute within a [`Txn`] and collect the column `name` from every row.
+    ///
+    /// Like [`fetch_column`](Self::fetch_column) but runs inside an existing
+    /// transaction.
+    pub async fn fetch_column_in<V: FromCyphrValue>(self, name: &str, txn: &mut Txn) -> Result<Vec<V>, CyphrError> {
+        let mut stream = txn.execute(self.into_query()?).await?;
+        let mut out = Vec::new();
+        while let Some(row) = stream.next(txn.handle()).await? {
+            out.push(column_from_row(&row, name)?);
+        }
+        Ok(out)
+    }
+}
+
+/// A query as a type: fixed Cypher text, parameters taken from the
+/// implementing struct's fields, and a known row type.
+///
+/// Usually implemented with `#[derive(CyphrQueryDef)]`, which checks at
+/// compile time that the fields match the query's `$placeholders` and adds
+/// inherent `fetch_*` methods returning [`Row`](Self::Row).
+///
+/// ```rust,ignore
+/// #[derive(CyphrQueryDef)]
+/// #[cyphr(query = "MATCH (u:User {id: $id}) RETURN u", returns = "UserRow")]
+/// struct FindUser {
+///     id: i64,
+/// }
+///
+/// let user: UserRow = FindUser { id: 7 }.fetch_one(&graph).await?;
+/// ```
+pub trait CyphrQueryDef: ToCyphrParams + Sized {
+    /// The type each result row maps to.
+    type Row: FromCyphr;
+
+    /// The Cypher text.
+    const QUERY: &'static str;
+
+    /// Build the [`CyphrQuery`] with every field bound as a parameter.
+    fn query(self) -> CyphrQuery {
+        CyphrQuery::new(Self::QUERY).params_from(self)
+    }
 }
 
 /// Convenience constructor — equivalent to [`CyphrQuery::new`].
@@ -206,3 +556,16 @@ impl CyphrQuery {
 pub fn query(q: impl Into<String>) -> CyphrQuery {
     CyphrQuery::new(q)
 }
+
+/// Map the only column of `row` through [`FromCyphrValue`].
+fn scalar_from_row<V: FromCyphrValue>(row: &Row) -> Result<V, CyphrError> {
+    let (key, v) = record::single_entry(row)?;
+    V::from_value(v).map_err(|e| e.with_context(format!("column '{key}'")))
+}
+
+/// Map the column `name` of `row` through [`FromCyphrValue`].
+fn column_from_row<V: FromCyphrValue>(row: &Row, name: &str) -> Result<V, CyphrError> {
+    let v = record::get_value(row, name)
+        .ok_or_else(|| CyphrError::missing_field(name, "fetch_column"))?;
+    V::from_value(v).map_err(|e| e.with_context(format!("column '{name}'")))
+}
@@ -0,0 +1,178 @@
+//! Creating, removing and checking relationships between nodes by id.
+//!
+//! [`RelationOps`] is implemented for every [`CyphrRelation`]. Its methods
+//! build the `MATCH (a:From {id: $from}), (b:To {id: $to}) ...` boilerplate
+//! from the relationship's `TYPE`, the endpoint labels and their
+//! `#[cyphr(id)]` fields, and run it on any [`Executor`]:
+//!
+//! ```rust,no_run
+//! use cyphr::prelude::*;
+//!
+//! #[derive(CyphrNode)]
+//! struct User {
+//!     #[cyphr(id)]
+//!     id: i64,
+//! }
+//!
+//! #[derive(CyphrRelation, ToCyphrParams)]
+//! #[cyphr(type = "FOLLOWS", from_type = User, to_type = User)]
+//! struct Follows {
+//!     since: i64,
+//! }
+//!
+//! # async fn example(graph: &neo4rs::Graph) -> Result<(), CyphrError> {
+//! Follows { since: 2024 }.connect::<User, User>(graph, 1, 2).await?;
+//! assert!(Follows::exists_between::<User, User>(graph, 1, 2).await?);
+//! Follows::disconnect::<User, User>(graph, 1, 2).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Endpoint types are checked at compile time: they must implement [`HasId`]
+//! and, when the relationship declares `from_type` / `to_type`, be those
+//! types ([`RelFrom`] / [`RelTo`]).
+//!
+//! ```compile_fail
+//! use cyphr::prelude::*;
+//!
+//! #[derive(CyphrNode)]
+//! struct User {
+//!     #[cyphr(id)]
+//!     id: i64,
+//! }
+//!
+//! #[derive(CyphrNode)]
+//! struct Post {
+//!     #[cyphr(id)]
+//!     id: i64,
+//! }
+//!
+//! #[derive(CyphrRelation, ToCyphrParams)]
+//! #[cyphr(type = "FOLLOWS", from_type = User, to_type = User)]
+//! struct Follows {}
+//!
+//! # fn graph() -> &'static neo4rs::Graph { unimplemented!() }
+//! // error: `Follows` relationships don't end at `Post` nodes
+//! let connected = Follows {}.connect::<User, Post>(graph(), 1, 2);
+//! ```
+
+use std::future::Future;
+
+use neo4rs::BoltType as Value;
+use cyphr_core::error::CyphrError;
+use cyphr_core::traits::{CyphrRelation, HasId, RelFrom, RelTo, ToCyphrParams};
+
+use crate::executor::Executor;
+use crate::ident::escape;
+use crate::query::CyphrQuery;
+use crate::repository::{label, map};
+
+/// Relationship operations between nodes identified by id.
+///
+/// Each `A` / `B` pair is checked at compile time against the
+/// relationship's `from_type` / `to_type`.
+pub trait RelationOps: CyphrRelation {
+    /// Create a relationship with `self`'s properties from the `A` with id
+    /// `from` to the `B` with id `to`. Returns `false` if either node is
+    /// missing.
+    ///
+    /// Always creates a new relationship; use
+    /// [`merge_between`](Self::merge_between) to reuse an existing one.
+    fn connect<A: HasId, B: HasId>(
+        self,
+        exec: impl Executor,
+        from: impl Into<Value>,
+        to: impl Into<Value>,
+    ) -> impl Future<Output = Result<bool, CyphrError>> + Send
+    where
+        Self: ToCyphrParams + RelFrom<A> + RelTo<B>,
+    {
+        let query = endpoints::<A, Self, B>("CREATE (a)-[r:{type}]->(b) SET r = $props RETURN count(r) AS n")
+            .param("from", from)
+            .param("to", to)
+            .param("props", props(self));
+        async move { Ok(count(exec, query).await? > 0) }
+    }
+
+    /// Like [`connect`](Self::connect), but `MERGE`s so at most one such
+    /// relationship exists, then updates its properties from `self`.
+    fn merge_between<A: HasId, B: HasId>(
+        self,
+        exec: impl Executor,
+        from: impl Into<Value>,
+        to: impl Into<Value>,
+    ) -> impl Future<Output = Result<bool, CyphrError>> + Send
+    where
+        Self: ToCyphrParams + RelFrom<A> + RelTo<B>,
+    {
+        let query = endpoints::<A, Self, B>("MERGE (a)-[r:{type}]->(b) SET r += $props RETURN count(r) AS n")
+            .param("from", from)
+            .param("to", to)
+            .param("props", props(self));
+        async move { Ok(count(exec, query).await? > 0) }
+    }
+
+    /// Delete every relationship of this type from `from` to `to` and return
+    /// how many there were.
+    fn disconnect<A: HasId, B: HasId>(
+        exec: impl Executor,
+        from: impl Into<Value>,
+        to: impl Into<Value>,
+    ) -> impl Future<Output = Result<i64, CyphrError>> + Send
+    where
+        Self: RelFrom<A> + RelTo<B>,
+    {
+        let query = between::<A, Self, B>("DELETE r RETURN count(*) AS n").param("from", from).param("to", to);
+        async move { count(exec, query).await }
+    }
+
+    /// Whether a relationship of this type runs from `from` to `to`.
+    fn exists_between<A: HasId, B: HasId>(
+        exec: impl Executor,
+        from: impl Into<Value>,
+        to: impl Into<Value>,
+    ) -> impl Future<Output = Result<bool, CyphrError>> + Send
+    where
+        Self: RelFrom<A> + RelTo<B>,
+    {
+        let query = between::<A, Self, B>("RETURN count(r) AS n").param("from", from).param("to", to);
+        async move { Ok(count(exec, query).await? > 0) }
+    }
+}
+
+impl<R: CyphrRelation> RelationOps for R {}
+
+/// `MATCH (a:A {id: $from}), (b:B {id: $to}) {tail}`, with `{type}` in
+/// `tail` replaced by `R::TYPE`.
+fn endpoints<A: HasId, R: CyphrRelation, B: HasId>(tail: &str) -> CyphrQuery {
+    CyphrQuery::new(format!(
+        "MATCH (a:{} {{{}: $from}}), (b:{} {{{}: $to}}) {}",
+        label::<A>(),
+        escape(A::ID_KEY),
+        label::<B>(),
+        escape(B::ID_KEY),
+        tail.replace("{type}", &escape(R::TYPE)),
+    ))
+}
+
+/// `MATCH (a:A {id: $from})-[r:R]->(b:B {id: $to}) {tail}`.
+fn between<A: HasId, R: CyphrRelation, B: HasId>(tail: &str) -> CyphrQuery {
+    CyphrQuery::new(format!(
+        "MATCH (a:{} {{{}: $from}})-[r:{}]->(b:{} {{{}: $to}}) {tail}",
+        label::<A>(),
+        escape(A::ID_KEY),
+        escape(R::TYPE),
+        label::<B>(),
+        escape(B::ID_KEY),
+    ))
+}
+
+fn props(rel: impl ToCyphrParams) -> Value {
+    map(rel.to_params().into_iter().collect())
+}
+
+/// Run `query` and read its `n` column.
+async fn count(exec: impl Executor, query: CyphrQuery) -> Result<i64, CyphrError> {
+    let rows = exec.fetch_rows(query).await?;
+    rows.first().ok_or(CyphrError::NoRows)?.get("n")
+}
@@ -0,0 +1,218 @@
+//! Generic CRUD for derived node types.
+//!
+//! [`NodeRepository<T>`] covers the lookups every label needs: by id, by any
+//! property, paged listing, counting, upsert and delete. Every method takes
+//! an [`Executor`], so the same repository works on a `&Graph` or inside a
+//! `&mut Txn`.
+//!
+//! ```rust,no_run
+//! use cyphr::prelude::*;
+//! use cyphr::repository::{NodeRepository, Page};
+//!
+//! #[derive(CyphrNode, ToCyphrParams)]
+//! #[cyphr(label = "User")]
+//! struct User {
+//!     #[cyphr(id)]
+//!     id: i64,
+//!     email: String,
+//! }
+//!
+//! # async fn example(graph: &neo4rs::Graph) -> Result<(), CyphrError> {
+//! let users = NodeRepository::<User>::new();
+//!
+//! users.save(graph, User { id: 1, email: "alice@example.com".into() }).await?;
+//! let alice = users.find_by_id(graph, 1).await?;
+//! let same = users.find_by(graph, user_props::EMAIL, "alice@example.com").await?;
+//! let first_page = users.find_all(graph, Page::new(0, 20)).await?;
+//!
+//! let mut txn = graph.start_txn().await?;
+//! users.detach_delete(&mut txn, 1).await?;
+//! txn.commit().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Lookups by id and deletes need a `#[cyphr(id)]` field; without one they
+//! return [`CyphrError::Mapping`]. [`save`](NodeRepository::save) needs
+//! [`HasId`], so its id value can be bound, and writes the properties from
+//! the type's `ToCyphrParams` impl, which leaves out the id field.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use neo4rs::BoltType as Value;
+use cyphr_core::error::CyphrError;
+use cyphr_core::traits::{CyphrNode, FromCyphrValue, HasId, ToCyphrParams};
+
+use crate::executor::Executor;
+use crate::ident::escape;
+use crate::query::CyphrQuery;
+
+/// One page of a listing: `size` nodes starting at `number * size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    pub number: u32,
+    pub size: u32,
+}
+
+impl Page {
+    /// Page `number` (from zero) of `size` nodes.
+    pub fn new(number: u32, size: u32) -> Self {
+        Self { number, size }
+    }
+
+    /// The page after this one.
+    pub fn next(self) -> Self {
+        Self { number: self.number + 1, ..self }
+    }
+
+    fn skip(self) -> i64 {
+        i64::from(self.number) * i64::from(self.size)
+    }
+}
+
+/// CRUD queries for nodes of type `T`.
+pub struct NodeRepository<T> {
+    _node: PhantomData<fn() -> T>,
+}
+
+impl<T> NodeRepository<T> {
+    /// A repository for `T`.
+    pub fn new() -> Self {
+        Self { _node: PhantomData }
+    }
+}
+
+impl<T> Default for NodeRepository<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for NodeRepository<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for NodeRepository<T> {}
+
+impl<T> fmt::Debug for NodeRepository<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeRepository").field("node", &std::any::type_name::<T>()).finish()
+    }
+}
+
+impl<T: CyphrNode + FromCyphrValue> NodeRepository<T> {
+    /// The node whose id is `id`, if any.
+    pub async fn find_by_id<E: Executor>(&self, exec: E, id: impl Into<Value>) -> Result<Option<T>, CyphrError> {
+        let query = CyphrQuery::new(format!("MATCH (n:{} {{{}: $id}}) RETURN n", label::<T>(), id_key::<T>()?))
+            .param("id", id);
+        let mut nodes = nodes::<T, E>(exec, query).await?;
+        match nodes.len() {
+            0 | 1 => Ok(nodes.pop()),
+            count => Err(CyphrError::TooManyRows { count }),
+        }
+    }
+
+    /// Every node whose `key` property equals `value`.
+    pub async fn find_by<E: Executor>(&self, exec: E, key: &str, value: impl Into<Value>) -> Result<Vec<T>, CyphrError> {
+        let query = CyphrQuery::new(format!("MATCH (n:{}) WHERE n.{} = $value RETURN n", label::<T>(), escape(key)))
+            .param("value", value);
+        nodes(exec, query).await
+    }
+
+    /// One page of nodes, ordered by id, or by element id if `T` has no
+    /// `#[cyphr(id)]` field.
+    pub async fn find_all<E: Executor>(&self, exec: E, page: Page) -> Result<Vec<T>, CyphrError> {
+        let order = match T::ID {
+            Some(key) => format!("n.{}", escape(key)),
+            None => "elementId(n)".to_owned(),
+        };
+        let query = CyphrQuery::new(format!(
+            "MATCH (n:{}) RETURN n ORDER BY {order} SKIP $skip LIMIT $limit",
+            label::<T>()
+        ))
+        .param("skip", page.skip())
+        .param("limit", i64::from(page.size));
+        nodes(exec, query).await
+    }
+
+    /// The number of nodes with `T`'s label.
+    pub async fn count<E: Executor>(&self, exec: E) -> Result<i64, CyphrError> {
+        let query = CyphrQuery::new(format!("MATCH (n:{}) RETURN count(n) AS count", label::<T>()));
+        let rows = exec.fetch_rows(query).await?;
+        rows.first().ok_or(CyphrError::NoRows)?.get("count")
+    }
+
+    /// Create or update the node with `node`'s id and return it as stored.
+    ///
+    /// `MERGE`s on the id property, then sets every property from
+    /// `ToCyphrParams`; properties bound to null are removed.
+    pub async fn save<E: Executor>(&self, exec: E, node: T) -> Result<T, CyphrError>
+    where
+        T: HasId + ToCyphrParams,
+    {
+        let id = node.id_value();
+        let props: Vec<(String, Value)> = node.to_params().into_iter().collect();
+        let query = CyphrQuery::new(format!(
+            "MERGE (n:{} {{{}: $id}}) SET n += $props RETURN n",
+            label::<T>(),
+            escape(T::ID_KEY)
+        ))
+        .param("id", id)
+        .param("props", map(props));
+        nodes(exec, query).await?.pop().ok_or(CyphrError::NoRows)
+    }
+
+    /// Delete the node with id `id` and return whether it existed.
+    ///
+    /// Fails if the node still has relationships; see
+    /// [`detach_delete`](Self::detach_delete).
+    pub async fn delete<E: Executor>(&self, exec: E, id: impl Into<Value>) -> Result<bool, CyphrError> {
+        self.delete_with(exec, id, "DELETE").await
+    }
+
+    /// Delete the node with id `id` and all its relationships, and return
+    /// whether it existed.
+    pub async fn detach_delete<E: Executor>(&self, exec: E, id: impl Into<Value>) -> Result<bool, CyphrError> {
+        self.delete_with(exec, id, "DETACH DELETE").await
+    }
+
+    async fn delete_with<E: Executor>(&self, exec: E, id: impl Into<Value>, clause: &str) -> Result<bool, CyphrError> {
+        let query = CyphrQuery::new(format!(
+            "MATCH (n:{} {{{}: $id}}) {clause} n RETURN count(*) AS deleted",
+            label::<T>(),
+            id_key::<T>()?
+        ))
+        .param("id", id);
+        let rows = exec.fetch_rows(query).await?;
+        let deleted: i64 = rows.first().ok_or(CyphrError::NoRows)?.get("deleted")?;
+        Ok(deleted > 0)
+    }
+}
+
+pub(crate) fn label<T: CyphrNode>() -> String {
+    escape(T::LABEL).into_owned()
+}
+
+pub(crate) fn id_key<T: CyphrNode>() -> Result<String, CyphrError> {
+    T::ID.map(|key| escape(key).into_owned()).ok_or_else(no_id::<T>)
+}
+
+fn no_id<T: CyphrNode>() -> CyphrError {
+    CyphrError::Mapping(format!("{} has no `#[cyphr(id)]` field", T::LABEL))
+}
+
+/// Run `query` and decode its `n` column.
+async fn nodes<T: FromCyphrValue, E: Executor>(exec: E, query: CyphrQuery) -> Result<Vec<T>, CyphrError> {
+    exec.fetch_rows(query).await?.iter().map(|row| row.get("n")).collect()
+}
+
+pub(crate) fn map(entries: Vec<(String, Value)>) -> Value {
+    let mut map = neo4rs::BoltMap::new();
+    for (key, value) in entries {
+        map.put(key.into(), value);
+    }
+    Value::Map(map)
+}
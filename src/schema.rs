@@ -0,0 +1,399 @@
+//! Installing constraints and indexes declared on derived types.
+//!
+//! Mark fields with `#[cyphr(unique)]`, `#[cyphr(index)]`,
+//! `#[cyphr(required)]` or `#[cyphr(fulltext = "index_name")]`, then pass
+//! each type's [`schema()`](cyphr_core::traits::CyphrNode::schema) to
+//! [`apply`] at start-up:
+//!
+//! ```rust,no_run
+//! use cyphr::prelude::*;
+//!
+//! #[derive(CyphrNode)]
+//! #[cyphr(label = "User")]
+//! struct User {
+//!     #[cyphr(unique)]
+//!     email: String,
+//!     #[cyphr(index, fulltext = "user_search")]
+//!     name: String,
+//!     #[cyphr(fulltext = "user_search")]
+//!     bio: String,
+//! }
+//!
+//! # async fn example(graph: &neo4rs::Graph) -> Result<(), CyphrError> {
+//! cyphr::schema::apply(graph, &[User::schema()]).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Every statement uses `IF NOT EXISTS`, so applying the same definitions
+//! again is a no-op. Existence constraints (`required`) need Neo4j
+//! Enterprise Edition.
+//!
+//! [`diff`] goes the other way: it reads `SHOW CONSTRAINTS`, `SHOW INDEXES`
+//! and `db.schema.nodeTypeProperties()` / `relTypeProperties()` and reports
+//! where the database and the declared types disagree. The comparison
+//! itself is [`compare`], which works on a [`LiveSchema`] built by hand as
+//! easily as one fetched from the server.
+//!
+//! ```rust,no_run
+//! # use cyphr::prelude::*;
+//! # #[derive(CyphrNode)]
+//! # struct User { #[cyphr(unique)] email: String }
+//! # async fn example(graph: &neo4rs::Graph) -> Result<(), CyphrError> {
+//! let report = cyphr::schema::diff(graph, &[User::schema()]).await?;
+//! for issue in report.issues() {
+//!     eprintln!("schema drift: {issue}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use neo4rs::Graph;
+use cyphr_core::error::CyphrError;
+use cyphr_core::ident::unquote_type;
+
+use crate::query::CyphrQuery;
+
+pub use cyphr_core::schema::{PropertyDef, SchemaDef, SchemaItem, SchemaKind, SchemaTarget};
+
+/// Create every constraint and index in `defs` that does not exist yet.
+///
+/// Statements run one at a time, outside any transaction, in the order
+/// given; the first failure is returned.
+pub async fn apply(graph: &Graph, defs: &[SchemaDef]) -> Result<(), CyphrError> {
+    for def in defs {
+        for statement in def.statements() {
+            graph.run(neo4rs::query(&statement)).await?;
+        }
+    }
+    Ok(())
+}
+
+/// One row of `SHOW CONSTRAINTS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintRow {
+    pub name: String,
+    /// The `type` column, e.g. `UNIQUENESS` or `NODE_PROPERTY_EXISTENCE`.
+    pub kind: String,
+    /// `NODE` or `RELATIONSHIP`.
+    pub entity_type: String,
+    pub labels_or_types: Vec<String>,
+    pub properties: Vec<String>,
+}
+
+/// One row of `SHOW INDEXES`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexRow {
+    pub name: String,
+    /// The `type` column, e.g. `RANGE`, `FULLTEXT` or `LOOKUP`.
+    pub kind: String,
+    /// `NODE` or `RELATIONSHIP`.
+    pub entity_type: String,
+    pub labels_or_types: Vec<String>,
+    pub properties: Vec<String>,
+    /// The constraint backing this index, if any.
+    pub owning_constraint: Option<String>,
+}
+
+/// One row of `db.schema.nodeTypeProperties()` or
+/// `db.schema.relTypeProperties()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyRow {
+    pub target: SchemaTarget,
+    /// The node's labels, or the single relationship type.
+    pub labels_or_types: Vec<String>,
+    /// `None` for a label or type whose elements have no properties.
+    pub property: Option<String>,
+    pub types: Vec<String>,
+    pub mandatory: bool,
+}
+
+/// What the database reports about its schema.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LiveSchema {
+    pub constraints: Vec<ConstraintRow>,
+    pub indexes: Vec<IndexRow>,
+    pub properties: Vec<PropertyRow>,
+}
+
+impl LiveSchema {
+    /// Read constraints, indexes and property types from the database.
+    pub async fn fetch(graph: &Graph) -> Result<Self, CyphrError> {
+        let constraints = CyphrQuery::new(
+            "SHOW CONSTRAINTS YIELD name, type, entityType, labelsOrTypes, properties",
+        )
+        .fetch_rows(graph)
+        .await?
+        .iter()
+        .map(|row| {
+            Ok(ConstraintRow {
+                name: row.get("name")?,
+                kind: row.get("type")?,
+                entity_type: row.get("entityType")?,
+                labels_or_types: row.get::<Option<Vec<String>>>("labelsOrTypes")?.unwrap_or_default(),
+                properties: row.get::<Option<Vec<String>>>("properties")?.unwrap_or_default(),
+            })
+        })
+        .collect::<Result<_, CyphrError>>()?;
+
+        let indexes = CyphrQuery::new(
+            "SHOW INDEXES YIELD name, type, entityType, labelsOrTypes, properties, owningConstraint",
+        )
+        .fetch_rows(graph)
+        .await?
+        .iter()
+        .map(|row| {
+            Ok(IndexRow {
+                name: row.get("name")?,
+                kind: row.get("type")?,
+                entity_type: row.get("entityType")?,
+                labels_or_types: row.get::<Option<Vec<String>>>("labelsOrTypes")?.unwrap_or_default(),
+                properties: row.get::<Option<Vec<String>>>("properties")?.unwrap_or_default(),
+                owning_constraint: row.get("owningConstraint")?,
+            })
+        })
+        .collect::<Result<_, CyphrError>>()?;
+
+        let mut properties: Vec<PropertyRow> = CyphrQuery::new(
+            "CALL db.schema.nodeTypeProperties() YIELD nodeLabels, propertyName, propertyTypes, mandatory",
+        )
+        .fetch_rows(graph)
+        .await?
+        .iter()
+        .map(|row| {
+            Ok(PropertyRow {
+                target: SchemaTarget::Node,
+                labels_or_types: row.get::<Option<Vec<String>>>("nodeLabels")?.unwrap_or_default(),
+                property: row.get("propertyName")?,
+                types: row.get::<Option<Vec<String>>>("propertyTypes")?.unwrap_or_default(),
+                mandatory: row.get("mandatory")?,
+            })
+        })
+        .collect::<Result<_, CyphrError>>()?;
+
+        let rel_rows = CyphrQuery::new(
+            "CALL db.schema.relTypeProperties() YIELD relType, propertyName, propertyTypes, mandatory",
+        )
+        .fetch_rows(graph)
+        .await?;
+        for row in &rel_rows {
+            let rel_type: String = row.get("relType")?;
+            properties.push(PropertyRow {
+                target: SchemaTarget::Relationship,
+                labels_or_types: vec![unquote_type(&rel_type)],
+                property: row.get("propertyName")?,
+                types: row.get::<Option<Vec<String>>>("propertyTypes")?.unwrap_or_default(),
+                mandatory: row.get("mandatory")?,
+            });
+        }
+
+        Ok(Self { constraints, indexes, properties })
+    }
+}
+
+/// One disagreement found by [`compare`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaIssue {
+    /// No node carries the label, or no relationship has the type.
+    MissingLabel { target: SchemaTarget, name: String },
+    /// A declared property is not set on any element.
+    MissingProperty { target: SchemaTarget, name: String, property: String },
+    /// A property present in the database is not declared.
+    UndeclaredProperty { target: SchemaTarget, name: String, property: String },
+    /// A property holds values of other types than its field maps to.
+    PropertyTypeMismatch {
+        target: SchemaTarget,
+        name: String,
+        property: String,
+        expected: String,
+        found: Vec<String>,
+    },
+    /// A declared constraint does not exist.
+    MissingConstraint { target: SchemaTarget, name: String, item: SchemaItem },
+    /// A declared index does not exist.
+    MissingIndex { target: SchemaTarget, name: String, item: SchemaItem },
+    /// A constraint on a declared label or type that is not declared.
+    ExtraConstraint { target: SchemaTarget, name: String, constraint: String },
+    /// An index on a declared label or type that is not declared.
+    ExtraIndex { target: SchemaTarget, name: String, index: String },
+}
+
+impl fmt::Display for SchemaIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaIssue::MissingLabel { target, name } => write!(f, "no {} {name} in the database", noun(*target)),
+            SchemaIssue::MissingProperty { name, property, .. } => write!(f, "{name}.{property} is never set"),
+            SchemaIssue::UndeclaredProperty { name, property, .. } => write!(f, "{name}.{property} is not declared"),
+            SchemaIssue::PropertyTypeMismatch { name, property, expected, found, .. } => {
+                write!(f, "{name}.{property} should be {expected}, found [{}]", found.join(", "))
+            }
+            SchemaIssue::MissingConstraint { item, .. } => write!(f, "missing constraint {}", item.name),
+            SchemaIssue::MissingIndex { item, .. } => write!(f, "missing index {}", item.name),
+            SchemaIssue::ExtraConstraint { name, constraint, .. } => {
+                write!(f, "undeclared constraint {constraint} on {name}")
+            }
+            SchemaIssue::ExtraIndex { name, index, .. } => write!(f, "undeclared index {index} on {name}"),
+        }
+    }
+}
+
+fn noun(target: SchemaTarget) -> &'static str {
+    match target {
+        SchemaTarget::Node => "nodes labelled",
+        SchemaTarget::Relationship => "relationships of type",
+    }
+}
+
+/// The result of [`diff`] or [`compare`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaReport {
+    issues: Vec<SchemaIssue>,
+}
+
+impl SchemaReport {
+    /// Every issue, grouped by definition in the order given.
+    pub fn issues(&self) -> &[SchemaIssue] {
+        &self.issues
+    }
+
+    /// Whether the database matches the definitions.
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Fetch the live schema and [`compare`] it with `defs`.
+pub async fn diff(graph: &Graph, defs: &[SchemaDef]) -> Result<SchemaReport, CyphrError> {
+    let live = LiveSchema::fetch(graph).await?;
+    Ok(compare(defs, &live))
+}
+
+/// Compare `defs` with `live`.
+///
+/// Constraints and indexes match by shape (kind, label or type, and
+/// properties), not by name. Only constraints and indexes on declared labels
+/// and types are reported as extra; token lookup indexes and indexes backing
+/// a constraint are never reported. Properties whose type can't be inferred
+/// from the field are not type-checked.
+pub fn compare(defs: &[SchemaDef], live: &LiveSchema) -> SchemaReport {
+    let mut issues = Vec::new();
+    for def in defs {
+        compare_properties(def, live, &mut issues);
+        compare_items(def, live, &mut issues);
+    }
+    SchemaReport { issues }
+}
+
+fn compare_properties(def: &SchemaDef, live: &LiveSchema, issues: &mut Vec<SchemaIssue>) {
+    let rows: Vec<&PropertyRow> = live
+        .properties
+        .iter()
+        .filter(|r| r.target == def.target && r.labels_or_types.contains(&def.name))
+        .collect();
+    let (target, name) = (def.target, def.name.clone());
+    if rows.is_empty() {
+        issues.push(SchemaIssue::MissingLabel { target, name });
+        return;
+    }
+
+    for prop in &def.properties {
+        let mut found: Vec<String> = Vec::new();
+        for row in rows.iter().filter(|r| r.property.as_deref() == Some(prop.name.as_str())) {
+            for t in &row.types {
+                if !found.contains(t) {
+                    found.push(t.clone());
+                }
+            }
+        }
+        if found.is_empty() {
+            issues.push(SchemaIssue::MissingProperty { target, name: name.clone(), property: prop.name.clone() });
+        } else if let Some(expected) = &prop.neo4j_type {
+            if found.iter().any(|t| t != expected) {
+                issues.push(SchemaIssue::PropertyTypeMismatch {
+                    target,
+                    name: name.clone(),
+                    property: prop.name.clone(),
+                    expected: expected.clone(),
+                    found,
+                });
+            }
+        }
+    }
+
+    let mut undeclared: Vec<&str> = Vec::new();
+    for row in &rows {
+        let Some(property) = row.property.as_deref() else { continue };
+        if !def.properties.iter().any(|p| p.name == property) && !undeclared.contains(&property) {
+            undeclared.push(property);
+        }
+    }
+    for property in undeclared {
+        issues.push(SchemaIssue::UndeclaredProperty { target, name: name.clone(), property: property.to_owned() });
+    }
+}
+
+fn compare_items(def: &SchemaDef, live: &LiveSchema, issues: &mut Vec<SchemaIssue>) {
+    let entity_type = match def.target {
+        SchemaTarget::Node => "NODE",
+        SchemaTarget::Relationship => "RELATIONSHIP",
+    };
+    let on_def = |kind_entity: &str, labels: &[String]| kind_entity == entity_type && labels == [def.name.as_str()];
+    let constraints: Vec<&ConstraintRow> =
+        live.constraints.iter().filter(|c| on_def(&c.entity_type, &c.labels_or_types)).collect();
+    let indexes: Vec<&IndexRow> = live
+        .indexes
+        .iter()
+        .filter(|i| on_def(&i.entity_type, &i.labels_or_types))
+        .filter(|i| i.kind != "LOOKUP" && i.owning_constraint.is_none())
+        .collect();
+
+    let mut used_constraints = vec![false; constraints.len()];
+    let mut used_indexes = vec![false; indexes.len()];
+    let (target, name) = (def.target, def.name.clone());
+    for item in &def.items {
+        let found = match item.kind {
+            SchemaKind::Unique | SchemaKind::Required => {
+                let position = constraints.iter().position(|c| {
+                    constraint_kind(&c.kind) == Some(item.kind) && c.properties == item.properties
+                });
+                position.map(|i| used_constraints[i] = true)
+            }
+            SchemaKind::Index | SchemaKind::Fulltext => {
+                let kind = if item.kind == SchemaKind::Index { "RANGE" } else { "FULLTEXT" };
+                let position = indexes.iter().position(|i| i.kind == kind && i.properties == item.properties);
+                position.map(|i| used_indexes[i] = true)
+            }
+        };
+        if found.is_none() {
+            let item = item.clone();
+            issues.push(match item.kind {
+                SchemaKind::Unique | SchemaKind::Required => {
+                    SchemaIssue::MissingConstraint { target, name: name.clone(), item }
+                }
+                SchemaKind::Index | SchemaKind::Fulltext => SchemaIssue::MissingIndex { target, name: name.clone(), item },
+            });
+        }
+    }
+
+    for (c, used) in constraints.iter().zip(used_constraints) {
+        if !used {
+            issues.push(SchemaIssue::ExtraConstraint { target, name: name.clone(), constraint: c.name.clone() });
+        }
+    }
+    for (i, used) in indexes.iter().zip(used_indexes) {
+        if !used {
+            issues.push(SchemaIssue::ExtraIndex { target, name: name.clone(), index: i.name.clone() });
+        }
+    }
+}
+
+/// The declared kind a `SHOW CONSTRAINTS` type corresponds to.
+fn constraint_kind(kind: &str) -> Option<SchemaKind> {
+    match kind {
+        "UNIQUENESS" | "NODE_UNIQUENESS" | "RELATIONSHIP_UNIQUENESS" => Some(SchemaKind::Unique),
+        "NODE_PROPERTY_EXISTENCE" | "RELATIONSHIP_PROPERTY_EXISTENCE" => Some(SchemaKind::Required),
+        _ => None,
+    }
+}
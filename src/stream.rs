@@ -1,8 +1,9 @@
-
 //! Streaming query results with automatic type mapping.
 
+use std::future::Future;
 use std::pin::Pin;
 use std::marker::PhantomData;
+use std::task::{Context, Poll};
 use futures::stream::Stream;
 use cyphr_core::traits::FromCyphr;
 use cyphr_core::error::CyphrError;
@@ -13,6 +14,10 @@ use cyphr_core::error::CyphrError;
 /// Each call to [`next()`](Self::next) pulls the next row from the database
 /// and maps it to `T`.
 ///
+/// `CyphrStream<T>` implements [`futures::Stream`] with
+/// `Item = Result<T, CyphrError>`, so it works with `StreamExt`,
+/// `TryStreamExt` and any other stream adapter.
+///
 /// # Example
 ///
 /// ```rust,no_run
@@ -33,7 +38,7 @@ use cyphr_core::error::CyphrError;
 /// ```
 pub struct CyphrStream<T> {
     inner: Pin<Box<dyn Stream<Item = Result<neo4rs::Row, neo4rs::Error>> + Send>>,
-    _marker: PhantomData<T>,
+    _marker: PhantomData<fn() -> T>,
 }
 
 impl<T: FromCyphr> CyphrStream<T> {
@@ -41,15 +46,171 @@ impl<T: FromCyphr> CyphrStream<T> {
         Self { inner, _marker: PhantomData }
     }
 
+    /// Build a stream over rows that are already in memory.
+    ///
+    /// Useful for testing code that consumes a `CyphrStream` without a
+    /// running database.
+    ///
+    /// ```rust
+    /// # use cyphr::stream::CyphrStream;
+    /// # #[derive(cyphr::FromCyphr)] struct UserRow { name: String }
+    /// let stream = CyphrStream::<UserRow>::from_rows(Vec::new());
+    /// ```
+    pub fn from_rows(rows: impl IntoIterator<Item = neo4rs::Row>) -> Self {
+        let rows: Vec<_> = rows.into_iter().map(Ok).collect();
+        Self::new(Box::pin(futures::stream::iter(rows)))
+    }
+
+    /// Drain the stream and return its only row, or `None` if it is empty.
+    ///
+    /// Returns [`CyphrError::TooManyRows`] if there is more than one row.
+    /// Only the first row is mapped to `T`.
+    pub async fn at_most_one(self) -> Result<Option<T>, CyphrError> {
+        at_most_one(self.inner).await
+    }
+
+    /// Drain the stream and return its only row.
+    ///
+    /// Returns [`CyphrError::NoRows`] if it is empty and
+    /// [`CyphrError::TooManyRows`] if there is more than one row.
+    pub async fn exactly_one(self) -> Result<T, CyphrError> {
+        self.at_most_one().await?.ok_or(CyphrError::NoRows)
+    }
+
     /// Pull the next row from the stream and map it to `T`.
     ///
     /// Returns `None` when the stream is exhausted.
     pub async fn next(&mut self) -> Option<Result<T, CyphrError>> {
-        use futures::StreamExt;
-        match self.inner.next().await {
-            None => None,
-            Some(Err(e)) => Some(Err(CyphrError::Neo4j(e))),
-            Some(Ok(row)) => Some(T::from_record(&row)),
+        futures::StreamExt::next(self).await
+    }
+
+    /// Group rows into batches of at most `size` items.
+    ///
+    /// The final batch may be shorter. When a row fails to convert or the
+    /// driver reports an error, the rows already collected are yielded as a
+    /// short batch, followed by the error; batching then resumes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// ```rust,no_run
+    /// # use cyphr::query::CyphrQuery;
+    /// # use cyphr::CyphrError;
+    /// # use futures::StreamExt;
+    /// # #[derive(cyphr::FromCyphr)] struct UserRow { name: String }
+    /// # async fn example(graph: &neo4rs::Graph) -> Result<(), CyphrError> {
+    /// let mut batches = CyphrQuery::new("MATCH (u:User) RETURN u.name AS name")
+    ///     .fetch_stream::<UserRow>(graph)
+    ///     .await?
+    ///     .chunks(500);
+    ///
+    /// while let Some(batch) = batches.next().await {
+    ///     let users: Vec<UserRow> = batch?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn chunks(self, size: usize) -> impl Stream<Item = Result<Vec<T>, CyphrError>> {
+        use futures::stream::{self, StreamExt, TryChunksError, TryStreamExt};
+        self.try_chunks(size).flat_map(|batch| {
+            let items = match batch {
+                Ok(rows) => vec![Ok(rows)],
+                Err(TryChunksError(rows, e)) if rows.is_empty() => vec![Err(e)],
+                Err(TryChunksError(rows, e)) => vec![Ok(rows), Err(e)],
+            };
+            stream::iter(items)
+        })
+    }
+
+    /// Run `f` on every row, with at most `limit` invocations in flight.
+    ///
+    /// Stops at the first error, either from the stream or from `f`. Pass
+    /// `None` for no concurrency limit.
+    ///
+    /// ```rust,no_run
+    /// # use cyphr::query::CyphrQuery;
+    /// # use cyphr::CyphrError;
+    /// # #[derive(cyphr::FromCyphr)] struct UserRow { name: String }
+    /// # async fn example(graph: &neo4rs::Graph) -> Result<(), CyphrError> {
+    /// CyphrQuery::new("MATCH (u:User) RETURN u.name AS name")
+    ///     .fetch_stream::<UserRow>(graph)
+    ///     .await?
+    ///     .try_for_each_concurrent(8, |user| async move {
+    ///         println!("{}", user.name);
+    ///         Ok(())
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn try_for_each_concurrent<F, Fut>(
+        self,
+        limit: impl Into<Option<usize>>,
+        f: F,
+    ) -> Result<(), CyphrError>
+    where
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Result<(), CyphrError>>,
+    {
+        futures::TryStreamExt::try_for_each_concurrent(self, limit, f).await
+    }
+
+    /// Drain the stream into any collection implementing `Default + Extend<T>`.
+    ///
+    /// Stops at the first error.
+    ///
+    /// ```rust,no_run
+    /// # use std::collections::VecDeque;
+    /// # use cyphr::query::CyphrQuery;
+    /// # use cyphr::CyphrError;
+    /// # #[derive(cyphr::FromCyphr)] struct UserRow { name: String }
+    /// # async fn example(graph: &neo4rs::Graph) -> Result<(), CyphrError> {
+    /// let users: VecDeque<UserRow> = CyphrQuery::new("MATCH (u:User) RETURN u.name AS name")
+    ///     .fetch_stream(graph)
+    ///     .await?
+    ///     .collect_into()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn collect_into<C: Default + Extend<T>>(self) -> Result<C, CyphrError> {
+        futures::TryStreamExt::try_collect(self).await
+    }
+}
+
+impl<T: FromCyphr> Stream for CyphrStream<T> {
+    type Item = Result<T, CyphrError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.inner.as_mut().poll_next(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(CyphrError::Neo4j(e)))),
+            Poll::Ready(Some(Ok(row))) => Poll::Ready(Some(T::from_record(&row))),
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Drain `rows`, returning the first mapped to `T` if it is the only one.
+pub(crate) async fn at_most_one<T: FromCyphr>(
+    rows: impl Stream<Item = Result<neo4rs::Row, neo4rs::Error>>,
+) -> Result<Option<T>, CyphrError> {
+    use futures::StreamExt;
+    let mut rows = std::pin::pin!(rows);
+    let Some(first) = rows.next().await.transpose()? else {
+        return Ok(None);
+    };
+    let mut count = 1;
+    while rows.next().await.transpose()?.is_some() {
+        count += 1;
+    }
+    if count > 1 {
+        return Err(CyphrError::TooManyRows { count });
+    }
+    T::from_record(&first).map(Some)
 }
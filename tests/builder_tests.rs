@@ -0,0 +1,126 @@
+use cyphr::builder::Fragment;
+use cyphr::query::CyphrQuery;
+
+#[test]
+fn test_builder_clauses_and_paging() {
+    let q = CyphrQuery::builder()
+        .clause("MATCH (u:User)")
+        .clause("RETURN u")
+        .order_by("u.name")
+        .order_by("u.id DESC")
+        .skip(40)
+        .limit(20)
+        .build();
+    assert_eq!(
+        q.text(),
+        "MATCH (u:User)\nRETURN u\nORDER BY u.name, u.id DESC\nSKIP $skip\nLIMIT $limit",
+    );
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["skip", "limit"]);
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_builder_paging_replaces_value() {
+    let q = CyphrQuery::builder()
+        .clause("MATCH (u:User) RETURN u")
+        .skip(1)
+        .skip(2)
+        .limit(5)
+        .limit(10)
+        .build();
+    assert_eq!(q.text(), "MATCH (u:User) RETURN u\nSKIP $skip\nLIMIT $limit");
+    assert_eq!(q.param_value("skip"), Some(&neo4rs::BoltType::from(2)));
+    assert_eq!(q.param_value("limit"), Some(&neo4rs::BoltType::from(10)));
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_builder_optional_filters() {
+    let search = |name: Option<&str>, min_age: Option<i64>| {
+        CyphrQuery::builder()
+            .clause("MATCH (u:User)")
+            .optional(name, |n| Fragment::new("u.name = $name").param("name", n))
+            .optional(min_age, |a| Fragment::new("u.age >= $age").param("age", a))
+            .clause("RETURN u")
+            .build()
+    };
+
+    assert_eq!(search(None, None).text(), "MATCH (u:User)\nRETURN u");
+    assert_eq!(search(None, Some(18)).text(), "MATCH (u:User)\nWHERE u.age >= $age\nRETURN u");
+    let q = search(Some("Alice"), Some(18));
+    assert_eq!(q.text(), "MATCH (u:User)\nWHERE u.name = $name AND u.age >= $age\nRETURN u");
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_builder_where_groups_per_clause() {
+    let q = CyphrQuery::builder()
+        .clause("MATCH (u:User)")
+        .where_("u.active")
+        .clause("MATCH (u)-[:WROTE]->(p:Post)")
+        .where_and("p.score > $min OR p.pinned", [("min", 5)])
+        .where_and("p.lang = $lang", [("lang", "en")])
+        .clause("RETURN p")
+        .build();
+    assert_eq!(
+        q.text(),
+        "MATCH (u:User)\nWHERE u.active\nMATCH (u)-[:WROTE]->(p:Post)\n\
+         WHERE (p.score > $min OR p.pinned) AND p.lang = $lang\nRETURN p",
+    );
+}
+
+#[test]
+fn test_builder_renames_colliding_params() {
+    let q = CyphrQuery::builder()
+        .clause(Fragment::new("MATCH (a:User {name: $name})").param("name", "Alice"))
+        .clause(Fragment::new("MATCH (b:User {name: $name})").param("name", "Bob"))
+        .where_and("b.nick <> '$name' AND b.alias <> $name", [("name", "Bobby")])
+        .clause("RETURN a, b")
+        .limit(1)
+        .build();
+    assert_eq!(
+        q.text(),
+        "MATCH (a:User {name: $name})\nMATCH (b:User {name: $name_2})\n\
+         WHERE b.nick <> '$name' AND b.alias <> $name_3\nRETURN a, b\nLIMIT $limit",
+    );
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["name", "name_2", "name_3", "limit"]);
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_builder_renames_avoid_fragment_keys() {
+    let q = CyphrQuery::builder()
+        .clause(Fragment::new("MATCH (a {x: $a})").param("a", 1))
+        .clause(Fragment::new("MATCH (b {x: $a, y: $a_2})").param("a", 2).param("a_2", 3))
+        .build();
+    assert_eq!(q.text(), "MATCH (a {x: $a})\nMATCH (b {x: $a_3, y: $a_2})");
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["a", "a_3", "a_2"]);
+    assert_eq!(q.param_value("a_3"), Some(&neo4rs::BoltType::from(2)));
+    assert_eq!(q.param_value("a_2"), Some(&neo4rs::BoltType::from(3)));
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_builder_fragment_rebinding_keeps_last_value() {
+    let q = CyphrQuery::builder()
+        .clause(Fragment::new("MATCH (a {x: $a})").param("a", 1))
+        .clause(Fragment::new("MATCH (b {x: $a})").param("a", 2).param("a", 3))
+        .build();
+    assert_eq!(q.text(), "MATCH (a {x: $a})\nMATCH (b {x: $a_2})");
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["a", "a_2"]);
+    assert_eq!(q.param_value("a_2"), Some(&neo4rs::BoltType::from(3)));
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_builder_renames_reserved_paging_params() {
+    let q = CyphrQuery::builder()
+        .clause(Fragment::new("UNWIND range(1, $limit) AS i").param("limit", 100))
+        .clause("RETURN i")
+        .limit(10)
+        .limit(20)
+        .build();
+    assert_eq!(q.text(), "UNWIND range(1, $limit) AS i\nRETURN i\nLIMIT $limit_2");
+    assert_eq!(q.param_value("limit"), Some(&neo4rs::BoltType::from(100)));
+    assert_eq!(q.param_value("limit_2"), Some(&neo4rs::BoltType::from(20)));
+}
@@ -0,0 +1,161 @@
+//! Snapshot tests for the text produced by `cypher!` and `cypher_query!`.
+//! Every expected string is exactly what should be pasted into Neo4j Browser.
+
+use cyphr::{cypher, cypher_query};
+
+#[test]
+fn test_print_patterns() {
+    assert_eq!(
+        cypher! { MATCH (u:User)-[:FOLLOWS]->(f:User) RETURN f },
+        "MATCH (u:User)-[:FOLLOWS]->(f:User) RETURN f",
+    );
+    assert_eq!(
+        cypher! { MATCH (a) <- [ r : KNOWS * 1 .. 3 ] - (b) RETURN r },
+        "MATCH (a)<-[r:KNOWS*1..3]-(b) RETURN r",
+    );
+    assert_eq!(
+        cypher! { MATCH (a)-->(b)<--(c), (c)--(d) RETURN a },
+        "MATCH (a)-->(b)<--(c), (c)--(d) RETURN a",
+    );
+    assert_eq!(
+        cypher! { MATCH (n:User|Admin)-[:A|B*]-(m:!Banned) RETURN n },
+        "MATCH (n:User|Admin)-[:A|B*]-(m:!Banned) RETURN n",
+    );
+    assert_eq!(
+        cypher! { MATCH p = shortestPath((a)-[*..5]-(b)) RETURN p },
+        "MATCH p = shortestPath((a)-[*..5]-(b)) RETURN p",
+    );
+    assert_eq!(
+        cypher! { MATCH (a)((x)-[:R]->(y)){1,3}(b) RETURN a },
+        "MATCH (a) ((x)-[:R]->(y)){1,3} (b) RETURN a",
+    );
+}
+
+#[test]
+fn test_print_operators() {
+    assert_eq!(
+        cypher! { MATCH (u) WHERE u.age<>30 AND u.name=~"A.*" OR u.x>=1 RETURN u },
+        "MATCH (u) WHERE u.age <> 30 AND u.name =~ \"A.*\" OR u.x >= 1 RETURN u",
+    );
+    assert_eq!(
+        cypher! { RETURN -1, 2*-x, a.list[0], a.list[1..], a.list[..2], size(xs)+1 },
+        "RETURN -1, 2 * -x, a.list[0], a.list[1..], a.list[..2], size(xs) + 1",
+    );
+    assert_eq!(
+        cypher! { MATCH (u) SET u:Active, u += {visits: 1}, u.score = 0.5 },
+        "MATCH (u) SET u:Active, u += {visits: 1}, u.score = 0.5",
+    );
+    assert_eq!(
+        cypher! { RETURN [x IN xs WHERE x:Admin | x.name], count(*), n {.name, .*, total: 1} },
+        "RETURN [x IN xs WHERE x:Admin | x.name], count(*), n {.name, .*, total: 1}",
+    );
+}
+
+#[test]
+fn test_print_preserves_literals() {
+    assert_eq!(
+        cypher! { RETURN "a : b", "( x )", "<-[", 'c' },
+        "RETURN \"a : b\", \"( x )\", \"<-[\", 'c'",
+    );
+    assert_eq!(
+        cypher! { MATCH (u {name: "  spaced  "}) RETURN u },
+        "MATCH (u {name: \"  spaced  \"}) RETURN u",
+    );
+}
+
+#[test]
+fn test_print_reescapes_string_literals() {
+    assert_eq!(cypher! { RETURN "\u{1F600}", "caf\u{e9}" }, "RETURN \"😀\", \"café\"");
+    assert_eq!(
+        cypher! { RETURN "say \"hi\"\n", "a\\b", r"C:\dir", '\'', "\0" },
+        r#"RETURN "say \"hi\"\n", "a\\b", "C:\\dir", '\'', "\u0000""#,
+    );
+}
+
+#[test]
+fn test_print_clauses() {
+    assert_eq!(
+        cypher! {
+            MATCH (u:User)
+            CALL { WITH u MATCH (u)-[:WROTE]->(p) RETURN count(p) AS posts }
+            RETURN u, posts ORDER BY posts DESC LIMIT 10
+        },
+        "MATCH (u:User) CALL { WITH u MATCH (u)-[:WROTE]->(p) RETURN count(p) AS posts } \
+         RETURN u, posts ORDER BY posts DESC LIMIT 10",
+    );
+    assert_eq!(
+        cypher! { CALL db.index.fulltext.queryNodes("idx", "q") YIELD node RETURN node },
+        "CALL db.index.fulltext.queryNodes(\"idx\", \"q\") YIELD node RETURN node",
+    );
+    assert_eq!(
+        cypher! { CREATE INDEX user_name IF NOT EXISTS FOR (u:User) ON (u.name) },
+        "CREATE INDEX user_name IF NOT EXISTS FOR (u:User) ON (u.name)",
+    );
+}
+
+#[test]
+fn test_print_params() {
+    let name = "Alice";
+    let age: i64 = 30;
+    let q = cypher_query! { MATCH (u:User {name: $name}) WHERE u.age > $age RETURN u };
+    assert_eq!(q.text(), "MATCH (u:User {name: $name}) WHERE u.age > $age RETURN u");
+}
+
+mod models {
+    use cyphr::{CyphrNode, CyphrRelation};
+
+    #[derive(CyphrNode)]
+    #[cyphr(label = "Person")]
+    #[allow(dead_code)]
+    pub struct User {
+        pub id: i64,
+    }
+
+    #[derive(CyphrRelation)]
+    #[cyphr(type = "FOLLOWS", from = "User", to = "User")]
+    #[allow(dead_code)]
+    pub struct Follows {
+        pub since: i64,
+    }
+
+    #[derive(CyphrNode)]
+    #[cyphr(label = "Blog Post")]
+    #[allow(dead_code)]
+    pub struct Post {
+        pub id: i64,
+    }
+
+    #[derive(CyphrRelation)]
+    #[cyphr(type = "LIKES`2", from = "User", to = "Post")]
+    #[allow(dead_code)]
+    pub struct Likes {
+        pub at: i64,
+    }
+}
+
+use models::{Follows, Likes, Post, User};
+
+#[test]
+fn test_print_label_splices() {
+    assert_eq!(
+        cypher! { MATCH (u:#User)-[f:#Follows]->(v:#models::User) RETURN u },
+        "MATCH (u:Person)-[f:FOLLOWS]->(v:Person) RETURN u",
+    );
+    assert_eq!(
+        cypher! { MATCH (u) WHERE u:#User OR (u)<-[:#Follows|LIKES]-() SET u:#User RETURN u },
+        "MATCH (u) WHERE u:Person OR (u)<-[:FOLLOWS|LIKES]-() SET u:Person RETURN u",
+    );
+
+    let id: i64 = 1;
+    let q = cypher_query! { MATCH (u:#User {id: $id}) RETURN u };
+    assert_eq!(q.text(), "MATCH (u:Person {id: $id}) RETURN u");
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["id"]);
+}
+
+#[test]
+fn test_print_label_splices_are_escaped() {
+    assert_eq!(
+        cypher! { MATCH (u:#User)-[:#Likes]->(p:#Post) RETURN p },
+        "MATCH (u:Person)-[:`LIKES``2`]->(p:`Blog Post`) RETURN p",
+    );
+}
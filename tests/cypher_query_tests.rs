@@ -24,3 +24,59 @@ fn test_cypher_query_dedup_params() {
         MATCH (u:User {name: $name}) WHERE u.name = $name RETURN u
     };
 }
+
+struct User {
+    id: i64,
+    name: String,
+}
+
+#[test]
+fn test_cypher_query_explicit_bindings() {
+    let user = User { id: 7, name: "Alice".into() };
+    let q = cypher_query!(
+        MATCH (u:User {id: $id}) RETURN u LIMIT $limit;
+        id = user.id, limit = 10 * 2
+    );
+    assert_eq!(q.text(), "MATCH (u:User {id: $id}) RETURN u LIMIT $limit");
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["id", "limit"]);
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_cypher_query_mixed_bindings_and_capture() {
+    let user = User { id: 7, name: "Alice".into() };
+    let limit: i64 = 5;
+    let q = cypher_query! {
+        MATCH (u:User {name: $name}) WHERE u.id <> $id RETURN u LIMIT $limit;
+        $name = user.name.clone(), id = user.id
+    };
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["name", "id", "limit"]);
+}
+
+#[test]
+fn test_cypher_query_inline_bindings() {
+    let user = User { id: 7, name: "Alice".into() };
+    let q = cypher_query! {
+        MATCH (u:User {id: $id(user.id)}) WHERE u.name <> $name RETURN u LIMIT $limit(10 * 2);
+        name = user.name
+    };
+    assert_eq!(q.text(), "MATCH (u:User {id: $id}) WHERE u.name <> $name RETURN u LIMIT $limit");
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["id", "name", "limit"]);
+    assert_eq!(q.param_value("limit"), Some(&neo4rs::BoltType::from(20)));
+}
+
+#[test]
+fn test_cypher_query_raw_ident_param() {
+    let r#type = "admin";
+    let q = cypher_query! { MATCH (u:User {type: $r#type}) RETURN u };
+    assert_eq!(q.text(), "MATCH (u:User {type: $type}) RETURN u");
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["type"]);
+}
+
+#[test]
+fn test_cypher_query_trailing_semicolon_is_not_a_binding_section() {
+    let name = "Alice";
+    let q = cypher_query! { MATCH (u:User {name: $name}) RETURN u; };
+    assert_eq!(q.text(), "MATCH (u:User {name: $name}) RETURN u;");
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["name"]);
+}
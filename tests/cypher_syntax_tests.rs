@@ -0,0 +1,120 @@
+//! Queries the compile-time Cypher checker must accept. Each test compiling is
+//! the assertion. Rejected queries and their error messages are pinned by the
+//! trybuild cases in `cyphr-macros/tests/ui`; the printed text is checked in
+//! `cypher_print_tests.rs`.
+
+use cyphr::{cypher, cypher_query};
+
+#[test]
+fn test_accepts_reading_clauses() {
+    let _ = cypher! { MATCH (n) RETURN n };
+    let _ = cypher! { MATCH (u:User)-[:FOLLOWS]->(f:User) WHERE u.name = "Alice" RETURN f.name, f.id };
+    let _ = cypher! { OPTIONAL MATCH (u:User)<-[r:WROTE|EDITED]-(p) RETURN u, r, p };
+    let _ = cypher! { MATCH (a)--(b), (b)-[*1..3]->(c) RETURN DISTINCT c ORDER BY c.name DESC SKIP 10 LIMIT 5 };
+    let _ = cypher! { MATCH p = (a:Person)-[:KNOWS*]->(b) RETURN nodes(p), length(p) };
+    let _ = cypher! { MATCH p = shortestPath((a)-[*..5]-(b)) RETURN p };
+    let _ = cypher! { MATCH (n:User:Admin) USING INDEX n:User(name) WHERE n.name STARTS WITH "A" RETURN n };
+    let _ = cypher! { MATCH (n:(User|Admin)&!Banned) RETURN n };
+    let _ = cypher! { MATCH (a)(()-[:R]->()){1,3}(b) RETURN a, b };
+    let _ = cypher! { UNWIND [1, 2, 3] AS x WITH x WHERE x > 1 RETURN x };
+    let _ = cypher! { MATCH (n) RETURN * };
+    let _ = cypher! { MATCH (n) RETURN n UNION ALL MATCH (m) RETURN m AS n };
+}
+
+#[test]
+fn test_accepts_updating_clauses() {
+    let _ = cypher! { CREATE (u:User {name: "Alice", age: 30})-[:OWNS]->(:Pet {name: "Rex"}) };
+    let _ = cypher! {
+        MERGE (u:User {id: 1})
+        ON CREATE SET u.created = timestamp()
+        ON MATCH SET u.seen = u.seen + 1
+        RETURN u
+    };
+    let _ = cypher! { MATCH (u:User) SET u:Active, u += {visits: 1}, u.score = 0.5 };
+    let _ = cypher! { MATCH (u:User) REMOVE u.age, u:Active };
+    let _ = cypher! { MATCH (u:User {id: 1}) DETACH DELETE u };
+    let _ = cypher! { MATCH (u:User) FOREACH (x IN [1, 2] | CREATE (:Tag {v: x})) };
+    let _ = cypher! { LOAD CSV WITH HEADERS FROM "file:///users.csv" AS row CREATE (:User {name: row.name}) };
+}
+
+#[test]
+fn test_accepts_expressions() {
+    let _ = cypher! {
+        MATCH (u:User)
+        WHERE u.age >= 18 AND NOT u.banned OR u.role IN ["admin", "mod"] XOR u.email =~ ".*@x.com"
+        RETURN u.age * 2 + 1 - 3 % 2 ^ 2 AS n, -u.age, u.tags[0], u.tags[1..], u.name IS NOT NULL
+    };
+    let _ = cypher! {
+        MATCH (u:User)
+        RETURN CASE WHEN u.age < 18 THEN "minor" ELSE "adult" END AS bracket,
+               CASE u.role WHEN "admin" THEN 1 WHEN "mod" THEN 2 END AS rank
+    };
+    let _ = cypher! {
+        MATCH (u:User)
+        RETURN [x IN u.tags WHERE x <> "" | toUpper(x)] AS tags,
+               [(u)-[:FOLLOWS]->(f) WHERE f.active | f.name] AS follows,
+               u {.name, .age, friends: size(u.friends), score: 1.5} AS proj,
+               {a: 1, b: [true, false, null]} AS literal,
+               reduce(acc = 0, x IN [1, 2, 3] | acc + x) AS total,
+               all(x IN u.tags WHERE x STARTS WITH "t") AS all_t,
+               count(*) AS c, count(DISTINCT u.name) AS names,
+               apoc.text.join(u.tags, ",") AS joined
+    };
+    let _ = cypher! { MATCH (u:User) WHERE (u)-[:FOLLOWS]->(:User) RETURN u };
+    let _ = cypher! { MATCH (u:User) WHERE EXISTS { MATCH (u)-[:OWNS]->(:Pet) } RETURN u };
+    let _ = cypher! { MATCH (u:User) RETURN COUNT { (u)-[:FOLLOWS]->() } AS follows };
+    let _ = cypher! { MATCH (u:User) WHERE u:Admin OR u.type IS :: STRING RETURN u };
+}
+
+#[test]
+fn test_accepts_procedures_and_subqueries() {
+    let _ = cypher! { CALL db.labels() YIELD label RETURN label };
+    let _ = cypher! { CALL db.index.fulltext.queryNodes("idx", "alice") YIELD node, score WHERE score > 1 RETURN node };
+    let _ = cypher! { CALL dbms.components() };
+    let _ = cypher! {
+        MATCH (u:User)
+        CALL { WITH u MATCH (u)-[:WROTE]->(p) RETURN count(p) AS posts }
+        RETURN u, posts
+    };
+    let _ = cypher! {
+        MATCH (u:User)
+        CALL (u) { MATCH (u)-[:WROTE]->(p) DETACH DELETE p } IN TRANSACTIONS OF 100 ROWS
+    };
+}
+
+#[test]
+fn test_accepts_admin_commands() {
+    let _ = cypher! { CREATE CONSTRAINT user_id IF NOT EXISTS FOR (u:User) REQUIRE u.id IS UNIQUE };
+    let _ = cypher! { CREATE INDEX user_name IF NOT EXISTS FOR (u:User) ON (u.name) };
+    let _ = cypher! { SHOW INDEXES };
+}
+
+#[test]
+fn test_accepts_keywords_as_names() {
+    let _ = cypher! { MATCH (n:Match {order: 1, limit: 2}) RETURN n.end AS set, n.count };
+    let _ = cypher! { MATCH (n) RETURN n; };
+}
+
+#[test]
+fn test_accepts_clause_keywords_as_variables() {
+    let _ = cypher! { MATCH (order:Order) RETURN order };
+    let _ = cypher! { MATCH (limit) WITH limit AS skip RETURN skip ORDER BY skip.at LIMIT 1 };
+}
+
+#[test]
+fn test_accepts_query_options() {
+    let _ = cypher! { EXPLAIN MATCH (n) RETURN n };
+    let _ = cypher! { PROFILE MATCH (n) RETURN n };
+    let _ = cypher! { CYPHER runtime=slotted MATCH (n) RETURN n };
+    let _ = cypher! { CYPHER 5 PROFILE MATCH (n) RETURN n };
+}
+
+#[test]
+fn test_accepts_parameters() {
+    let id: i64 = 1;
+    let props = 0;
+    let names: Vec<String> = Vec::new();
+    let _q = cypher_query! {
+        MATCH (u:User {id: $id}) WHERE u.name IN $names SET u += $props RETURN u
+    };
+}
@@ -0,0 +1,124 @@
+use cyphr::ident::CypherIdent;
+use cyphr::query::CyphrQuery;
+use cyphr::CyphrError;
+
+fn ident(name: &str) -> String {
+    CypherIdent::new(name).unwrap().to_string()
+}
+
+#[test]
+fn test_plain_names_unquoted() {
+    assert_eq!(ident("User"), "User");
+    assert_eq!(ident("_private"), "_private");
+    assert_eq!(ident("HAS_TAG_2"), "HAS_TAG_2");
+}
+
+#[test]
+fn test_unicode_letters_unquoted() {
+    assert_eq!(ident("Café"), "Café");
+    assert_eq!(ident("Пользователь"), "Пользователь");
+    assert_eq!(ident("用户"), "用户");
+}
+
+#[test]
+fn test_names_needing_quotes() {
+    assert_eq!(ident("Blog Post"), "`Blog Post`");
+    assert_eq!(ident("has-tag"), "`has-tag`");
+    assert_eq!(ident("2fa"), "`2fa`");
+    assert_eq!(ident("n.name"), "`n.name`");
+    assert_eq!(ident("🚀"), "`🚀`");
+    assert_eq!(ident("x²"), "`x²`");
+}
+
+#[test]
+fn test_backticks_doubled() {
+    assert_eq!(ident("a`b"), "`a``b`");
+    assert_eq!(ident("`"), "````");
+    assert_eq!(ident("User` {admin: true}) //"), "`User`` {admin: true}) //`");
+}
+
+#[test]
+fn test_as_str_is_unescaped() {
+    let id = CypherIdent::new("a`b").unwrap();
+    assert_eq!(id.as_str(), "a`b");
+    assert!(id.is_quoted());
+    assert!(!CypherIdent::new("User").unwrap().is_quoted());
+}
+
+#[test]
+fn test_rejects_empty_and_nul() {
+    let err = CypherIdent::new("").unwrap_err();
+    assert!(matches!(err, CyphrError::InvalidIdent { .. }));
+    assert_eq!(err.to_string(), "invalid identifier \"\": must not be empty");
+    assert!(CypherIdent::new("a\0b").is_err());
+}
+
+#[test]
+fn test_strict() {
+    assert_eq!(CypherIdent::strict("Café").unwrap().to_string(), "Café");
+    assert!(CypherIdent::strict("Blog Post").is_err());
+    assert!(CypherIdent::strict("a`b").is_err());
+    assert!(CypherIdent::strict("9lives").is_err());
+}
+
+#[test]
+fn test_from_str() {
+    let id: CypherIdent = "Tag".parse().unwrap();
+    assert_eq!(id.as_str(), "Tag");
+    assert!("".parse::<CypherIdent>().is_err());
+}
+
+#[test]
+fn test_query_ident_slots() {
+    let q = CyphrQuery::new("MATCH (a:{label})-[r:{rel}]->(b:{label}) RETURN r.{key}")
+        .ident("label", &CypherIdent::new("User").unwrap())
+        .ident("rel", &CypherIdent::new("LIKES").unwrap())
+        .ident("key", &CypherIdent::new("last seen").unwrap());
+    assert_eq!(q.text(), "MATCH (a:User)-[r:LIKES]->(b:User) RETURN r.`last seen`");
+}
+
+#[test]
+fn test_query_ident_injection_is_escaped() {
+    let q = CyphrQuery::new("MATCH (n:{label}) RETURN n")
+        .try_ident("label", "User) DETACH DELETE n //")
+        .unwrap();
+    assert_eq!(q.text(), "MATCH (n:`User) DETACH DELETE n //`) RETURN n");
+
+    let q = CyphrQuery::new("MATCH (n:{label}) RETURN n")
+        .try_ident("label", "x`) DETACH DELETE n //")
+        .unwrap();
+    assert_eq!(q.text(), "MATCH (n:`x``) DETACH DELETE n //`) RETURN n");
+}
+
+#[test]
+fn test_query_ident_skips_strings_and_comments() {
+    let q = CyphrQuery::new("MATCH (n:{label} {note: '{label}'}) // {label}\nRETURN n.`{label}`")
+        .ident("label", &CypherIdent::new("User").unwrap());
+    assert_eq!(q.text(), "MATCH (n:User {note: '{label}'}) // {label}\nRETURN n.`{label}`");
+}
+
+#[test]
+fn test_query_ident_value_not_refilled() {
+    let q = CyphrQuery::new("MATCH (n:{a}:{b}) RETURN n")
+        .try_ident("a", "{b}")
+        .unwrap()
+        .try_ident("b", "Admin")
+        .unwrap();
+    assert_eq!(q.text(), "MATCH (n:`{b}`:Admin) RETURN n");
+}
+
+#[test]
+fn test_query_ident_keeps_params() {
+    let q = CyphrQuery::new("MATCH (n:{label} {id: $id}) RETURN n")
+        .param("id", 1_i64)
+        .try_ident("label", "User")
+        .unwrap();
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), vec!["id"]);
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_query_try_ident_rejects_empty() {
+    let result = CyphrQuery::new("MATCH (n:{label}) RETURN n").try_ident("label", "");
+    assert!(matches!(result, Err(CyphrError::InvalidIdent { .. })));
+}
@@ -0,0 +1,47 @@
+use cyphr::include_cypher;
+
+const FIND_USER: &str = "// Look up a user by id. Edited by the DBA team.
+MATCH (u:User {id: $id})
+WHERE u.name <> 'a : b' AND u.`display name` IS NOT NULL
+RETURN u
+LIMIT $limit";
+
+#[test]
+fn test_include_cypher_keeps_file_text() {
+    let id: i64 = 7;
+    let limit: i64 = 1;
+    let q = include_cypher!("tests/queries/find_user.cypher");
+    assert_eq!(q.text(), FIND_USER);
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["id", "limit"]);
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_include_cypher_with_bindings() {
+    let id: i64 = 7;
+    let q = include_cypher!("tests/queries/find_user.cypher"; limit = 5 * 2);
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["id", "limit"]);
+    assert!(q.validate().is_ok());
+}
+
+include_cypher!(fn find_user, "tests/queries/find_user.cypher");
+
+#[test]
+fn test_include_cypher_fn() {
+    let q = find_user(7, 10);
+    assert_eq!(q.text(), FIND_USER);
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["id", "limit"]);
+}
+
+include_cypher!(pub mod user_queries, "tests/queries/users");
+
+#[test]
+fn test_include_cypher_mod() {
+    let q = user_queries::by_name("Al");
+    assert_eq!(q.text(), "MATCH (u:User)\nWHERE u.name STARTS WITH $prefix\nRETURN u ORDER BY u.name");
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["prefix"]);
+
+    // `$type` becomes the raw identifier `r#type`.
+    let q = user_queries::set_type(1, "admin");
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["id", "type"]);
+}
@@ -0,0 +1,100 @@
+use cyphr::migrate::{AppliedMigration, Migration, MigrationState, MigrationStatus, Migrator};
+use cyphr::CyphrError;
+
+fn applied(m: &Migration) -> AppliedMigration {
+    AppliedMigration { version: m.version(), name: m.name().to_owned(), checksum: m.checksum().to_owned() }
+}
+
+fn states(report: &[MigrationStatus]) -> Vec<(i64, MigrationState)> {
+    report.iter().map(|s| (s.version, s.state.clone())).collect()
+}
+
+#[test]
+fn test_embedded_matches_dir() {
+    let embedded = cyphr::embed_migrations!("tests/migrations");
+    let loaded = Migrator::from_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/migrations")).unwrap();
+    assert_eq!(embedded.migrations(), loaded.migrations());
+
+    let versions: Vec<_> = embedded.migrations().iter().map(|m| (m.version(), m.name())).collect();
+    assert_eq!(versions, vec![(1, "create_users"), (2, "seed_admin")]);
+}
+
+#[test]
+fn test_statements_split() {
+    let migrator = cyphr::embed_migrations!("tests/migrations");
+    let [first, second] = migrator.migrations() else { panic!("expected two migrations") };
+    assert_eq!(first.statements(), [
+        "// Users are identified by email.\nCREATE CONSTRAINT user_email IF NOT EXISTS FOR (u:User) REQUIRE u.email IS UNIQUE",
+        "CREATE INDEX user_name IF NOT EXISTS FOR (u:User) ON (u.name)",
+    ]);
+    assert_eq!(second.statements().len(), 1);
+    assert!(second.statements()[0].contains("'Admin; root'"));
+}
+
+#[test]
+fn test_schema_migrations_detected() {
+    let migrator = cyphr::embed_migrations!("tests/migrations");
+    let [first, second] = migrator.migrations() else { panic!("expected two migrations") };
+    assert!(first.is_schema());
+    assert!(!second.is_schema());
+
+    for text in ["drop index user_name", "CREATE FULLTEXT INDEX bio FOR (n:User) ON EACH [n.bio]", "/* x */ DROP CONSTRAINT c"] {
+        assert!(Migration::new(1, "a", text).is_schema(), "{text}");
+    }
+    for text in ["CREATE (:Index {name: 'CONSTRAINT'})", "MATCH (n:INDEX) RETURN n", "CREATE `INDEX`"] {
+        assert!(!Migration::new(1, "a", text).is_schema(), "{text}");
+    }
+}
+
+#[test]
+fn test_checksum_ignores_line_endings() {
+    let unix = Migration::new(1, "a", "MATCH (n)\nRETURN n");
+    let windows = Migration::new(1, "a", "MATCH (n)\r\nRETURN n");
+    let edited = Migration::new(1, "a", "MATCH (n)\nRETURN n LIMIT 1");
+    assert_eq!(unix.checksum(), windows.checksum());
+    assert_ne!(unix.checksum(), edited.checksum());
+    assert_eq!(unix.checksum().len(), 16);
+}
+
+#[test]
+fn test_from_file_names() {
+    let m = Migration::from_file("0010__add_tags.cypher", "RETURN 1").unwrap();
+    assert_eq!((m.version(), m.name()), (10, "add_tags"));
+    for bad in ["add_tags.cypher", "v1_add.cypher", "0001.cypher", "0001_add.txt"] {
+        let err = Migration::from_file(bad, "RETURN 1").unwrap_err();
+        assert!(matches!(err, CyphrError::Migration(_)), "{bad}");
+    }
+}
+
+#[test]
+fn test_compare_pending_and_applied() {
+    let migrator = Migrator::new(vec![
+        Migration::new(2, "second", "RETURN 2"),
+        Migration::new(1, "first", "RETURN 1"),
+    ]);
+    let first = applied(&migrator.migrations()[0]);
+    let report = migrator.compare(&[first]).unwrap();
+    assert_eq!(states(&report), vec![(1, MigrationState::Applied), (2, MigrationState::Pending)]);
+    assert_eq!(report[1].name, "second");
+}
+
+#[test]
+fn test_compare_drift_and_missing() {
+    let migrator = Migrator::new(vec![Migration::new(1, "first", "RETURN 1")]);
+    let recorded = [
+        AppliedMigration { version: 1, name: "first".into(), checksum: "0000000000000000".into() },
+        AppliedMigration { version: 3, name: "removed".into(), checksum: "1111111111111111".into() },
+    ];
+    let report = migrator.compare(&recorded).unwrap();
+    assert_eq!(states(&report), vec![
+        (1, MigrationState::Drifted { recorded: "0000000000000000".into() }),
+        (3, MigrationState::Missing),
+    ]);
+}
+
+#[test]
+fn test_compare_duplicate_versions() {
+    let migrator = Migrator::new(vec![Migration::new(1, "a", "RETURN 1"), Migration::new(1, "b", "RETURN 2")]);
+    let err = migrator.compare(&[]).unwrap_err();
+    assert_eq!(err.to_string(), "migration error: `a` and `b` both have version 1");
+}
@@ -0,0 +1,3 @@
+// Users are identified by email.
+CREATE CONSTRAINT user_email IF NOT EXISTS FOR (u:User) REQUIRE u.email IS UNIQUE;
+CREATE INDEX user_name IF NOT EXISTS FOR (u:User) ON (u.name);
@@ -0,0 +1,3 @@
+MERGE (u:User {email: 'admin@example.com'})
+SET u.name = 'Admin; root'
+/* trailing comment; not a statement */
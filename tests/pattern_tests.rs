@@ -0,0 +1,134 @@
+use cyphr::pattern::{param, Expr, Match, Var};
+use cyphr::prelude::*;
+
+#[derive(CyphrNode)]
+#[cyphr(label = "User")]
+#[allow(dead_code)]
+struct User {
+    name: String,
+}
+
+#[derive(CyphrNode)]
+#[cyphr(label = "Post")]
+#[allow(dead_code)]
+struct Post {
+    title: String,
+}
+
+#[derive(CyphrNode)]
+#[cyphr(label = "Blog Post")]
+#[allow(dead_code)]
+struct Legacy {
+    title: String,
+}
+
+#[derive(CyphrRelation)]
+#[cyphr(type = "FOLLOWS", from_type = User, to_type = User)]
+#[allow(dead_code)]
+struct Follows {
+    since: i64,
+}
+
+#[derive(CyphrRelation)]
+#[cyphr(type = "WROTE", from = "User", to = "Post", from_type = User, to_type = Post)]
+#[allow(dead_code)]
+struct Wrote {
+    at: i64,
+}
+
+#[derive(CyphrRelation)]
+#[cyphr(type = "LINKS_TO")]
+#[allow(dead_code)]
+struct LinksTo {
+    weight: i64,
+}
+
+#[test]
+fn test_single_node() {
+    let stmt = Match::node::<User>("u").ret([Expr::raw("u")]);
+    assert_eq!(stmt.to_string(), "MATCH (u:User)\nRETURN u");
+}
+
+#[test]
+fn test_outgoing_chain() {
+    let u = Var::<User>::new("u");
+    let stmt = Match::node(&u)
+        .rel::<Follows>("f")
+        .node::<User>("v")
+        .rel::<Wrote>("w")
+        .node::<Post>("p")
+        .ret([(&u).into(), Expr::raw("p")]);
+    assert_eq!(
+        stmt.to_string(),
+        "MATCH (u:User)-[f:FOLLOWS]->(v:User)-[w:WROTE]->(p:Post)\nRETURN u, p",
+    );
+}
+
+#[test]
+fn test_incoming_rel() {
+    let stmt = Match::node::<Post>("p").rel_in::<Wrote>("w").node::<User>("u").ret([]);
+    assert_eq!(stmt.to_string(), "MATCH (p:Post)<-[w:WROTE]-(u:User)\nRETURN *");
+}
+
+#[test]
+fn test_undeclared_endpoints_connect_anything() {
+    let stmt = Match::node::<Post>("a").rel::<LinksTo>("l").node::<User>("b").ret([]);
+    assert_eq!(stmt.to_string(), "MATCH (a:Post)-[l:LINKS_TO]->(b:User)\nRETURN *");
+}
+
+#[test]
+fn test_labels_and_keys_escaped() {
+    let l = Var::<Legacy>::new("l");
+    let stmt = Match::node(&l).ret([l.prop("first title").as_("title")]);
+    assert_eq!(stmt.to_string(), "MATCH (l:`Blog Post`)\nRETURN l.`first title` AS title");
+}
+
+#[test]
+fn test_where_and_params() {
+    let u = Var::<User>::new("u");
+    let q = Match::node(&u)
+        .where_(u.prop("name").starts_with(param("name", "Al")))
+        .where_(u.prop("age").ge(param("age", 18_i64)))
+        .ret([u.prop("name")])
+        .query();
+    assert_eq!(q.text(), "MATCH (u:User)\nWHERE u.name STARTS WITH $name AND u.age >= $age\nRETURN u.name");
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), vec!["name", "age"]);
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_colliding_params_renamed() {
+    let u = Var::<User>::new("u");
+    let q = Match::node(&u)
+        .where_(u.prop("age").gt(param("n", 18_i64)).and(u.prop("age").lt(param("n", 65_i64))))
+        .ret([u.prop("name")])
+        .query();
+    assert_eq!(q.text(), "MATCH (u:User)\nWHERE u.age > $n AND u.age < $n_2\nRETURN u.name");
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_boolean_precedence() {
+    let u = Var::<User>::new("u");
+    let a = u.prop("a").is_null();
+    let b = u.prop("b").eq(Expr::raw("1"));
+    let c = u.prop("c").eq(Expr::raw("2"));
+    assert_eq!(a.clone().and(b.clone().or(c.clone())).text(), "u.a IS NULL AND (u.b = 1 OR u.c = 2)");
+    assert_eq!(a.clone().or(b.clone().and(c.clone())).text(), "u.a IS NULL OR u.b = 1 AND u.c = 2");
+    assert_eq!(a.or(b).not().text(), "NOT (u.a IS NULL OR u.b = 1)");
+    assert_eq!(c.not().text(), "NOT u.c = 2");
+}
+
+#[test]
+fn test_order_skip_limit() {
+    let u = Var::<User>::new("u");
+    let stmt = Match::node(&u)
+        .ret([u.prop("name")])
+        .order_by(u.prop("name").desc())
+        .skip(10)
+        .limit(5);
+    assert_eq!(
+        stmt.to_string(),
+        "MATCH (u:User)\nRETURN u.name\nORDER BY u.name DESC\nSKIP $skip\nLIMIT $limit",
+    );
+}
@@ -0,0 +1,5 @@
+// Look up a user by id. Edited by the DBA team.
+MATCH (u:User {id: $id})
+WHERE u.name <> 'a : b' AND u.`display name` IS NOT NULL
+RETURN u
+LIMIT $limit
@@ -0,0 +1,3 @@
+MATCH (u:User)
+WHERE u.name STARTS WITH $prefix
+RETURN u ORDER BY u.name
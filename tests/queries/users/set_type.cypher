@@ -0,0 +1,2 @@
+MATCH (u:User {id: $id})
+SET u.type = $type
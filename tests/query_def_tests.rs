@@ -0,0 +1,69 @@
+use cyphr::prelude::*;
+
+#[derive(FromCyphr)]
+#[allow(dead_code)]
+struct UserRow {
+    name: String,
+}
+
+#[derive(CyphrQueryDef)]
+#[cyphr(query = "MATCH (u:User {id: $id}) RETURN u.name AS name", returns = "UserRow")]
+struct FindUser {
+    id: i64,
+}
+
+#[derive(CyphrQueryDef)]
+#[cyphr(query = "MATCH (u:User) WHERE u.email = $email_address AND u.age > $min_age RETURN u")]
+struct FindAdults {
+    #[cyphr(prop = "email_address")]
+    email: String,
+    min_age: i64,
+    #[cyphr(skip)]
+    #[allow(dead_code)]
+    note: &'static str,
+}
+
+#[derive(CyphrQueryDef)]
+#[cyphr(query = "MATCH (u:User {type: $type}) RETURN u")]
+struct FindByType {
+    r#type: String,
+}
+
+#[derive(CyphrQueryDef)]
+#[cyphr(query = "MATCH (u:User) RETURN count(u)")]
+struct CountUsers;
+
+fn row_type<Q: CyphrQueryDefTrait>(_: &Q) -> &'static str {
+    std::any::type_name::<Q::Row>()
+}
+
+#[test]
+fn test_query_def_text_and_params() {
+    assert_eq!(FindUser::QUERY, "MATCH (u:User {id: $id}) RETURN u.name AS name");
+    let q = FindUser { id: 7 }.query();
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["id"]);
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_query_def_raw_ident_param() {
+    let q = FindByType { r#type: "admin".into() }.query();
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), ["type"]);
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_query_def_renames_and_skips() {
+    let q = FindAdults { email: "a@b.c".into(), min_age: 18, note: "ignored" }.query();
+    let mut keys: Vec<&str> = q.param_keys().collect();
+    keys.sort();
+    assert_eq!(keys, ["email_address", "min_age"]);
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_query_def_row_types() {
+    assert!(row_type(&FindUser { id: 1 }).ends_with("UserRow"));
+    assert!(row_type(&CountUsers).ends_with("CyphrRow"));
+    assert_eq!(CountUsers.query().param_keys().count(), 0);
+}
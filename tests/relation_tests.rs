@@ -0,0 +1,109 @@
+use std::future::Future;
+use std::sync::Mutex;
+
+use cyphr::executor::Executor;
+use cyphr::prelude::*;
+use cyphr::query::CyphrQuery;
+use futures::executor::block_on;
+use neo4rs::BoltType;
+
+#[derive(CyphrNode)]
+#[cyphr(label = "User")]
+#[allow(dead_code)]
+struct User {
+    #[cyphr(id)]
+    id: i64,
+}
+
+#[derive(CyphrNode)]
+#[cyphr(label = "Blog Post")]
+#[allow(dead_code)]
+struct Post {
+    #[cyphr(id, prop = "slug")]
+    key: String,
+}
+
+#[derive(CyphrRelation, ToCyphrParams)]
+#[cyphr(type = "FOLLOWS", from = "User", to = "User", from_type = User, to_type = User)]
+struct Follows {
+    since: i64,
+}
+
+#[derive(CyphrRelation, ToCyphrParams)]
+#[cyphr(type = "WROTE", from = "User", to = "Blog Post", from_type = User, to_type = Post)]
+struct Wrote {}
+
+/// Records each query and answers every one with `n`.
+struct Recorder {
+    n: i64,
+    queries: Mutex<Vec<CyphrQuery>>,
+}
+
+impl Recorder {
+    fn answering(n: i64) -> Self {
+        Self { n, queries: Mutex::new(Vec::new()) }
+    }
+
+    fn texts(&self) -> Vec<String> {
+        self.queries.lock().unwrap().iter().map(|q| q.text().to_owned()).collect()
+    }
+
+    fn param(&self, index: usize, key: &str) -> BoltType {
+        self.queries.lock().unwrap()[index].param_value(key).unwrap().clone()
+    }
+}
+
+impl Executor for &Recorder {
+    fn fetch_rows(self, query: CyphrQuery) -> impl Future<Output = Result<Vec<CyphrRow>, CyphrError>> + Send {
+        self.queries.lock().unwrap().push(query);
+        let row: CyphrRow = vec![("n".to_string(), BoltType::from(self.n))].into_iter().collect();
+        std::future::ready(Ok(vec![row]))
+    }
+}
+
+#[test]
+fn test_connect() {
+    let db = Recorder::answering(1);
+    assert!(block_on(Follows { since: 2024 }.connect::<User, User>(&db, 1, 2)).unwrap());
+    assert_eq!(db.texts(), [
+        "MATCH (a:User {id: $from}), (b:User {id: $to}) CREATE (a)-[r:FOLLOWS]->(b) SET r = $props RETURN count(r) AS n",
+    ]);
+    assert_eq!(db.param(0, "from"), BoltType::from(1));
+    assert_eq!(db.param(0, "to"), BoltType::from(2));
+    let BoltType::Map(props) = db.param(0, "props") else { panic!("props should be a map") };
+    assert_eq!(props.get::<i64>("since").unwrap(), 2024);
+}
+
+#[test]
+fn test_connect_missing_endpoint() {
+    let db = Recorder::answering(0);
+    assert!(!block_on(Follows { since: 2024 }.connect::<User, User>(&db, 1, 99)).unwrap());
+}
+
+#[test]
+fn test_merge_between() {
+    let db = Recorder::answering(1);
+    assert!(block_on(Wrote {}.merge_between::<User, Post>(&db, 1, "hello-world")).unwrap());
+    assert_eq!(db.texts(), [
+        "MATCH (a:User {id: $from}), (b:`Blog Post` {slug: $to}) MERGE (a)-[r:WROTE]->(b) SET r += $props RETURN count(r) AS n",
+    ]);
+    assert_eq!(db.param(0, "to"), BoltType::from("hello-world"));
+}
+
+#[test]
+fn test_disconnect() {
+    let db = Recorder::answering(2);
+    assert_eq!(block_on(Follows::disconnect::<User, User>(&db, 1, 2)).unwrap(), 2);
+    assert_eq!(db.texts(), [
+        "MATCH (a:User {id: $from})-[r:FOLLOWS]->(b:User {id: $to}) DELETE r RETURN count(*) AS n",
+    ]);
+}
+
+#[test]
+fn test_exists_between() {
+    let db = Recorder::answering(0);
+    assert!(!block_on(Wrote::exists_between::<User, Post>(&db, 1, "x")).unwrap());
+    assert_eq!(db.texts(), [
+        "MATCH (a:User {id: $from})-[r:WROTE]->(b:`Blog Post` {slug: $to}) RETURN count(r) AS n",
+    ]);
+}
@@ -0,0 +1,167 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Mutex;
+
+use cyphr::executor::Executor;
+use cyphr::prelude::*;
+use cyphr::query::CyphrQuery;
+use cyphr::repository::{NodeRepository, Page};
+use futures::executor::block_on;
+use neo4rs::BoltType;
+
+#[derive(Debug, PartialEq, CyphrNode, ToCyphrParams)]
+#[cyphr(label = "User")]
+struct User {
+    #[cyphr(id)]
+    id: i64,
+    email: String,
+}
+
+#[derive(Debug, CyphrNode)]
+#[cyphr(label = "Audit Log")]
+#[allow(dead_code)]
+struct AuditLog {
+    message: String,
+}
+
+/// A query's text and bound parameters.
+type Recorded = (String, Vec<(String, BoltType)>);
+
+/// Records each query and answers with canned rows.
+#[derive(Default)]
+struct Recorder {
+    queries: Mutex<Vec<Recorded>>,
+    replies: Mutex<VecDeque<Vec<CyphrRow>>>,
+}
+
+impl Recorder {
+    fn replying(replies: Vec<Vec<CyphrRow>>) -> Self {
+        Self { replies: Mutex::new(replies.into()), ..Self::default() }
+    }
+
+    fn texts(&self) -> Vec<String> {
+        self.queries.lock().unwrap().iter().map(|(text, _)| text.clone()).collect()
+    }
+
+    fn params(&self, index: usize) -> Vec<(String, BoltType)> {
+        self.queries.lock().unwrap()[index].1.clone()
+    }
+}
+
+impl Executor for &Recorder {
+    fn fetch_rows(self, query: CyphrQuery) -> impl Future<Output = Result<Vec<CyphrRow>, CyphrError>> + Send {
+        let params = query
+            .param_keys()
+            .map(|k| (k.to_owned(), query.param_value(k).unwrap().clone()))
+            .collect();
+        self.queries.lock().unwrap().push((query.text().to_owned(), params));
+        let rows = self.replies.lock().unwrap().pop_front().unwrap_or_default();
+        std::future::ready(Ok(rows))
+    }
+}
+
+fn user_row(id: i64, email: &str) -> CyphrRow {
+    let node = neo4rs::BoltNode::new(
+        neo4rs::BoltInteger::new(id),
+        vec![BoltType::from("User")].into(),
+        vec![
+            (neo4rs::BoltString::from("id"), BoltType::from(id)),
+            (neo4rs::BoltString::from("email"), BoltType::from(email)),
+        ]
+        .into_iter()
+        .collect(),
+    );
+    vec![("n".to_string(), BoltType::Node(node))].into_iter().collect()
+}
+
+fn count_row(column: &str, n: i64) -> CyphrRow {
+    vec![(column.to_string(), BoltType::from(n))].into_iter().collect()
+}
+
+#[test]
+fn test_find_by_id() {
+    let db = Recorder::replying(vec![vec![user_row(7, "a@b.c")], vec![]]);
+    let users = NodeRepository::<User>::new();
+
+    let found = block_on(users.find_by_id(&db, 7)).unwrap();
+    assert_eq!(found, Some(User { id: 7, email: "a@b.c".into() }));
+    assert_eq!(block_on(users.find_by_id(&db, 8)).unwrap(), None);
+
+    assert_eq!(db.texts()[0], "MATCH (n:User {id: $id}) RETURN n");
+    assert_eq!(db.params(1), [("id".to_string(), BoltType::from(8))]);
+}
+
+#[test]
+fn test_find_by_id_too_many() {
+    let db = Recorder::replying(vec![vec![user_row(7, "a"), user_row(7, "b")]]);
+    let err = block_on(NodeRepository::<User>::new().find_by_id(&db, 7)).unwrap_err();
+    assert!(matches!(err, CyphrError::TooManyRows { count: 2 }));
+}
+
+#[test]
+fn test_find_by_property() {
+    let db = Recorder::replying(vec![vec![user_row(1, "a@b.c"), user_row(2, "a@b.c")]]);
+    let found = block_on(NodeRepository::<User>::new().find_by(&db, user_props::EMAIL, "a@b.c")).unwrap();
+    assert_eq!(found.len(), 2);
+    assert_eq!(db.texts(), ["MATCH (n:User) WHERE n.email = $value RETURN n"]);
+
+    let db = Recorder::default();
+    block_on(NodeRepository::<User>::new().find_by(&db, "first name", "Al")).unwrap();
+    assert_eq!(db.texts(), ["MATCH (n:User) WHERE n.`first name` = $value RETURN n"]);
+}
+
+#[test]
+fn test_find_all_paged() {
+    let db = Recorder::default();
+    block_on(NodeRepository::<User>::new().find_all(&db, Page::new(0, 20).next())).unwrap();
+    block_on(NodeRepository::<AuditLog>::new().find_all(&db, Page::new(0, 5))).unwrap();
+    assert_eq!(db.texts(), [
+        "MATCH (n:User) RETURN n ORDER BY n.id SKIP $skip LIMIT $limit",
+        "MATCH (n:`Audit Log`) RETURN n ORDER BY elementId(n) SKIP $skip LIMIT $limit",
+    ]);
+    assert_eq!(db.params(0), [
+        ("skip".to_string(), BoltType::from(20)),
+        ("limit".to_string(), BoltType::from(20)),
+    ]);
+}
+
+#[test]
+fn test_count() {
+    let db = Recorder::replying(vec![vec![count_row("count", 3)]]);
+    assert_eq!(block_on(NodeRepository::<User>::new().count(&db)).unwrap(), 3);
+    assert_eq!(db.texts(), ["MATCH (n:User) RETURN count(n) AS count"]);
+}
+
+#[test]
+fn test_save_merges_on_id() {
+    let db = Recorder::replying(vec![vec![user_row(7, "new@b.c")]]);
+    let saved = block_on(NodeRepository::<User>::new().save(&db, User { id: 7, email: "new@b.c".into() })).unwrap();
+    assert_eq!(saved.email, "new@b.c");
+    assert_eq!(db.texts(), ["MERGE (n:User {id: $id}) SET n += $props RETURN n"]);
+
+    let params = db.params(0);
+    assert_eq!(params[0], ("id".to_string(), BoltType::from(7)));
+    let BoltType::Map(props) = &params[1].1 else { panic!("props should be a map") };
+    assert_eq!(props.value.len(), 1);
+    assert_eq!(props.get::<String>("email").unwrap(), "new@b.c");
+}
+
+#[test]
+fn test_delete() {
+    let db = Recorder::replying(vec![vec![count_row("deleted", 1)], vec![count_row("deleted", 0)]]);
+    let users = NodeRepository::<User>::new();
+    assert!(block_on(users.delete(&db, 7)).unwrap());
+    assert!(!block_on(users.detach_delete(&db, 8)).unwrap());
+    assert_eq!(db.texts(), [
+        "MATCH (n:User {id: $id}) DELETE n RETURN count(*) AS deleted",
+        "MATCH (n:User {id: $id}) DETACH DELETE n RETURN count(*) AS deleted",
+    ]);
+}
+
+#[test]
+fn test_requires_id_field() {
+    let db = Recorder::default();
+    let err = block_on(NodeRepository::<AuditLog>::new().delete(&db, 1)).unwrap_err();
+    assert_eq!(err.to_string(), "mapping error: Audit Log has no `#[cyphr(id)]` field");
+    assert!(db.texts().is_empty());
+}
@@ -0,0 +1,267 @@
+use cyphr::prelude::*;
+use cyphr::schema::{
+    compare, ConstraintRow, IndexRow, LiveSchema, PropertyRow, SchemaDef, SchemaIssue, SchemaKind, SchemaTarget,
+};
+
+#[derive(CyphrNode)]
+#[cyphr(label = "User")]
+#[allow(dead_code)]
+struct User {
+    #[cyphr(id, unique)]
+    id: i64,
+    #[cyphr(prop = "email_address", unique, required)]
+    email: String,
+    #[cyphr(index, fulltext = "user_search")]
+    name: String,
+    #[cyphr(fulltext = "user_search")]
+    bio: String,
+}
+
+#[derive(CyphrNode)]
+#[cyphr(label = "Blog Post")]
+#[allow(dead_code)]
+struct Post {
+    #[cyphr(unique)]
+    slug: String,
+}
+
+#[derive(CyphrNode)]
+#[allow(dead_code)]
+struct Tag {
+    name: String,
+}
+
+#[derive(CyphrNode)]
+#[allow(dead_code)]
+struct Profile {
+    age: i32,
+    score: Option<f64>,
+    aliases: Vec<String>,
+    avatar: Vec<u8>,
+    active: bool,
+    extra: std::collections::HashMap<String, String>,
+}
+
+#[derive(CyphrRelation)]
+#[cyphr(type = "FOLLOWS")]
+#[allow(dead_code)]
+struct Follows {
+    #[cyphr(index)]
+    since: i64,
+    #[cyphr(unique)]
+    token: String,
+}
+
+#[test]
+fn test_node_schema() {
+    let def = User::schema();
+    assert_eq!(def.target, SchemaTarget::Node);
+    assert_eq!(def.name, "User");
+    assert_eq!(def.statements(), [
+        "CREATE CONSTRAINT User_id_unique IF NOT EXISTS FOR (n:User) REQUIRE n.id IS UNIQUE",
+        "CREATE CONSTRAINT User_email_address_unique IF NOT EXISTS FOR (n:User) REQUIRE n.email_address IS UNIQUE",
+        "CREATE CONSTRAINT User_email_address_required IF NOT EXISTS FOR (n:User) REQUIRE n.email_address IS NOT NULL",
+        "CREATE INDEX User_name_index IF NOT EXISTS FOR (n:User) ON (n.name)",
+        "CREATE FULLTEXT INDEX user_search IF NOT EXISTS FOR (n:User) ON EACH [n.name, n.bio]",
+    ]);
+}
+
+#[test]
+fn test_fulltext_grouped() {
+    let def = User::schema();
+    let fulltext: Vec<_> = def.items.iter().filter(|i| i.kind == SchemaKind::Fulltext).collect();
+    assert_eq!(fulltext.len(), 1);
+    assert_eq!(fulltext[0].properties, ["name", "bio"]);
+}
+
+#[test]
+fn test_relationship_schema() {
+    let def = Follows::schema();
+    assert_eq!(def.target, SchemaTarget::Relationship);
+    assert_eq!(def.statements(), [
+        "CREATE INDEX FOLLOWS_since_index IF NOT EXISTS FOR ()-[r:FOLLOWS]-() ON (r.since)",
+        "CREATE CONSTRAINT FOLLOWS_token_unique IF NOT EXISTS FOR ()-[r:FOLLOWS]-() REQUIRE r.token IS UNIQUE",
+    ]);
+}
+
+#[test]
+fn test_names_escaped() {
+    assert_eq!(Post::schema().statements(), [
+        "CREATE CONSTRAINT `Blog Post_slug_unique` IF NOT EXISTS FOR (n:`Blog Post`) REQUIRE n.slug IS UNIQUE",
+    ]);
+}
+
+#[test]
+fn test_no_schema_attrs() {
+    assert_eq!(Tag::schema(), SchemaDef::node("Tag").property("name", Some("String"), false));
+    assert!(Tag::schema().statements().is_empty());
+}
+
+#[test]
+fn test_property_types() {
+    let props: Vec<_> = Profile::schema()
+        .properties
+        .into_iter()
+        .map(|p| (p.name, p.neo4j_type, p.optional))
+        .collect();
+    assert_eq!(props, [
+        ("age".to_owned(), Some("Long".to_owned()), false),
+        ("score".to_owned(), Some("Double".to_owned()), true),
+        ("aliases".to_owned(), Some("StringArray".to_owned()), false),
+        ("avatar".to_owned(), Some("ByteArray".to_owned()), false),
+        ("active".to_owned(), Some("Boolean".to_owned()), false),
+        ("extra".to_owned(), None, false),
+    ]);
+}
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+fn node_prop(label: &str, property: &str, types: &[&str]) -> PropertyRow {
+    PropertyRow {
+        target: SchemaTarget::Node,
+        labels_or_types: strings(&[label]),
+        property: Some(property.to_owned()),
+        types: strings(types),
+        mandatory: true,
+    }
+}
+
+fn constraint(name: &str, kind: &str, label: &str, property: &str) -> ConstraintRow {
+    ConstraintRow {
+        name: name.to_owned(),
+        kind: kind.to_owned(),
+        entity_type: "NODE".to_owned(),
+        labels_or_types: strings(&[label]),
+        properties: strings(&[property]),
+    }
+}
+
+fn index(name: &str, kind: &str, label: &str, properties: &[&str], owner: Option<&str>) -> IndexRow {
+    IndexRow {
+        name: name.to_owned(),
+        kind: kind.to_owned(),
+        entity_type: "NODE".to_owned(),
+        labels_or_types: strings(&[label]),
+        properties: strings(properties),
+        owning_constraint: owner.map(str::to_owned),
+    }
+}
+
+/// A database holding exactly what `User` declares.
+fn user_db() -> LiveSchema {
+    LiveSchema {
+        constraints: vec![
+            constraint("c1", "UNIQUENESS", "User", "id"),
+            constraint("c2", "UNIQUENESS", "User", "email_address"),
+            constraint("c3", "NODE_PROPERTY_EXISTENCE", "User", "email_address"),
+        ],
+        indexes: vec![
+            index("c1", "RANGE", "User", &["id"], Some("c1")),
+            index("c2", "RANGE", "User", &["email_address"], Some("c2")),
+            index("i1", "RANGE", "User", &["name"], None),
+            index("user_search", "FULLTEXT", "User", &["name", "bio"], None),
+            index("labels", "LOOKUP", "", &[], None),
+        ],
+        properties: vec![
+            node_prop("User", "id", &["Long"]),
+            node_prop("User", "email_address", &["String"]),
+            node_prop("User", "name", &["String"]),
+            node_prop("User", "bio", &["String"]),
+        ],
+    }
+}
+
+#[test]
+fn test_compare_clean() {
+    let report = compare(&[User::schema()], &user_db());
+    assert!(report.is_clean(), "{:?}", report.issues());
+}
+
+#[test]
+fn test_compare_missing_and_extra() {
+    let mut db = user_db();
+    db.constraints.remove(2);
+    db.indexes.remove(2);
+    db.indexes.push(index("legacy", "TEXT", "User", &["bio"], None));
+    db.constraints.push(constraint("other", "UNIQUENESS", "Account", "id"));
+
+    let report = compare(&[User::schema()], &db);
+    assert_eq!(report.issues().len(), 3, "{:?}", report.issues());
+    assert!(matches!(
+        &report.issues()[0],
+        SchemaIssue::MissingConstraint { item, .. }
+            if item.kind == SchemaKind::Required && item.name == "User_email_address_required"
+    ));
+    assert!(matches!(
+        &report.issues()[1],
+        SchemaIssue::MissingIndex { item, .. } if item.name == "User_name_index"
+    ));
+    assert_eq!(report.issues()[2], SchemaIssue::ExtraIndex {
+        target: SchemaTarget::Node,
+        name: "User".into(),
+        index: "legacy".into(),
+    });
+    assert_eq!(report.issues()[2].to_string(), "undeclared index legacy on User");
+}
+
+#[test]
+fn test_compare_properties() {
+    let mut db = user_db();
+    db.properties[0] = node_prop("User", "id", &["Long", "String"]);
+    db.properties.remove(3);
+    db.properties.push(node_prop("User", "nickname", &["String"]));
+
+    let report = compare(&[User::schema()], &db);
+    assert_eq!(report.issues(), [
+        SchemaIssue::PropertyTypeMismatch {
+            target: SchemaTarget::Node,
+            name: "User".into(),
+            property: "id".into(),
+            expected: "Long".into(),
+            found: strings(&["Long", "String"]),
+        },
+        SchemaIssue::MissingProperty { target: SchemaTarget::Node, name: "User".into(), property: "bio".into() },
+        SchemaIssue::UndeclaredProperty {
+            target: SchemaTarget::Node,
+            name: "User".into(),
+            property: "nickname".into(),
+        },
+    ]);
+}
+
+#[test]
+fn test_compare_relationship_and_missing_label() {
+    let db = LiveSchema {
+        constraints: vec![ConstraintRow {
+            name: "t".into(),
+            kind: "RELATIONSHIP_UNIQUENESS".into(),
+            entity_type: "RELATIONSHIP".into(),
+            labels_or_types: strings(&["FOLLOWS"]),
+            properties: strings(&["token"]),
+        }],
+        indexes: vec![IndexRow {
+            name: "s".into(),
+            kind: "RANGE".into(),
+            entity_type: "RELATIONSHIP".into(),
+            labels_or_types: strings(&["FOLLOWS"]),
+            properties: strings(&["since"]),
+            owning_constraint: None,
+        }],
+        properties: ["since", "token"]
+            .iter()
+            .zip([["Long"], ["String"]])
+            .map(|(p, t)| PropertyRow {
+                target: SchemaTarget::Relationship,
+                labels_or_types: strings(&["FOLLOWS"]),
+                property: Some(p.to_string()),
+                types: strings(&t),
+                mandatory: true,
+            })
+            .collect(),
+    };
+    let report = compare(&[Follows::schema(), Tag::schema()], &db);
+    assert_eq!(report.issues(), [SchemaIssue::MissingLabel { target: SchemaTarget::Node, name: "Tag".into() }]);
+    assert_eq!(report.issues()[0].to_string(), "no nodes labelled Tag in the database");
+}
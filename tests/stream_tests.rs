@@ -0,0 +1,132 @@
+use std::collections::VecDeque;
+use cyphr::prelude::*;
+use futures::executor::block_on;
+use futures::{StreamExt, TryStreamExt};
+use neo4rs::{BoltList, BoltType, Row};
+
+#[derive(Debug, FromCyphr)]
+struct NameRow {
+    name: String,
+}
+
+fn name_row(name: &str) -> Row {
+    Row::new(
+        BoltList::from(vec![BoltType::from("name")]),
+        BoltList::from(vec![BoltType::from(name)]),
+    )
+}
+
+fn bad_row() -> Row {
+    Row::new(
+        BoltList::from(vec![BoltType::from("name")]),
+        BoltList::from(vec![BoltType::from(1)]),
+    )
+}
+
+fn names(n: usize) -> Vec<Row> {
+    (0..n).map(|i| name_row(&format!("user{i}"))).collect()
+}
+
+#[test]
+fn test_stream_inherent_next() {
+    let mut stream = CyphrStream::<NameRow>::from_rows(names(2));
+    block_on(async {
+        assert_eq!(stream.next().await.unwrap().unwrap().name, "user0");
+        assert_eq!(stream.next().await.unwrap().unwrap().name, "user1");
+        assert!(stream.next().await.is_none());
+    });
+}
+
+#[test]
+fn test_stream_with_stream_ext() {
+    let stream = CyphrStream::<NameRow>::from_rows(names(3));
+    let out: Vec<String> = block_on(stream.map_ok(|r| r.name).try_collect()).unwrap();
+    assert_eq!(out, vec!["user0", "user1", "user2"]);
+}
+
+#[test]
+fn test_stream_chunks() {
+    let stream = CyphrStream::<NameRow>::from_rows(names(5));
+    let batches: Vec<_> = block_on(stream.chunks(2).collect());
+    let sizes: Vec<usize> = batches.into_iter().map(|b| b.unwrap().len()).collect();
+    assert_eq!(sizes, vec![2, 2, 1]);
+}
+
+#[test]
+fn test_stream_chunks_error() {
+    let stream = CyphrStream::<NameRow>::from_rows(vec![name_row("a"), bad_row(), name_row("b")]);
+    let batches: Vec<_> = block_on(stream.chunks(2).collect());
+    // The row buffered before the error is not lost.
+    assert_eq!(batches.len(), 3);
+    assert!(matches!(&batches[0], Ok(rows) if rows.len() == 1 && rows[0].name == "a"));
+    assert!(matches!(batches[1], Err(CyphrError::Context { .. })));
+    assert!(matches!(&batches[2], Ok(rows) if rows.len() == 1 && rows[0].name == "b"));
+}
+
+#[test]
+fn test_stream_chunks_error_at_boundary() {
+    let stream = CyphrStream::<NameRow>::from_rows(vec![name_row("a"), name_row("b"), bad_row()]);
+    let batches: Vec<_> = block_on(stream.chunks(2).collect());
+    assert_eq!(batches.len(), 2);
+    assert!(matches!(&batches[0], Ok(rows) if rows.len() == 2));
+    assert!(batches[1].is_err());
+}
+
+#[test]
+fn test_stream_try_for_each_concurrent() {
+    let seen = std::sync::Mutex::new(Vec::new());
+    let stream = CyphrStream::<NameRow>::from_rows(names(4));
+    block_on(stream.try_for_each_concurrent(2, |row| {
+        seen.lock().unwrap().push(row.name);
+        async { Ok(()) }
+    }))
+    .unwrap();
+    assert_eq!(seen.into_inner().unwrap().len(), 4);
+}
+
+#[test]
+fn test_stream_collect_into() {
+    let stream = CyphrStream::<NameRow>::from_rows(names(3));
+    let out: VecDeque<NameRow> = block_on(stream.collect_into()).unwrap();
+    assert_eq!(out.len(), 3);
+    assert_eq!(out[2].name, "user2");
+}
+
+#[test]
+fn test_stream_collect_into_error() {
+    let stream = CyphrStream::<NameRow>::from_rows(vec![name_row("a"), bad_row()]);
+    let err = block_on(stream.collect_into::<Vec<NameRow>>()).unwrap_err();
+    assert!(err.to_string().contains("NameRow::name"));
+}
+
+#[test]
+fn test_stream_at_most_one() {
+    let none = block_on(CyphrStream::<NameRow>::from_rows(names(0)).at_most_one()).unwrap();
+    assert!(none.is_none());
+
+    let one = block_on(CyphrStream::<NameRow>::from_rows(names(1)).at_most_one()).unwrap();
+    assert_eq!(one.unwrap().name, "user0");
+
+    let err = block_on(CyphrStream::<NameRow>::from_rows(names(2)).at_most_one()).unwrap_err();
+    assert!(matches!(err, CyphrError::TooManyRows { count: 2 }), "{err}");
+}
+
+#[test]
+fn test_stream_exactly_one() {
+    let err = block_on(CyphrStream::<NameRow>::from_rows(names(0)).exactly_one()).unwrap_err();
+    assert!(matches!(err, CyphrError::NoRows), "{err}");
+
+    let one = block_on(CyphrStream::<NameRow>::from_rows(names(1)).exactly_one()).unwrap();
+    assert_eq!(one.name, "user0");
+
+    let err = block_on(CyphrStream::<NameRow>::from_rows(names(2)).exactly_one()).unwrap_err();
+    assert!(matches!(err, CyphrError::TooManyRows { count: 2 }), "{err}");
+}
+
+#[test]
+fn test_stream_at_most_one_counts_before_mapping() {
+    // A bad second row still reports the row count, not a mapping error.
+    let stream = CyphrStream::<NameRow>::from_rows(vec![name_row("a"), bad_row(), bad_row()]);
+    let err = block_on(stream.at_most_one()).unwrap_err();
+    assert!(matches!(err, CyphrError::TooManyRows { count: 3 }), "{err}");
+}
@@ -0,0 +1,146 @@
+use cyphr::prelude::*;
+use futures::executor::block_on;
+use neo4rs::{BoltList, BoltType, Row};
+
+#[derive(Debug, CyphrNode)]
+#[cyphr(label = "User")]
+struct User {
+    name: String,
+}
+
+#[derive(Debug, CyphrRelation)]
+#[cyphr(type = "FOLLOWS", from = "User", to = "User")]
+struct Follows {
+    since: i64,
+}
+
+fn user(id: i64, name: &str) -> BoltType {
+    BoltType::Node(neo4rs::BoltNode::new(
+        neo4rs::BoltInteger::new(id),
+        vec![BoltType::from("User")].into(),
+        vec![(neo4rs::BoltString::from("name"), BoltType::from(name))].into_iter().collect(),
+    ))
+}
+
+fn follows(id: i64, start: i64, end: i64) -> BoltType {
+    BoltType::Relation(neo4rs::BoltRelation {
+        id: neo4rs::BoltInteger::new(id),
+        start_node_id: neo4rs::BoltInteger::new(start),
+        end_node_id: neo4rs::BoltInteger::new(end),
+        typ: neo4rs::BoltString::from("FOLLOWS"),
+        properties: vec![(neo4rs::BoltString::from("since"), BoltType::from(2020 + id))].into_iter().collect(),
+    })
+}
+
+/// `RETURN collect(n) AS nodes, collect(r) AS rels`
+fn collected(nodes: Vec<BoltType>, rels: Vec<BoltType>) -> Row {
+    Row::new(
+        BoltList::from(vec![BoltType::from("nodes"), BoltType::from("rels")]),
+        BoltList::from(vec![BoltType::List(nodes.into()), BoltType::List(rels.into())]),
+    )
+}
+
+type Follow = Subgraph<User, Follows>;
+
+fn names<'a>(nodes: impl Iterator<Item = (i64, &'a User)>) -> Vec<&'a str> {
+    let mut names: Vec<&str> = nodes.map(|(_, u)| u.name.as_str()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_subgraph_dedups_across_rows() {
+    // ann -> bob, bob -> cat, cat -> ann; bob and the first edge appear twice
+    let rows = vec![
+        collected(vec![user(1, "ann"), user(2, "bob")], vec![follows(10, 1, 2)]),
+        collected(vec![user(2, "bob"), user(3, "cat")], vec![follows(10, 1, 2), follows(11, 2, 3)]),
+        collected(vec![], vec![follows(12, 3, 1)]),
+    ];
+    let graph: Follow = block_on(CyphrStream::<Follow>::from_rows(rows).collect_into()).unwrap();
+
+    assert_eq!(graph.node_count(), 3);
+    assert_eq!(graph.edge_count(), 3);
+    assert_eq!(graph.node(2).unwrap().name, "bob");
+    assert_eq!(graph.edge(11).unwrap().rel.since, 2031);
+}
+
+#[test]
+fn test_subgraph_adjacency() {
+    let row = collected(
+        vec![user(1, "ann"), user(2, "bob"), user(3, "cat")],
+        vec![follows(10, 1, 2), follows(11, 2, 1), follows(12, 3, 1)],
+    );
+    let graph = Follow::from_record(&row).unwrap();
+
+    let out: Vec<i64> = graph.out_edges(1).map(|e| e.end).collect();
+    assert_eq!(out, [2]);
+    let mut inc: Vec<i64> = graph.in_edges(1).map(|e| e.start).collect();
+    inc.sort();
+    assert_eq!(inc, [2, 3]);
+    assert_eq!(names(graph.neighbors(1)), ["bob", "cat"]);
+    assert_eq!(graph.out_edges(4).count(), 0);
+}
+
+#[test]
+fn test_subgraph_neighbors_skip_missing_nodes() {
+    let row = collected(vec![user(1, "ann")], vec![follows(10, 1, 2)]);
+    let graph = Follow::from_record(&row).unwrap();
+    assert_eq!(graph.out_edges(1).count(), 1);
+    assert_eq!(graph.neighbors(1).count(), 0);
+}
+
+#[test]
+fn test_subgraph_from_paths() {
+    let path = |nodes: Vec<BoltType>, ids: Vec<i64>, indices: Vec<i64>| {
+        let rels: Vec<BoltType> = ids
+            .into_iter()
+            .map(|id| {
+                BoltType::UnboundedRelation(neo4rs::BoltUnboundedRelation::new(
+                    neo4rs::BoltInteger::new(id),
+                    neo4rs::BoltString::from("FOLLOWS"),
+                    vec![(neo4rs::BoltString::from("since"), BoltType::from(2020))].into_iter().collect(),
+                ))
+            })
+            .collect();
+        let p = BoltType::Path(neo4rs::BoltPath {
+            nodes: nodes.into(),
+            rels: rels.into(),
+            indices: indices.into_iter().map(BoltType::from).collect::<Vec<_>>().into(),
+        });
+        Row::new(BoltList::from(vec![BoltType::from("p")]), BoltList::from(vec![p]))
+    };
+    // (ann)-[10]->(bob) and (cat)<-[11]-(bob)<-[10]-(ann), written from cat
+    let rows = [
+        path(vec![user(1, "ann"), user(2, "bob")], vec![10], vec![1, 1]),
+        path(vec![user(3, "cat"), user(2, "bob"), user(1, "ann")], vec![11, 10], vec![-1, 1, -2, 2]),
+    ];
+    let graph: Follow = rows.iter().map(|row| Follow::from_record(row).unwrap()).collect();
+
+    assert_eq!(graph.node_count(), 3);
+    assert_eq!(graph.edge_count(), 2);
+    let edge = graph.edge(11).unwrap();
+    assert_eq!((edge.start, edge.end), (2, 3));
+    assert_eq!(names(graph.neighbors(2)), ["ann", "cat"]);
+}
+
+#[test]
+fn test_subgraph_skips_scalars_and_nulls() {
+    let row = Row::new(
+        BoltList::from(vec![BoltType::from("n"), BoltType::from("score"), BoltType::from("r")]),
+        BoltList::from(vec![user(1, "ann"), BoltType::from(0.5), BoltType::Null(neo4rs::BoltNull)]),
+    );
+    let graph = Follow::from_record(&row).unwrap();
+    assert_eq!(graph.node_count(), 1);
+    assert_eq!(graph.edge_count(), 0);
+}
+
+#[test]
+fn test_subgraph_decode_error() {
+    let bad = BoltType::Node(neo4rs::BoltNode::new(
+        neo4rs::BoltInteger::new(1),
+        vec![BoltType::from("User")].into(),
+        Default::default(),
+    ));
+    let err = Follow::from_record(&collected(vec![bad], vec![])).unwrap_err();
+    assert!(err.to_string().starts_with("Subgraph (column 'nodes'): "), "{err}");
+}
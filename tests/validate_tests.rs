@@ -0,0 +1,122 @@
+use cyphr::query::CyphrQuery;
+use cyphr::{cypher_query, CyphrError, ToCyphrParams};
+use cyphr_core::record::CyphrRow;
+use futures::executor::block_on;
+use neo4rs::Graph;
+
+fn mismatch(q: &CyphrQuery) -> (Vec<String>, Vec<String>) {
+    match q.validate() {
+        Err(CyphrError::ParamMismatch { missing, unused }) => (missing, unused),
+        other => panic!("expected ParamMismatch, got: {other:?}"),
+    }
+}
+
+#[test]
+fn test_validate_ok() {
+    let q = CyphrQuery::new("MATCH (u:User {name: $name}) WHERE u.age > $age RETURN u")
+        .param("name", "Alice")
+        .param("age", 30_i64);
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_validate_missing_and_unused() {
+    let q = CyphrQuery::new("MATCH (u:User {name: $name}) WHERE u.age > $age RETURN u")
+        .param("name", "Alice")
+        .param("agee", 30_i64);
+    let (missing, unused) = mismatch(&q);
+    assert_eq!(missing, vec!["age"]);
+    assert_eq!(unused, vec!["agee"]);
+}
+
+#[test]
+fn test_validate_repeated_placeholder() {
+    let q = CyphrQuery::new("MATCH (u {id: $id}) WHERE u.parent <> $id RETURN u")
+        .param("id", 1_i64);
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_validate_skips_string_literals() {
+    let q = CyphrQuery::new(r#"RETURN '$a', "$b", 'it\'s $c', $d AS d"#).param("d", 1_i64);
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_validate_skips_comments() {
+    let q = CyphrQuery::new("MATCH (u) // WHERE u.id = $old\n/* $also\n$gone */ RETURN u");
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_validate_skips_quoted_identifiers() {
+    let q = CyphrQuery::new("MATCH (u:`$Weird`) RETURN u.`$prop`");
+    assert!(q.validate().is_ok());
+}
+
+#[test]
+fn test_validate_quoted_and_numeric_params() {
+    let q = CyphrQuery::new("RETURN $`first name`, $0, $straße");
+    let (missing, unused) = mismatch(&q);
+    assert_eq!(missing, vec!["first name", "0", "straße"]);
+    assert!(unused.is_empty());
+}
+
+#[test]
+fn test_validate_lone_dollar_is_not_a_param() {
+    let q = CyphrQuery::new("RETURN 1 $ 2");
+    assert!(q.validate().is_ok());
+}
+
+#[derive(ToCyphrParams)]
+struct CreateUser {
+    name: String,
+    age: i64,
+    email: String,
+}
+
+#[test]
+fn test_validate_params_from() {
+    let q = CyphrQuery::new("CREATE (u:User {name: $name, age: $age})")
+        .params_from(CreateUser { name: "Alice".into(), age: 30, email: "a@b.c".into() });
+    let (missing, unused) = mismatch(&q);
+    assert!(missing.is_empty());
+    assert_eq!(unused, vec!["email"]);
+}
+
+#[test]
+fn test_validate_cypher_query_macro() {
+    let name = "Alice";
+    let q = cypher_query! { MATCH (u:User {name: $name}) RETURN u };
+    assert!(q.validate().is_ok());
+    assert_eq!(q.param_keys().collect::<Vec<_>>(), vec!["name"]);
+}
+
+#[test]
+fn test_param_mismatch_display() {
+    let err = CyphrError::ParamMismatch {
+        missing: vec!["a".into(), "b".into()],
+        unused: vec!["c".into()],
+    };
+    assert_eq!(err.to_string(), "parameter mismatch: missing [a, b], unused [c]");
+}
+
+#[test]
+fn test_validation_defaults_to_debug_builds() {
+    let q = CyphrQuery::new("MATCH (u:User {id: $id}) RETURN u").param("id", 1).param("extra", 2);
+    assert_eq!(q.is_checked(), cfg!(debug_assertions));
+    assert!(!q.checked().unchecked().is_checked());
+}
+
+#[test]
+fn test_checked_query_is_not_executed() {
+    // The pool connects lazily, so nothing is listening here.
+    let graph = block_on(Graph::new("127.0.0.1:1", "neo4j", "neo4j")).unwrap();
+    let q = CyphrQuery::new("MATCH (u:User {id: $id}) RETURN u").param("idd", 1).checked();
+    match block_on(q.fetch_all::<CyphrRow>(&graph)) {
+        Err(CyphrError::ParamMismatch { missing, unused }) => {
+            assert_eq!((missing, unused), (vec!["id".to_string()], vec!["idd".to_string()]));
+        }
+        other => panic!("expected ParamMismatch, got: {:?}", other.map(|rows| rows.len())),
+    }
+}